@@ -0,0 +1,206 @@
+//! Moments worth interrupting the player for
+//!
+//! The watcher compares the household with how it looked on the previous
+//! tick and reports each problem once, when it starts.
+
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+use crate::household::Household;
+use crate::i18n::tr;
+use crate::pet::{LifeStage, Pet};
+
+/// Egg health below this is an emergency
+pub const EGG_DANGER: u8 = 30;
+
+/// Kinds of alert, so each can be switched on or off
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertKind {
+    /// Hunger hit zero
+    Starving,
+    /// Health fell below 20
+    Critical,
+    /// An egg is close to failing
+    EggDanger,
+    /// The pet got sick
+    Sick,
+    /// A random event happened
+    Event,
+}
+
+impl AlertKind {
+    /// Every kind of alert
+    pub const ALL: [AlertKind; 5] = [
+        AlertKind::Starving,
+        AlertKind::Critical,
+        AlertKind::EggDanger,
+        AlertKind::Sick,
+        AlertKind::Event,
+    ];
+
+    /// Whether the alert needs care right away, rather than being news
+    pub fn is_urgent(self) -> bool {
+        self != AlertKind::Event
+    }
+}
+
+/// Something that just happened
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alert {
+    pub kind: AlertKind,
+    /// Name of the pet it is about
+    pub name: String,
+    /// What happened, in words
+    pub message: String,
+}
+
+/// A pet's problems at the last look
+#[derive(Debug, Clone)]
+struct Watched {
+    /// Pets are told apart by when they were born
+    born: Instant,
+    problems: Vec<AlertKind>,
+    /// Newest random event already reported
+    last_event: Option<Instant>,
+}
+
+/// Turns changes in the household into alerts
+#[derive(Debug, Default)]
+pub struct AlertWatcher {
+    /// Every pet at the last look; `None` before the first one
+    seen: Option<Vec<Watched>>,
+}
+
+impl AlertWatcher {
+    /// Problems that started, and events that happened, since the last call
+    ///
+    /// The first call only takes note of how things are.
+    pub fn observe(&mut self, household: &Household) -> Vec<Alert> {
+        let now: Vec<Watched> = household
+            .pets
+            .iter()
+            .map(|companion| Watched {
+                born: companion.pet.birth_time,
+                problems: problems(&companion.pet),
+                last_event: companion
+                    .event_system
+                    .event_history
+                    .last()
+                    .map(|event| event.timestamp),
+            })
+            .collect();
+        let Some(before) = self.seen.replace(now.clone()) else {
+            return Vec::new();
+        };
+
+        let mut alerts = Vec::new();
+        for (companion, watched) in household.pets.iter().zip(&now) {
+            let pet = &companion.pet;
+            let old = before.iter().find(|old| old.born == watched.born);
+            for &kind in &watched.problems {
+                if old.is_none_or(|old| !old.problems.contains(&kind)) {
+                    alerts.push(Alert {
+                        kind,
+                        name: pet.name.clone(),
+                        message: problem_message(kind, pet),
+                    });
+                }
+            }
+
+            // Pets that just arrived have nothing to report yet
+            let Some(old) = old else {
+                continue;
+            };
+            let new_events = companion
+                .event_system
+                .event_history
+                .iter()
+                .filter(|event| old.last_event.is_none_or(|at| event.timestamp > at));
+            for event in new_events {
+                alerts.push(Alert {
+                    kind: AlertKind::Event,
+                    name: pet.name.clone(),
+                    message: event.message.clone(),
+                });
+            }
+        }
+        alerts
+    }
+}
+
+/// Problems a pet has right now
+fn problems(pet: &Pet) -> Vec<AlertKind> {
+    if pet.stage == LifeStage::Egg {
+        let in_danger = !pet.is_egg_dead() && pet.get_egg_health() < EGG_DANGER;
+        return if in_danger {
+            vec![AlertKind::EggDanger]
+        } else {
+            Vec::new()
+        };
+    }
+    if !pet.state.is_alive() {
+        return Vec::new();
+    }
+
+    let mut problems = Vec::new();
+    if pet.stats.is_starving() {
+        problems.push(AlertKind::Starving);
+    }
+    if pet.stats.is_critical() {
+        problems.push(AlertKind::Critical);
+    }
+    if pet.state.is_sick() {
+        problems.push(AlertKind::Sick);
+    }
+    problems
+}
+
+/// What to say about a problem
+fn problem_message(kind: AlertKind, pet: &Pet) -> String {
+    let name = &pet.name;
+    match kind {
+        AlertKind::Starving => tr!("status.starving", name = name),
+        AlertKind::Critical => tr!("alert.critical", name = name),
+        AlertKind::EggDanger => tr!("alert.egg_danger", name = name),
+        AlertKind::Sick => tr!("journal.got_sick", name = name),
+        AlertKind::Event => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{EventType, GameEvent};
+    use crate::stats::StatValue;
+
+    #[test]
+    fn alerts_once_when_problems_start() {
+        let mut household = Household::new(Pet::default());
+        let mut watcher = AlertWatcher::default();
+        assert!(watcher.observe(&household).is_empty());
+
+        let pet = &mut household.selected_mut().pet;
+        pet.stage = LifeStage::Adult;
+        pet.stats.hunger = StatValue::new(0);
+        pet.stats.health = StatValue::new(10);
+        let kinds: Vec<AlertKind> = watcher
+            .observe(&household)
+            .into_iter()
+            .map(|alert| alert.kind)
+            .collect();
+        assert_eq!(kinds, [AlertKind::Starving, AlertKind::Critical]);
+        assert!(watcher.observe(&household).is_empty());
+
+        household.selected_mut().event_system.remember(GameEvent {
+            event_type: EventType::FoundTreasure,
+            timestamp: Instant::now(),
+            message: "Fluffy found a shiny pebble".to_string(),
+        });
+        let alerts = watcher.observe(&household);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].kind, AlertKind::Event);
+        assert_eq!(alerts[0].message, "Fluffy found a shiny pebble");
+    }
+}
@@ -2,7 +2,7 @@ use std::collections::VecDeque;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-use super::frame::{AnimationFrame, Particle};
+use super::frame::{AnimationFrame, ArtColor, Particle};
 use super::loader::FrameCache;
 use super::types::{AnimationPriority, AnimationType};
 
@@ -100,6 +100,20 @@ impl AnimationEngine {
     }
 
     pub fn request(&mut self, anim_type: AnimationType) {
+        // Effects are particle bursts layered over the current animation
+        if anim_type.priority() == AnimationPriority::Background {
+            for spec in self.frame_cache.effect_particles(anim_type) {
+                self.particles.push(Particle::new(spec, 0, 0));
+            }
+            return;
+        }
+
+        // Re-requesting what is already playing or queued is a no-op
+        let already_pending = self.queue.iter().any(|r| r.anim_type == anim_type);
+        if already_pending || self.current_type() == Some(anim_type) {
+            return;
+        }
+
         let request = AnimationRequest {
             anim_type,
             priority: anim_type.priority(),
@@ -162,10 +176,10 @@ impl AnimationEngine {
     }
 
     fn start_next_from_queue(&mut self) {
-        if self.current.is_none() {
-            if let Some(request) = self.queue.pop_front() {
-                self.start_animation(request);
-            }
+        if self.current.is_none()
+            && let Some(request) = self.queue.pop_front()
+        {
+            self.start_animation(request);
         }
 
         if self.current.is_none() {
@@ -187,7 +201,6 @@ impl AnimationEngine {
             .map(|anim| anim.current_frame_ref().art.as_slice())
     }
 
-    #[allow(dead_code)]
     pub fn current_type(&self) -> Option<AnimationType> {
         self.current.as_ref().map(|anim| anim.anim_type)
     }
@@ -196,7 +209,7 @@ impl AnimationEngine {
         &self.particles
     }
 
-    pub fn current_color(&self) -> Option<ratatui::style::Color> {
+    pub fn current_color(&self) -> Option<ArtColor> {
         self.current
             .as_ref()
             .and_then(|anim| anim.current_frame_ref().color_override)
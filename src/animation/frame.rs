@@ -1,11 +1,22 @@
-use ratatui::style::Color;
 use std::time::Duration;
 
+/// Color roles used by animation frames and particles, resolved through
+/// the active theme when the pet is drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArtColor {
+    Sleeping,
+    Hearts,
+    Food,
+    Sparkles,
+    Zzz,
+    Sweat,
+}
+
 #[derive(Debug, Clone)]
 pub struct AnimationFrame {
     pub art: Vec<String>,
     pub duration: Duration,
-    pub color_override: Option<Color>,
+    pub color_override: Option<ArtColor>,
     pub particles: Vec<ParticleSpec>,
 }
 
@@ -24,7 +35,7 @@ impl AnimationFrame {
         self
     }
 
-    pub fn with_color(mut self, color: Color) -> Self {
+    pub fn with_color(mut self, color: ArtColor) -> Self {
         self.color_override = Some(color);
         self
     }
@@ -38,7 +49,7 @@ pub struct ParticleSpec {
     pub vx: f32,
     pub vy: f32,
     pub lifetime_ms: u64,
-    pub color: Color,
+    pub color: ArtColor,
 }
 
 #[derive(Debug, Clone)]
@@ -69,7 +80,9 @@ impl Particle {
         self.birth_time.elapsed() < Duration::from_millis(self.spec.lifetime_ms)
     }
 
-    pub fn position(&self) -> (u16, u16) {
-        (self.x as u16, self.y as u16)
+    /// Position relative to the top-left of the pet art; may be negative
+    /// when the particle has drifted above or left of the art
+    pub fn position(&self) -> (i16, i16) {
+        (self.x.round() as i16, self.y.round() as i16)
     }
 }
@@ -1,9 +1,7 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use ratatui::style::Color;
-
-use super::frame::AnimationFrame;
+use super::frame::{AnimationFrame, ArtColor, ParticleSpec};
 use super::types::AnimationType;
 
 pub struct FrameCache {
@@ -99,7 +97,7 @@ impl FrameCache {
                 "  > ^ <  ".to_string(),
             ])
             .with_duration(800)
-            .with_color(Color::DarkGray)]),
+            .with_color(ArtColor::Sleeping)]),
         );
 
         cache.insert(
@@ -493,6 +491,33 @@ impl FrameCache {
         );
     }
 
+    /// Particles spawned by an effect animation, drawn over whatever the
+    /// pet is currently doing
+    pub fn effect_particles(&self, anim_type: AnimationType) -> Vec<ParticleSpec> {
+        let (symbol, color) = match anim_type {
+            AnimationType::EffectHearts => ('♥', ArtColor::Hearts),
+            AnimationType::EffectFood => ('*', ArtColor::Food),
+            AnimationType::EffectSparkles => ('✦', ArtColor::Sparkles),
+            AnimationType::EffectZzz => ('z', ArtColor::Zzz),
+            AnimationType::EffectSweat => ('\'', ArtColor::Sweat),
+            _ => return Vec::new(),
+        };
+
+        let lifetime_ms = anim_type.duration_ms();
+        [(1, -0.6), (4, 0.0), (7, 0.6)]
+            .into_iter()
+            .map(|(x_offset, vx)| ParticleSpec {
+                symbol,
+                x_offset,
+                y_offset: 0,
+                vx,
+                vy: -1.5,
+                lifetime_ms,
+                color,
+            })
+            .collect()
+    }
+
     fn fallback_frame() -> AnimationFrame {
         AnimationFrame::new(vec![
             "  /\\_/\\  ".to_string(),
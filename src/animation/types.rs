@@ -7,6 +7,7 @@ pub enum AnimationPriority {
     Mood = 2,
     Action = 3,
     Transition = 4,
+    #[allow(dead_code)]
     Critical = 5,
 }
 
@@ -1,17 +1,48 @@
 //! Application state and main loop logic
 
-use std::time::Instant;
+use std::cell::RefCell;
+use std::fs;
+use std::io;
+use std::mem;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
+use ratatui::layout::{Position, Rect};
+use serde::{Deserialize, Serialize};
+
+use crate::alerts::AlertWatcher;
 use crate::animation::types::AnimationType;
-use crate::events::EventSystem;
-use crate::pet::{LifeStage, Pet, PetState};
-use crate::widgets::AnimatedPet;
+use crate::attention::Attention;
+use crate::client::{Client, ClientError, Update};
+use crate::daemon;
+use crate::hooks::Hooks;
+use crate::household::{Companion, Household, MAX_PETS};
+use crate::http::Bridge;
+use crate::i18n::{self, tr};
+use crate::journal::{Journal, JournalCategory};
+use crate::keymap::{Action, Keymap};
+use crate::mouse::HitMap;
+use crate::notify::Notifier;
+use crate::pet::{ActionError, LifeStage, Pet, PetState};
+use crate::prompt::PromptStatus;
+use crate::rpc::{ActionParams, RemoteAction, RemoteState, SetupChoices};
+use crate::save::{self, SaveError, SaveLock, SaveState};
+use crate::scripts::{Order, Scripts};
+use crate::settings::Settings;
+use crate::setup::{self, Setup};
+use crate::vacation::Vacation;
+use crate::views::{Navigation, View};
 
 /// Game state
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum GameState {
+    /// Choosing the name, species and difficulty of a new pet
+    Setup,
     /// Normal gameplay
     Playing,
+    /// Time stopped by the player
+    Paused,
     /// Egg died - game over
     GameOver,
 }
@@ -23,45 +54,145 @@ pub struct App {
     pub should_quit: bool,
     /// Current game state
     pub game_state: GameState,
-    /// The pet
-    pub pet: Pet,
+    /// The pets and which one is selected
+    pub household: Household,
     /// Last update time
     last_update: Instant,
     /// Status message
     pub status_message: String,
-    /// Event system for random occurrences
-    pub event_system: EventSystem,
-    /// Animation system
-    pub animated_pet: AnimatedPet,
+    /// Problems to point out, shown instead of the status until the player
+    /// does something
+    pub notice: Option<String>,
     /// Last animation update time
     last_animation_update: Instant,
+    /// Which view is shown
+    pub navigation: Navigation,
+    /// User settings
+    pub settings: Settings,
+    /// Active key bindings
+    pub keymap: Keymap,
+    /// Clickable regions of the last rendered frame
+    pub hit_map: RefCell<HitMap>,
+    /// Last known mouse position
+    pub hover: Option<Position>,
+    /// Persistent record of events, actions and state changes
+    pub journal: Journal,
+    /// Choices in the new-game wizard
+    pub setup: Setup,
+    /// When the game was paused, or the wizard opened for another pet
+    paused_since: Option<Instant>,
+    /// Pet-sitter mode, while it lasts
+    pub vacation: Option<Vacation>,
+    /// Where to leave the status for shell prompts, if anywhere
+    pub status_cache: Option<PathBuf>,
+    /// The daemon running the game, when this is only showing it
+    pub remote: Option<Client>,
+    /// Why the daemon refused the last action, until it is announced
+    pub refused: Option<String>,
+    /// Requests from the HTTP API, if it is enabled
+    pub http: Option<Bridge>,
+    /// Commands and webhooks run when something happens
+    pub hooks: Hooks,
+    /// Notices when a pet starts needing care
+    pub alerts: AlertWatcher,
+    /// Desktop notifications, if they are enabled
+    pub notifier: Notifier,
+    /// Bells and titles for when the terminal is in the background
+    pub attention: Attention,
+    /// Events and care bots added by scripts
+    pub scripts: Scripts,
+    /// Claim on the saved game while it runs here
+    pub save_lock: Option<SaveLock>,
 }
 
 impl App {
     /// Create a new application instance
     pub fn new() -> Self {
-        let pet = Pet::new("Fluffy");
+        let pet = Pet::default();
         let status = pet.status_message();
 
         Self {
             should_quit: false,
             game_state: GameState::Playing,
-            pet,
+            household: Household::new(pet),
             last_update: Instant::now(),
             status_message: status,
-            event_system: EventSystem::new(),
-            animated_pet: AnimatedPet::new(),
+            notice: None,
             last_animation_update: Instant::now(),
+            navigation: Navigation::new(),
+            settings: Settings::new(),
+            keymap: Keymap::new(),
+            hit_map: RefCell::new(HitMap::default()),
+            hover: None,
+            journal: Journal::in_memory(),
+            setup: Setup::new(),
+            paused_since: None,
+            vacation: None,
+            status_cache: None,
+            remote: None,
+            refused: None,
+            http: None,
+            hooks: Hooks::default(),
+            alerts: AlertWatcher::default(),
+            notifier: Notifier::default(),
+            attention: Attention::default(),
+            scripts: Scripts::default(),
+            save_lock: None,
         }
     }
 
+    /// The line under the pet: the notice if there is one, else the status
+    pub fn status_line(&self) -> &str {
+        self.notice.as_deref().unwrap_or(&self.status_message)
+    }
+
+    /// The selected pet with its systems
+    pub fn companion(&self) -> &Companion {
+        self.household.selected()
+    }
+
+    /// The selected pet, which actions apply to
+    pub fn pet(&self) -> &Pet {
+        &self.household.selected().pet
+    }
+
+    /// The selected pet, which actions apply to
+    pub fn pet_mut(&mut self) -> &mut Pet {
+        &mut self.household.selected_mut().pet
+    }
+
     /// Handle tick event (called periodically)
     pub fn tick(&mut self) {
-        // Update animation at 10 FPS
-        self.update_animation();
+        // Update animation at 10 FPS, frozen while paused
+        if self.game_state != GameState::Paused {
+            self.update_animation();
+        }
 
-        // Check for game over
-        if self.game_state == GameState::GameOver {
+        // Hooks and dashboards see the state as of the last tick; hooks run
+        // where the game does
+        if self.remote.is_none() {
+            let mut hooks = mem::take(&mut self.hooks);
+            hooks.check(self);
+            self.hooks = hooks;
+        }
+        let alerts = self.alerts.observe(&self.household);
+        self.attention.notice(&alerts);
+        if self.remote.is_none() {
+            self.notifier.send(&alerts);
+        }
+        if let Some(mut http) = self.http.take() {
+            http.answer(self);
+            self.http = Some(http);
+        }
+
+        // The daemon keeps the time
+        if self.remote.is_some() {
+            self.sync_remote();
+            return;
+        }
+
+        // Time stands still during setup and after game over
+        if self.game_state != GameState::Playing {
             return;
         }
 
@@ -69,68 +200,240 @@ impl App {
         let delta = now.duration_since(self.last_update);
         self.last_update = now;
 
-        // Update the pet
-        self.pet.update(delta);
+        let mut events = Vec::new();
+        let mut decayed = false;
+        for (index, companion) in self.household.pets.iter_mut().enumerate() {
+            // Update the pet
+            decayed |= companion.pet.update(delta);
+            companion.sample_history();
+            companion.journal_state_changes(&mut self.journal);
+
+            // Let the pet-sitter look after it
+            if let Some(vacation) = &self.vacation
+                && let Some(task) = vacation.care(&mut companion.pet)
+            {
+                self.journal.record(
+                    JournalCategory::Action,
+                    None,
+                    task.message(&companion.pet.name),
+                );
+                companion.sync_observed();
+            }
 
-        // Update animation based on pet state
-        self.update_pet_animation();
+            // Update animation based on pet state
+            companion.update_animation_state();
 
-        // Check if egg died
-        if self.pet.is_egg_dead() {
-            self.game_state = GameState::GameOver;
-            self.status_message = "The egg failed to hatch... Game Over!".to_string();
-            self.animated_pet.trigger(AnimationType::TransitionDie);
-            return;
+            // Update event system (only for hatched pets)
+            if companion.pet.stage != LifeStage::Egg {
+                companion.roll_event(index, &mut self.scripts);
+                companion.journal_state_changes(&mut self.journal);
+                events.extend(companion.event_system.pending_event.take());
+            }
+        }
+        self.end_vacation_when_due();
+        if decayed {
+            self.write_status_cache();
+            for (index, companion) in self.household.pets.iter().enumerate() {
+                self.scripts.update(index, &companion.pet);
+            }
+        }
+        let said = self.obey_scripts();
+
+        // Check if an egg died; the game is over once no pet is left
+        if let Some(index) = self
+            .household
+            .pets
+            .iter()
+            .position(|companion| companion.pet.is_egg_dead())
+        {
+            match self.household.remove(index) {
+                Some(lost) => self.journal.record(
+                    JournalCategory::System,
+                    None,
+                    tr!("journal.egg_lost", name = lost.pet.name),
+                ),
+                None => {
+                    self.game_state = GameState::GameOver;
+                    self.status_message = tr!("journal.game_over");
+                    self.journal
+                        .record(JournalCategory::System, None, self.status_message.clone());
+                    self.animate(AnimationType::TransitionDie);
+                    return;
+                }
+            }
         }
 
-        // Update event system (only for hatched pets)
-        if self.pet.stage != LifeStage::Egg {
-            self.event_system.update(&mut self.pet, delta);
+        events.extend(self.household.interact());
 
-            // Check for pending events and display them
-            if let Some(event) = self.event_system.pending_event.take() {
-                self.status_message = event.message;
-                return;
+        // Show the latest event, if any
+        for event in &events {
+            self.journal.record_event(event);
+        }
+        self.status_message = match events.pop() {
+            Some(event) => event.message,
+            None => self.pet().status_message(),
+        };
+        // Scripts speak last, like an event of their own
+        if let Some(message) = said {
+            self.status_message = message;
+        }
+    }
+
+    /// Carry out what scripts asked for, leaving the selection alone;
+    /// returns the last thing they said
+    fn obey_scripts(&mut self) -> Option<String> {
+        if self.scripts.orders.is_empty() {
+            return None;
+        }
+        let selected = self.household.selected_index();
+        let mut said = None;
+        for order in mem::take(&mut self.scripts.orders) {
+            match order {
+                Order::Say(message) => {
+                    self.journal
+                        .record(JournalCategory::Event, None, message.clone());
+                    said = Some(message);
+                }
+                Order::Act(pet, action) => {
+                    let params = ActionParams {
+                        action,
+                        pet: Some(pet),
+                        setup: None,
+                    };
+                    if let Err(refusal) = daemon::perform(self, params) {
+                        tracing::debug!("script action refused: {}", refusal);
+                    }
+                }
+            }
+        }
+        self.household.select(selected);
+        said
+    }
+
+    /// Send the pet-sitter home once the vacation is over
+    fn end_vacation_when_due(&mut self) {
+        if self.vacation.as_ref().is_some_and(Vacation::is_over) {
+            self.vacation = None;
+            self.journal.record(
+                JournalCategory::System,
+                None,
+                tr!("journal.vacation_over", name = self.pet_names()),
+            );
+        }
+    }
+
+    /// Names of every pet, for messages about the whole household
+    pub fn pet_names(&self) -> String {
+        let names: Vec<&str> = self
+            .household
+            .pets
+            .iter()
+            .map(|companion| companion.pet.name.as_str())
+            .collect();
+        match names.split_last() {
+            Some((last, rest)) if !rest.is_empty() => {
+                tr!("household.pair", first = rest.join(", "), second = last)
             }
+            _ => names.concat(),
         }
+    }
+
+    /// Trigger an animation on the selected pet
+    fn animate(&mut self, animation: AnimationType) {
+        self.household
+            .selected_mut()
+            .animated_pet
+            .trigger(animation);
+    }
+
+    /// Show the result of a player action and journal it
+    fn log_action(&mut self, message: String) {
+        self.journal
+            .record(JournalCategory::Action, None, message.clone());
+        self.status_message = message;
+        // The action explains any state change; don't journal it twice
+        self.household.selected_mut().sync_observed();
+    }
 
-        // Update status message
-        self.status_message = self.pet.status_message();
+    /// Explain why the pet refused an action, suggesting what to do instead
+    fn refuse(&mut self, err: ActionError) {
+        self.status_message = match err.suggestion() {
+            Some(action) => tr!(
+                "error.hint",
+                error = err,
+                hint = self.keymap.hint(action, action.description())
+            ),
+            None => err.to_string(),
+        };
     }
 
     /// Update animation system (called at 10 FPS)
     pub fn update_animation(&mut self) {
+        if !self.settings.animations {
+            return;
+        }
+
         if self.last_animation_update.elapsed().as_millis() >= 100 {
-            self.animated_pet.update();
+            for companion in &mut self.household.pets {
+                companion.animated_pet.update();
+            }
             self.last_animation_update = Instant::now();
         }
     }
 
-    /// Update animation based on pet state
-    fn update_pet_animation(&mut self) {
-        use crate::pet::PetState;
+    /// Every stat in plain sentences, for reading out loud
+    pub fn status_report(&self) -> Vec<String> {
+        let pet = self.pet();
+        let mut lines = Vec::new();
+
+        let state = i18n::lookup(match pet.state {
+            PetState::Normal => "report.awake",
+            PetState::Sleeping { .. } => "report.asleep",
+            PetState::Sick { .. } => "report.sick",
+            PetState::Dead => "report.dead",
+        });
+        if pet.stage == LifeStage::Egg {
+            lines.push(tr!("report.egg", name = pet.name));
+            lines.push(format!("{} {}", tr!("stat.warmth"), pet.get_warmth()));
+            lines.push(format!(
+                "{} {}",
+                tr!("stat.incubation"),
+                pet.get_incubation()
+            ));
+            lines.push(format!("{} {}", tr!("stat.health"), pet.get_egg_health()));
+        } else {
+            lines.push(tr!(
+                "report.pet",
+                name = pet.name,
+                stage = pet.stage.display_name(),
+                state = state,
+                age = pet.age_formatted()
+            ));
+            let stats = &pet.stats;
+            lines.push(format!("{} {}", tr!("stat.hunger"), stats.hunger.value()));
+            lines.push(format!(
+                "{} {}",
+                tr!("stat.happiness"),
+                stats.happiness.value()
+            ));
+            lines.push(format!("{} {}", tr!("stat.energy"), stats.energy.value()));
+            lines.push(format!("{} {}", tr!("stat.health"), stats.health.value()));
+            lines.push(format!("{} {}", tr!("stat.hygiene"), stats.hygiene.value()));
+        }
+        lines.push(pet.status_message());
 
-        match self.pet.state {
-            PetState::Normal => {
-                // Check happiness level for idle animation
-                if self.pet.stats.happiness.value() < 30 {
-                    self.animated_pet.set_idle_sad();
-                } else if self.pet.stats.happiness.value() > 70 {
-                    self.animated_pet.set_idle_happy();
-                } else {
-                    self.animated_pet.set_idle();
-                }
-            }
-            PetState::Sleeping { .. } => {
-                self.animated_pet.set_idle_sleeping();
-            }
-            PetState::Sick { .. } => {
-                self.animated_pet.trigger(AnimationType::TransitionGetSick);
-            }
-            PetState::Dead => {
-                self.animated_pet.trigger(AnimationType::TransitionDie);
-            }
+        if self.game_state == GameState::GameOver {
+            lines.push(tr!(
+                "report.game_over",
+                keys = self.keymap.keys_label(Action::Restart)
+            ));
         }
+        lines
+    }
+
+    /// Whether the mouse is over an area
+    pub fn is_hovered(&self, area: Rect) -> bool {
+        self.hover.is_some_and(|pos| area.contains(pos))
     }
 
     /// Quit the application
@@ -141,114 +444,481 @@ impl App {
     /// Restart the game (only works in Game Over state)
     pub fn restart(&mut self) {
         if self.game_state == GameState::GameOver {
-            let name = self.pet.name.clone();
-            self.pet = Pet::new(&name);
+            self.begin_setup(Setup::after(self.pet()));
+        }
+    }
+
+    /// Stop or restart time; refused on difficulties without pausing
+    pub fn toggle_pause(&mut self) {
+        if matches!(self.game_state, GameState::Playing | GameState::Paused)
+            && self.forward(RemoteAction::Pause)
+        {
+            return;
+        }
+
+        match self.game_state {
+            GameState::Paused => {
+                self.resume_clock();
+                self.game_state = GameState::Playing;
+                self.status_message = tr!("journal.resumed");
+            }
+            GameState::Playing if !self.pet().difficulty.allows_pause() => {
+                self.status_message = tr!("error.no_pause");
+                return;
+            }
+            GameState::Playing => {
+                self.paused_since = Some(Instant::now());
+                self.game_state = GameState::Paused;
+                self.status_message = tr!(
+                    "status.paused",
+                    keys = self.keymap.keys_label(Action::Pause)
+                );
+            }
+            GameState::Setup | GameState::GameOver => return,
+        }
+
+        let message = i18n::lookup(if self.game_state == GameState::Paused {
+            "journal.paused"
+        } else {
+            "journal.resumed"
+        });
+        self.journal
+            .record(JournalCategory::System, None, message.to_string());
+    }
+
+    /// Hand the pet to a sitter for the configured time, or take it back
+    pub fn toggle_vacation(&mut self) {
+        if self.game_state != GameState::Playing || self.forward(RemoteAction::Vacation) {
+            return;
+        }
+
+        let message = if self.vacation.take().is_some() {
+            tr!("journal.vacation_ended", name = self.pet_names())
+        } else {
+            let hours = self.settings.vacation_hours;
+            self.vacation = Some(Vacation::new(Duration::from_secs(hours * 60 * 60)));
+            tr!(
+                "journal.vacation_started",
+                name = self.pet_names(),
+                hours = hours
+            )
+        };
+        self.journal
+            .record(JournalCategory::System, None, message.clone());
+        self.status_message = message;
+    }
+
+    /// Count the time since pausing as not having passed
+    fn resume_clock(&mut self) {
+        let paused = self
+            .paused_since
+            .take()
+            .map_or(Duration::ZERO, |at| at.elapsed());
+        self.household.shift_clock(paused);
+        self.last_update = Instant::now();
+    }
+
+    /// Switch actions to the next pet
+    pub fn select_next_pet(&mut self) {
+        let next = (self.household.selected_index() + 1) % self.household.len();
+        self.select_pet(next);
+    }
+
+    /// Switch actions to the pet at `index`
+    pub fn select_pet(&mut self, index: usize) {
+        if self.household.len() < 2 || index >= self.household.len() {
+            return;
+        }
+        self.household.select(index);
+        self.status_message = tr!("household.selected", name = self.pet().name);
+    }
+
+    /// Open the wizard for an extra pet, stopping time meanwhile
+    pub fn adopt(&mut self) {
+        if self.game_state != GameState::Playing {
+            return;
+        }
+        if !self.household.has_room() {
+            self.status_message = tr!("error.household_full", max = MAX_PETS);
+            return;
+        }
+        self.paused_since = Some(Instant::now());
+        self.begin_setup(Setup::adopting());
+    }
+
+    /// Have the selected pet lay an egg with another adult
+    pub fn match_pets(&mut self) {
+        if self.game_state != GameState::Playing || self.forward(RemoteAction::Match) {
+            return;
+        }
+        if !self.household.has_room() {
+            self.status_message = tr!("error.household_full", max = MAX_PETS);
+            return;
+        }
+
+        match self.household.match_selected(setup::random_name()) {
+            Ok((egg, partner)) => {
+                let message = tr!(
+                    "journal.matched",
+                    first = self.pet().name,
+                    second = self.household.pets[partner].pet.name,
+                    name = egg.name,
+                    species = egg.species().label()
+                );
+                self.household.add(egg);
+                self.log_action(message);
+            }
+            Err(err) => self.refuse(err),
+        }
+    }
+
+    /// Close an adoption wizard without adding a pet
+    pub fn cancel_setup(&mut self) {
+        if self.game_state == GameState::Setup && self.setup.adopting {
+            self.resume_clock();
             self.game_state = GameState::Playing;
-            self.event_system = EventSystem::new();
-            self.status_message = self.pet.status_message();
-            self.last_update = Instant::now();
-            self.animated_pet = AnimatedPet::new();
+            self.navigation.open(View::Main);
         }
     }
 
+    /// Leave the selected pet's status for shell prompts
+    pub fn write_status_cache(&self) {
+        if let Some(path) = &self.status_cache
+            && let Err(err) = PromptStatus::new(self.pet()).write(path)
+        {
+            tracing::warn!("could not write prompt status: {}", err);
+        }
+    }
+
+    /// Write the game for next time, or forget it if no pet was chosen
+    pub fn save(&self) -> Result<(), SaveError> {
+        self.write_status_cache();
+        let path = save::state_path();
+        match self.save_state() {
+            Some(state) => state.write(&path),
+            None => match fs::remove_file(&path) {
+                Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err.into()),
+                _ => Ok(()),
+            },
+        }
+    }
+
+    /// Save, but only while this process holds the lock on the save; a game
+    /// shown from the daemon, or left without the lock, writes nothing
+    pub fn save_if_owned(&self) -> Result<(), SaveError> {
+        match self.save_lock {
+            Some(_) => self.save(),
+            None => Ok(()),
+        }
+    }
+
+    /// The game to write to disk; `None` until the first pet is chosen
+    pub fn save_state(&self) -> Option<SaveState> {
+        if self.game_state == GameState::Setup && !self.setup.adopting {
+            return None;
+        }
+        Some(SaveState {
+            paused: self.game_state == GameState::Paused,
+            ..SaveState::new(&self.household, self.vacation.as_ref())
+        })
+    }
+
+    /// Pick a saved game back up, replaying the time spent away unless it
+    /// was paused
+    pub fn restore(&mut self, mut state: SaveState) {
+        state.catch_up();
+        self.household = state.household();
+        self.vacation = state.vacation;
+        self.last_update = Instant::now();
+        if state.paused {
+            self.paused_since = Some(Instant::now());
+            self.game_state = GameState::Paused;
+            self.status_message = tr!(
+                "status.paused",
+                keys = self.keymap.keys_label(Action::Pause)
+            );
+        } else {
+            self.game_state = GameState::Playing;
+            self.status_message = self.pet().status_message();
+        }
+        self.navigation.open(View::Main);
+    }
+
+    /// Show the new-game wizard
+    pub fn begin_setup(&mut self, setup: Setup) {
+        self.setup = setup;
+        self.game_state = GameState::Setup;
+        self.navigation.open(View::Setup);
+    }
+
+    /// Hatch a new egg from the choices made in the wizard, either joining
+    /// the household or starting a new one
+    pub fn start_game(&mut self) {
+        if let Some(remote) = &mut self.remote {
+            let action = if self.setup.adopting {
+                RemoteAction::Adopt
+            } else {
+                RemoteAction::Restart
+            };
+            remote.perform(ActionParams {
+                action,
+                pet: None,
+                setup: Some(SetupChoices {
+                    name: self.setup.name.clone(),
+                    species: self.setup.species,
+                    difficulty: self.setup.difficulty,
+                }),
+            });
+            self.game_state = GameState::Playing;
+            self.navigation.open(View::Main);
+            return;
+        }
+
+        let pet = self.setup.pet();
+        let key = if self.setup.adopting {
+            self.resume_clock();
+            self.household.add(pet);
+            "journal.adopted"
+        } else {
+            self.household = Household::new(pet);
+            self.vacation = None;
+            self.last_update = Instant::now();
+            "journal.new_game"
+        };
+        self.game_state = GameState::Playing;
+        self.status_message = self.pet().status_message();
+
+        let pet = self.pet();
+        let message = tr!(
+            key,
+            name = pet.name,
+            species = pet.species().label(),
+            difficulty = pet.difficulty.label()
+        );
+        self.journal.record(JournalCategory::System, None, message);
+        self.navigation.open(View::Main);
+    }
+
     /// Warm the egg (only in Egg stage)
     pub fn warm_egg(&mut self) {
-        if self.game_state == GameState::GameOver {
+        if self.game_state != GameState::Playing || self.forward(RemoteAction::Warm) {
             return;
         }
 
-        match self.pet.warm() {
+        match self.pet_mut().warm() {
             Ok(()) => {
-                let warmth = self.pet.get_warmth();
-                self.status_message = format!("You warmed the egg! Warmth: {}%", warmth);
+                let warmth = self.pet().get_warmth();
+                self.log_action(tr!("action.warmed", warmth = warmth));
             }
-            Err(msg) => self.status_message = msg.to_string(),
+            Err(err) => self.refuse(err),
         }
     }
 
     /// Feed the pet
     pub fn feed_pet(&mut self) {
-        if self.game_state == GameState::GameOver {
+        if self.game_state != GameState::Playing || self.forward(RemoteAction::Feed) {
             return;
         }
 
-        match self.pet.feed() {
+        match self.pet_mut().feed() {
             Ok(()) => {
-                self.status_message = format!("You fed {}!", self.pet.name);
-                self.animated_pet.trigger(AnimationType::ActionEating);
+                self.log_action(tr!("action.fed", name = self.pet().name));
+                self.animate(AnimationType::ActionEating);
             }
-            Err(msg) => self.status_message = msg.to_string(),
+            Err(err) => self.refuse(err),
         }
     }
 
     /// Play with the pet
     pub fn play_with_pet(&mut self) {
-        if self.game_state == GameState::GameOver {
+        if self.game_state != GameState::Playing || self.forward(RemoteAction::Play) {
             return;
         }
 
-        match self.pet.play() {
+        match self.pet_mut().play() {
             Ok(()) => {
-                self.status_message = format!("You played with {}!", self.pet.name);
-                self.animated_pet.trigger(AnimationType::ActionPlaying);
+                self.log_action(tr!("action.played", name = self.pet().name));
+                self.animate(AnimationType::ActionPlaying);
             }
-            Err(msg) => self.status_message = msg.to_string(),
+            Err(err) => self.refuse(err),
         }
     }
 
     /// Clean the pet
     pub fn clean_pet(&mut self) {
-        if self.game_state == GameState::GameOver {
+        if self.game_state != GameState::Playing || self.forward(RemoteAction::Clean) {
             return;
         }
 
-        match self.pet.clean() {
+        match self.pet_mut().clean() {
             Ok(()) => {
-                self.status_message = format!("You cleaned {}!", self.pet.name);
-                self.animated_pet.trigger(AnimationType::ActionCleaning);
+                self.log_action(tr!("action.cleaned", name = self.pet().name));
+                self.animate(AnimationType::ActionCleaning);
             }
-            Err(msg) => self.status_message = msg.to_string(),
+            Err(err) => self.refuse(err),
         }
     }
 
     /// Toggle sleep/wake
     pub fn toggle_sleep(&mut self) {
-        if self.game_state == GameState::GameOver {
+        if self.game_state != GameState::Playing || self.forward(RemoteAction::Sleep) {
             return;
         }
 
-        match self.pet.state {
-            PetState::Sleeping { .. } => match self.pet.wake() {
+        match self.pet().state {
+            PetState::Sleeping { .. } => match self.pet_mut().wake() {
                 Ok(()) => {
-                    self.status_message = format!("{} woke up!", self.pet.name);
-                    self.animated_pet.trigger(AnimationType::TransitionWakeUp);
+                    self.log_action(tr!("action.woke", name = self.pet().name));
+                    self.animate(AnimationType::TransitionWakeUp);
                 }
-                Err(msg) => self.status_message = msg.to_string(),
+                Err(err) => self.refuse(err),
             },
-            _ => match self.pet.sleep() {
+            _ => match self.pet_mut().sleep() {
                 Ok(()) => {
-                    self.status_message = format!("{} went to sleep!", self.pet.name);
-                    self.animated_pet.trigger(AnimationType::ActionSleeping);
+                    self.log_action(tr!("action.slept", name = self.pet().name));
+                    self.animate(AnimationType::ActionSleeping);
                 }
-                Err(msg) => self.status_message = msg.to_string(),
+                Err(err) => self.refuse(err),
             },
         }
     }
 
+    /// Pat the pet (clicking on it)
+    pub fn pat_pet(&mut self) {
+        if self.game_state != GameState::Playing || self.forward(RemoteAction::Pat) {
+            return;
+        }
+
+        match self.pet_mut().pat() {
+            Ok(()) => {
+                self.log_action(tr!("action.patted", name = self.pet().name));
+                self.animate(AnimationType::EffectHearts);
+            }
+            Err(err) => self.refuse(err),
+        }
+    }
+
     /// Give medicine to the pet
     pub fn give_medicine(&mut self) {
-        if self.game_state == GameState::GameOver {
+        if self.game_state != GameState::Playing || self.forward(RemoteAction::Medicine) {
             return;
         }
 
-        match self.pet.give_medicine() {
+        match self.pet_mut().give_medicine() {
             Ok(()) => {
-                self.status_message = format!("You gave {} medicine!", self.pet.name);
-                self.animated_pet.trigger(AnimationType::ActionMedicine);
+                self.log_action(tr!("action.medicine", name = self.pet().name));
+                self.animate(AnimationType::ActionMedicine);
+            }
+            Err(err) => self.refuse(err),
+        }
+    }
+
+    /// Show the daemon's game and send actions there from now on
+    pub async fn attach(&mut self, mut client: Client) -> Result<(), ClientError> {
+        client.subscribe().await?;
+        let state = client.state().await?;
+        self.mirror(state);
+        self.navigation.open(View::Main);
+        self.remote = Some(client);
+        Ok(())
+    }
+
+    /// Requests sent to the daemon so far, to tell whether an action went
+    /// there
+    pub fn requests_sent(&self) -> u64 {
+        self.remote.as_ref().map_or(0, Client::requests_sent)
+    }
+
+    /// Send an action to the daemon instead of running it here; `false`
+    /// when there is no daemon
+    fn forward(&mut self, action: RemoteAction) -> bool {
+        let pet = Some(self.household.selected_index());
+        match &mut self.remote {
+            Some(remote) => {
+                remote.perform(ActionParams {
+                    action,
+                    pet,
+                    setup: None,
+                });
+                true
             }
-            Err(msg) => self.status_message = msg.to_string(),
+            None => false,
         }
     }
+
+    /// Take in whatever the daemon reported since the last tick
+    fn sync_remote(&mut self) {
+        let Some(remote) = &mut self.remote else {
+            return;
+        };
+        for update in remote.poll() {
+            match update {
+                Update::State(state) => self.mirror(state),
+                Update::Action(action, Ok(message)) => {
+                    self.status_message = message;
+                    let animation = match action {
+                        RemoteAction::Feed => AnimationType::ActionEating,
+                        RemoteAction::Play => AnimationType::ActionPlaying,
+                        RemoteAction::Clean => AnimationType::ActionCleaning,
+                        RemoteAction::Medicine => AnimationType::ActionMedicine,
+                        RemoteAction::Pat => AnimationType::EffectHearts,
+                        _ => continue,
+                    };
+                    self.animate(animation);
+                }
+                Update::Action(_, Err(message)) => {
+                    self.status_message = message.clone();
+                    self.refused = Some(message);
+                }
+                Update::Event(entry) => {
+                    self.status_message = entry.message.clone();
+                    self.journal.push(entry);
+                }
+                Update::Disconnected => {
+                    // Carry on with the last known state
+                    self.remote = None;
+                    self.last_update = Instant::now();
+                    self.status_message = tr!("daemon.disconnected");
+                    self.save_lock = SaveLock::acquire().ok().flatten();
+                    self.journal
+                        .record(JournalCategory::System, None, self.status_message.clone());
+                }
+            }
+        }
+    }
+
+    /// Show the daemon's game as if it were running here
+    pub fn mirror(&mut self, state: RemoteState) {
+        let same_pets = state.pets.len() == self.household.len()
+            && state
+                .pets
+                .iter()
+                .zip(&self.household.pets)
+                .all(|(pet, companion)| {
+                    pet.name == companion.pet.name && pet.genome == companion.pet.genome
+                });
+        if same_pets {
+            for (companion, pet) in self.household.pets.iter_mut().zip(state.pets) {
+                companion.pet = pet;
+                companion.sample_history();
+                companion.sync_observed();
+                companion.update_animation_state();
+            }
+        } else if let Some(household) = Household::from_pets(state.pets, state.selected) {
+            self.household = household;
+        }
+
+        self.vacation = state.vacation;
+        if self.game_state != GameState::Setup {
+            self.game_state = state.game_state;
+        }
+        // The daemon's status is about the pet it has selected
+        self.status_message = if self.household.selected_index() == state.selected {
+            state.status
+        } else {
+            self.pet().status_message()
+        };
+    }
 }
 
 impl Default for App {
@@ -256,3 +926,22 @@ impl Default for App {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scripts_have_the_last_word() {
+        let mut app = App::new();
+        app.scripts
+            .orders
+            .push(Order::Say("The pumpkin glows".to_string()));
+
+        app.tick();
+
+        assert_eq!(app.status_message, "The pumpkin glows");
+        let said = app.journal.recent(1).next().unwrap();
+        assert_eq!(said.message, "The pumpkin glows");
+    }
+}
@@ -0,0 +1,290 @@
+//! Getting the player's attention from inside the terminal
+//!
+//! While the terminal is in the background, alerts ring the bell, send
+//! OSC 9 or OSC 777 notifications and put the news in the window title.
+//! What each kind of alert does is read from `attention.json` in the
+//! config directory:
+//!
+//! ```json
+//! {
+//!   "only_unfocused": true,
+//!   "alerts": {
+//!     "starving": ["bell", "osc9", "title"],
+//!     "event": ["title"]
+//!   }
+//! }
+//! ```
+//!
+//! Kinds left out keep their defaults: the bell and the title for
+//! problems, nothing for random events.
+//! Terminals that never report focus changes
+//! need `"only_unfocused": false` to get any cues. The terminal's own title
+//! is saved on its title stack at startup and put back once the news has
+//! been seen and when the game ends.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+
+use serde::Deserialize;
+
+use crate::alerts::{Alert, AlertKind};
+use crate::i18n::tr;
+use crate::paths;
+
+/// File name of the settings inside the config directory
+const ATTENTION_FILE: &str = "attention.json";
+/// Title of OSC 777 notifications
+const TITLE: &str = "MyPet";
+/// Saves the window title on the terminal's title stack
+const PUSH_TITLE: &str = "\x1b[22;0t";
+/// Puts back the title saved last
+const POP_TITLE: &str = "\x1b[23;0t";
+
+/// A way of getting attention
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Cue {
+    /// The terminal bell
+    Bell,
+    /// An OSC 9 notification (iTerm2, Windows Terminal, kitty, ...)
+    Osc9,
+    /// An OSC 777 notification (rxvt-unicode, foot, VTE, ...)
+    Osc777,
+    /// The window title
+    Title,
+}
+
+/// Cues sent to the terminal when alerts arrive
+#[derive(Debug)]
+pub struct Attention {
+    /// Whether cues are sent at all; only the full-screen interface can
+    pub enabled: bool,
+    /// Stay quiet while the terminal has focus
+    pub only_unfocused: bool,
+    /// What each kind of alert does
+    pub cues: HashMap<AlertKind, Vec<Cue>>,
+    /// Problems with the settings, for the player to fix
+    pub warnings: Vec<String>,
+    /// Whether the terminal has focus, as far as it has said
+    focused: bool,
+    /// Whether the terminal's title was saved, to be put back on leaving
+    saved: bool,
+    /// Whether the title shows news that should be cleared
+    titled: bool,
+    /// Escape sequences waiting to be written
+    pending: String,
+}
+
+impl Default for Attention {
+    fn default() -> Self {
+        let cues = AlertKind::ALL
+            .into_iter()
+            .map(|kind| {
+                let cues = if kind.is_urgent() {
+                    vec![Cue::Bell, Cue::Title]
+                } else {
+                    Vec::new()
+                };
+                (kind, cues)
+            })
+            .collect();
+        Self {
+            enabled: false,
+            only_unfocused: true,
+            cues,
+            warnings: Vec::new(),
+            focused: true,
+            saved: false,
+            titled: false,
+            pending: String::new(),
+        }
+    }
+}
+
+impl Attention {
+    /// Read the settings from the config directory
+    pub fn load() -> Self {
+        let path = paths::config_dir().join(ATTENTION_FILE);
+        match fs::read_to_string(&path) {
+            Ok(json) => Self::from_json(&json),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Self {
+                enabled: true,
+                ..Self::default()
+            },
+            Err(err) => Self {
+                warnings: vec![format!("could not read {}: {}", path.display(), err)],
+                ..Self::default()
+            },
+        }
+    }
+
+    /// Build the settings from the contents of a settings file
+    pub fn from_json(json: &str) -> Self {
+        #[derive(Deserialize)]
+        struct Settings {
+            #[serde(default = "only_unfocused")]
+            only_unfocused: bool,
+            #[serde(default)]
+            alerts: HashMap<AlertKind, Vec<Cue>>,
+        }
+
+        fn only_unfocused() -> bool {
+            true
+        }
+
+        let mut attention = Self::default();
+        match serde_json::from_str::<Settings>(json) {
+            Ok(settings) => {
+                attention.enabled = true;
+                attention.only_unfocused = settings.only_unfocused;
+                attention.cues.extend(settings.alerts);
+            }
+            Err(err) => attention
+                .warnings
+                .push(format!("invalid attention file: {}", err)),
+        }
+        attention
+    }
+
+    /// Save the terminal's title so it can be put back later
+    pub fn begin(&mut self) {
+        if self.enabled && !self.saved {
+            self.saved = true;
+            self.pending.push_str(PUSH_TITLE);
+        }
+    }
+
+    /// Note whether the terminal has focus; coming back clears the title
+    pub fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+        if focused && self.titled {
+            self.titled = false;
+            // Saved again for the next time news arrives
+            self.pending.push_str(POP_TITLE);
+            self.pending.push_str(PUSH_TITLE);
+        }
+    }
+
+    /// Queue the cues for the alerts, unless the player is looking
+    pub fn notice(&mut self, alerts: &[Alert]) {
+        if !self.enabled || (self.only_unfocused && self.focused) {
+            return;
+        }
+        for alert in alerts {
+            let Some(cues) = self.cues.get(&alert.kind) else {
+                continue;
+            };
+            for cue in cues {
+                let message = clean(&alert.message);
+                match cue {
+                    Cue::Bell => self.pending.push('\x07'),
+                    Cue::Osc9 => self.pending.push_str(&format!("\x1b]9;{}\x07", message)),
+                    Cue::Osc777 => self.pending.push_str(&format!(
+                        "\x1b]777;notify;{};{}\x07",
+                        TITLE,
+                        message.replace(';', ",")
+                    )),
+                    Cue::Title if self.saved => {
+                        self.titled = true;
+                        let news = tr!("attention.title", message = message);
+                        self.pending.push_str(&title(&news));
+                    }
+                    // Without a saved title there would be no way back
+                    Cue::Title => {}
+                }
+            }
+        }
+    }
+
+    /// Put the terminal's own title back, as when leaving
+    pub fn finish(&mut self) {
+        if self.saved {
+            self.saved = false;
+            self.titled = false;
+            self.pending.push_str(POP_TITLE);
+        }
+    }
+
+    /// Escape sequences to write to the terminal, taken out of the queue
+    pub fn take(&mut self) -> String {
+        std::mem::take(&mut self.pending)
+    }
+}
+
+/// The sequence that sets the window title
+fn title(text: &str) -> String {
+    format!("\x1b]0;{}\x07", text)
+}
+
+/// Text with control characters removed, so it can't end a sequence early
+fn clean(text: &str) -> String {
+    text.chars().filter(|c| !c.is_control()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alert(kind: AlertKind, message: &str) -> Alert {
+        Alert {
+            kind,
+            name: "Fluffy".to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn cues_only_when_unfocused() {
+        let mut attention = Attention::from_json(
+            r#"{"alerts": {"starving": ["bell", "osc9", "osc777", "title"]}}"#,
+        );
+        let alerts = [
+            alert(AlertKind::Starving, "Fluffy is very hungry!\x1b"),
+            alert(AlertKind::Event, "Fluffy found a shiny pebble"),
+        ];
+        attention.begin();
+        assert_eq!(attention.take(), "\x1b[22;0t");
+        attention.notice(&alerts);
+        assert!(attention.take().is_empty());
+
+        attention.set_focused(false);
+        attention.notice(&alerts);
+        assert_eq!(
+            attention.take(),
+            "\x07\x1b]9;Fluffy is very hungry!\x07\
+             \x1b]777;notify;MyPet;Fluffy is very hungry!\x07\
+             \x1b]0;MyPet – Fluffy is very hungry!\x07"
+        );
+
+        attention.set_focused(true);
+        assert_eq!(attention.take(), "\x1b[23;0t\x1b[22;0t");
+        attention.set_focused(true);
+        assert!(attention.take().is_empty());
+
+        // Leaving puts the terminal's title back, news or not
+        attention.set_focused(false);
+        attention.notice(&alerts);
+        attention.take();
+        attention.finish();
+        assert_eq!(attention.take(), "\x1b[23;0t");
+        attention.finish();
+        assert!(attention.take().is_empty());
+    }
+
+    #[test]
+    fn reads_settings() {
+        let attention =
+            Attention::from_json(r#"{"only_unfocused": false, "alerts": {"sick": []}}"#);
+        assert!(!attention.only_unfocused);
+        assert!(attention.cues[&AlertKind::Sick].is_empty());
+        assert_eq!(
+            attention.cues[&AlertKind::Critical],
+            [Cue::Bell, Cue::Title]
+        );
+
+        let bad = Attention::from_json(r#"{"alerts": {"starving": ["siren"]}}"#);
+        assert!(!bad.enabled);
+        assert_eq!(bad.warnings.len(), 1);
+    }
+}
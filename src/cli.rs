@@ -0,0 +1,168 @@
+//! Command line options
+
+use std::net::SocketAddr;
+
+use crate::glyphs::GlyphMode;
+
+/// Shown for `--help` and after invalid arguments
+pub const USAGE: &str = "\
+Usage: mypet-tui [OPTIONS] [COMMAND]
+
+Commands (act on the saved game once instead of opening the game):
+  status            Show the selected pet's stats
+  feed              Feed the selected pet
+  play              Play with the selected pet
+  clean             Clean the selected pet
+  sleep             Put the selected pet to bed, or wake it up
+  prompt            Print a short status for shell prompts and tmux, as
+                    last left by the game
+  daemon            Keep the game running in the background; the game and
+                    the commands above connect to it while it runs
+
+Options:
+  --high-contrast   Add text indicators and bold/reverse styling instead of
+                    relying on color alone (also enabled by NO_COLOR)
+  --ascii           Only draw ASCII characters
+  --unicode         Draw Unicode even if the terminal looks like it can't
+  --screen-reader   Write changes as plain lines and read commands from
+                    stdin instead of drawing a screen
+  --json            Print command results as JSON
+  --http ADDR       Serve the pet over HTTP on a loopback address such as
+                    127.0.0.1:8080, with the game or the daemon
+  --format FORMAT   Template for prompt, default
+                    \"{alert}{face} {warmth} {hunger} {happiness}\"; also
+                    {name}, {energy}, {health} and {hygiene}
+  -h, --help        Show this help";
+
+/// One-shot command to run instead of the game
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Status,
+    Feed,
+    Play,
+    Clean,
+    Sleep,
+    Prompt,
+    Daemon,
+}
+
+impl Command {
+    /// The command named `name`, if there is one
+    fn parse(name: &str) -> Option<Self> {
+        Some(match name {
+            "status" => Command::Status,
+            "feed" => Command::Feed,
+            "play" => Command::Play,
+            "clean" => Command::Clean,
+            "sleep" => Command::Sleep,
+            "prompt" => Command::Prompt,
+            "daemon" => Command::Daemon,
+            _ => return None,
+        })
+    }
+}
+
+/// Options given on the command line
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Options {
+    /// Print usage and exit
+    pub help: bool,
+    /// Start in high-contrast mode
+    pub high_contrast: bool,
+    /// Character set to use instead of the detected one
+    pub glyphs: Option<GlyphMode>,
+    /// Use the linear, screen-reader friendly frontend
+    pub screen_reader: bool,
+    /// Command to run instead of the game
+    pub command: Option<Command>,
+    /// Print command results as JSON
+    pub json: bool,
+    /// Template for the prompt command
+    pub format: Option<String>,
+    /// Where to serve the HTTP API
+    pub http: Option<SocketAddr>,
+}
+
+impl Options {
+    /// Parse the arguments following the program name
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut options = Self::default();
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-h" | "--help" => options.help = true,
+                "--high-contrast" => options.high_contrast = true,
+                "--ascii" => options.glyphs = Some(GlyphMode::Ascii),
+                "--unicode" => options.glyphs = Some(GlyphMode::Unicode),
+                "--screen-reader" => options.screen_reader = true,
+                "--json" => options.json = true,
+                "--format" => {
+                    options.format = Some(args.next().ok_or("--format needs a template")?);
+                }
+                "--http" => {
+                    let addr = args.next().ok_or("--http needs an address")?;
+                    options.http = Some(
+                        addr.parse()
+                            .map_err(|_| format!("invalid address '{}' for --http", addr))?,
+                    );
+                }
+                name if options.command.is_none() && !name.starts_with('-') => {
+                    options.command = Some(
+                        Command::parse(name)
+                            .ok_or_else(|| format!("unknown command '{}'", name))?,
+                    );
+                }
+                _ => return Err(format!("unknown option '{}'", arg)),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Options, String> {
+        Options::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn parses_flags() {
+        assert_eq!(parse(&[]), Ok(Options::default()));
+
+        let options = parse(&["--high-contrast", "--ascii"]).unwrap();
+        assert!(options.high_contrast);
+        assert_eq!(options.glyphs, Some(GlyphMode::Ascii));
+        assert!(!options.help);
+        assert!(!options.screen_reader);
+        assert!(parse(&["--screen-reader"]).unwrap().screen_reader);
+
+        let options = parse(&["--http", "127.0.0.1:8080"]).unwrap();
+        assert_eq!(options.http, Some(([127, 0, 0, 1], 8080).into()));
+        assert!(parse(&["--http", "localhost"]).is_err());
+    }
+
+    #[test]
+    fn parses_commands() {
+        let options = parse(&["feed", "--json"]).unwrap();
+        assert_eq!(options.command, Some(Command::Feed));
+        assert!(options.json);
+        assert_eq!(parse(&["status"]).unwrap().command, Some(Command::Status));
+        assert_eq!(parse(&["daemon"]).unwrap().command, Some(Command::Daemon));
+
+        let options = parse(&["prompt", "--format", "{face}"]).unwrap();
+        assert_eq!(options.command, Some(Command::Prompt));
+        assert_eq!(options.format.as_deref(), Some("{face}"));
+        assert!(parse(&["prompt", "--format"]).is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_options() {
+        assert!(parse(&["--loud"]).is_err());
+        assert!(parse(&["dance"]).is_err());
+        assert!(parse(&["feed", "play"]).is_err());
+    }
+}
@@ -0,0 +1,246 @@
+//! Connection from the game (or a one-shot command) to the daemon
+//!
+//! The game keeps drawing locally but hands every action to the daemon and
+//! mirrors the state it reports back. Reading and writing happen in
+//! background tasks so the game's tick never waits on the socket.
+
+use std::collections::{HashMap, VecDeque};
+use std::io;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+use thiserror::Error;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::UnixStream;
+use tokio::sync::mpsc;
+
+use crate::journal::JournalEntry;
+use crate::rpc::{
+    ActionParams, ActionResult, Message, RemoteAction, RemoteState, Request, RpcError,
+};
+
+/// How often the game asks for a fresh state
+const STATE_POLL: Duration = Duration::from_millis(500);
+
+/// Why a call to the daemon failed
+#[derive(Debug, Error)]
+pub enum ClientError {
+    #[error("{0}")]
+    Io(#[from] io::Error),
+    #[error("{}", .0.message)]
+    Rpc(RpcError),
+    #[error("unexpected answer from the daemon: {0}")]
+    Format(#[from] serde_json::Error),
+    #[error("the daemon closed the connection")]
+    Closed,
+}
+
+/// What an unanswered request was for
+#[derive(Debug, Clone, Copy)]
+enum Pending {
+    State,
+    Action(RemoteAction),
+}
+
+/// Something the daemon told us since the last poll
+#[derive(Debug)]
+pub enum Update {
+    /// The whole game as it is now
+    State(RemoteState),
+    /// Outcome of an action: what happened, or why it was refused
+    Action(RemoteAction, Result<String, String>),
+    /// A new journal entry
+    Event(JournalEntry),
+    /// The daemon went away
+    Disconnected,
+}
+
+/// An open connection to the daemon
+#[derive(Debug)]
+pub struct Client {
+    outgoing: mpsc::UnboundedSender<Request>,
+    incoming: mpsc::UnboundedReceiver<Message>,
+    /// Messages read while waiting for a particular answer
+    backlog: VecDeque<Message>,
+    next_id: u64,
+    pending: HashMap<u64, Pending>,
+    last_poll: Instant,
+}
+
+impl Client {
+    /// Connect to the daemon listening at `path`
+    pub async fn connect(path: &Path) -> io::Result<Self> {
+        Ok(Self::new(UnixStream::connect(path).await?))
+    }
+
+    /// Talk to the daemon over an open stream
+    pub fn new(stream: UnixStream) -> Self {
+        let (reader, mut writer) = stream.into_split();
+
+        let (outgoing, mut requests) = mpsc::unbounded_channel::<Request>();
+        tokio::spawn(async move {
+            while let Some(request) = requests.recv().await {
+                let Ok(mut line) = serde_json::to_string(&request) else {
+                    continue;
+                };
+                line.push('\n');
+                if writer.write_all(line.as_bytes()).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        let (messages, incoming) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            let mut lines = BufReader::new(reader).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                match serde_json::from_str::<Message>(&line) {
+                    Ok(message) => {
+                        if messages.send(message).is_err() {
+                            break;
+                        }
+                    }
+                    Err(err) => tracing::warn!("bad message from daemon: {}", err),
+                }
+            }
+        });
+
+        Self {
+            outgoing,
+            incoming,
+            backlog: VecDeque::new(),
+            next_id: 1,
+            pending: HashMap::new(),
+            last_poll: Instant::now(),
+        }
+    }
+
+    /// Number of requests sent so far
+    pub fn requests_sent(&self) -> u64 {
+        self.next_id - 1
+    }
+
+    /// Send a request without waiting for the answer
+    fn send(&mut self, method: &str, params: Value) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        // A closed connection shows up as `Update::Disconnected` on poll
+        let _ = self.outgoing.send(Request::new(id, method, params));
+        id
+    }
+
+    /// Send a request and wait for its answer, keeping anything else for
+    /// the next poll
+    pub async fn call(&mut self, method: &str, params: Value) -> Result<Value, ClientError> {
+        let id = self.send(method, params);
+        loop {
+            let message = self.incoming.recv().await.ok_or(ClientError::Closed)?;
+            if message.id != Some(id) {
+                self.backlog.push_back(message);
+                continue;
+            }
+            return match (message.result, message.error) {
+                (_, Some(error)) => Err(ClientError::Rpc(error)),
+                (Some(result), None) => Ok(result),
+                (None, None) => Ok(Value::Null),
+            };
+        }
+    }
+
+    /// The game as the daemon has it
+    pub async fn state(&mut self) -> Result<RemoteState, ClientError> {
+        Ok(serde_json::from_value(
+            self.call("get_state", Value::Null).await?,
+        )?)
+    }
+
+    /// Run an action and wait for what happened
+    pub async fn act(&mut self, params: ActionParams) -> Result<String, ClientError> {
+        let result = self
+            .call("perform_action", serde_json::to_value(params)?)
+            .await?;
+        Ok(serde_json::from_value::<ActionResult>(result)?.message)
+    }
+
+    /// Ask for journal entries as they are written
+    pub async fn subscribe(&mut self) -> Result<(), ClientError> {
+        self.call("subscribe_events", Value::Null).await.map(drop)
+    }
+
+    /// Run an action; the outcome arrives with a later poll
+    pub fn perform(&mut self, params: ActionParams) {
+        let action = params.action;
+        match serde_json::to_value(params) {
+            Ok(params) => {
+                let id = self.send("perform_action", params);
+                self.pending.insert(id, Pending::Action(action));
+            }
+            Err(err) => tracing::warn!("could not send action: {}", err),
+        }
+    }
+
+    /// Everything that arrived since the last call, asking for a fresh
+    /// state when the last one is getting old
+    pub fn poll(&mut self) -> Vec<Update> {
+        let waiting = self
+            .pending
+            .values()
+            .any(|pending| matches!(pending, Pending::State));
+        if !waiting && self.last_poll.elapsed() >= STATE_POLL {
+            let id = self.send("get_state", Value::Null);
+            self.pending.insert(id, Pending::State);
+            self.last_poll = Instant::now();
+        }
+
+        let mut messages: Vec<Message> = self.backlog.drain(..).collect();
+        let mut disconnected = false;
+        loop {
+            match self.incoming.try_recv() {
+                Ok(message) => messages.push(message),
+                Err(mpsc::error::TryRecvError::Empty) => break,
+                Err(mpsc::error::TryRecvError::Disconnected) => {
+                    disconnected = true;
+                    break;
+                }
+            }
+        }
+
+        let mut updates: Vec<Update> = messages
+            .into_iter()
+            .filter_map(|message| self.read(message))
+            .collect();
+        if disconnected {
+            updates.push(Update::Disconnected);
+        }
+        updates
+    }
+
+    /// Make sense of one message
+    fn read(&mut self, message: Message) -> Option<Update> {
+        let Some(id) = message.id else {
+            return match message.method.as_deref() {
+                Some("event") => serde_json::from_value(message.params?)
+                    .ok()
+                    .map(Update::Event),
+                _ => None,
+            };
+        };
+        match self.pending.remove(&id)? {
+            Pending::State => serde_json::from_value(message.result?)
+                .map_err(|err| tracing::warn!("bad state from daemon: {}", err))
+                .ok()
+                .map(Update::State),
+            Pending::Action(action) => {
+                let outcome = match (message.result, message.error) {
+                    (_, Some(error)) => Err(error.message),
+                    (result, None) => Ok(result
+                        .and_then(|result| serde_json::from_value::<ActionResult>(result).ok())
+                        .map(|result| result.message)
+                        .unwrap_or_default()),
+                };
+                Some(Update::Action(action, outcome))
+            }
+        }
+    }
+}
@@ -0,0 +1,260 @@
+//! One-shot commands run against the saved game
+//!
+//! Each command loads the saved household, replays the time since it was
+//! written, acts on the selected pet and saves it again, so the pet can be
+//! looked after from scripts and shell aliases without opening the game.
+//! While the daemon runs, commands are sent to it instead, and while the
+//! game is open in a terminal they refuse rather than act on a stale copy.
+
+use serde::Serialize;
+
+use crate::app::App;
+use crate::cli::Command;
+use crate::client::Client;
+use crate::difficulty::Difficulty;
+use crate::genome::Coat;
+use crate::i18n::tr;
+use crate::journal::{Journal, JournalCategory};
+use crate::paths;
+use crate::pet::{ActionError, LifeStage, Pet, Species};
+use crate::prompt;
+use crate::rpc::{self, ActionParams, RemoteAction};
+use crate::save::{self, SaveLock, SaveState};
+
+/// A pet's state in a form scripts can read
+#[derive(Debug, Clone, Serialize)]
+pub struct PetSummary {
+    pub name: String,
+    pub species: Species,
+    pub coat: Coat,
+    pub difficulty: Difficulty,
+    pub stage: LifeStage,
+    /// `normal`, `sleeping`, `sick` or `dead`
+    pub state: &'static str,
+    pub age_seconds: u64,
+    /// Stats of a hatched pet
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stats: Option<StatSummary>,
+    /// Progress of an egg
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub egg: Option<EggSummary>,
+    /// One-line description of how the pet is doing
+    pub status: String,
+}
+
+/// Stat values of a hatched pet, 0-100
+#[derive(Debug, Clone, Serialize)]
+pub struct StatSummary {
+    pub hunger: u8,
+    pub happiness: u8,
+    pub energy: u8,
+    pub health: u8,
+    pub hygiene: u8,
+}
+
+/// Egg values, 0-100
+#[derive(Debug, Clone, Serialize)]
+pub struct EggSummary {
+    pub warmth: u8,
+    pub incubation: u8,
+    pub health: u8,
+}
+
+impl PetSummary {
+    /// Summarize a pet as it is now
+    pub fn new(pet: &Pet) -> Self {
+        let stats = &pet.stats;
+        let hatched = pet.stage != LifeStage::Egg;
+        Self {
+            name: pet.name.clone(),
+            species: pet.species(),
+            coat: pet.genome.coat,
+            difficulty: pet.difficulty,
+            stage: pet.stage,
+            state: pet.state.id(),
+            age_seconds: pet.age_seconds,
+            stats: hatched.then(|| StatSummary {
+                hunger: stats.hunger.value(),
+                happiness: stats.happiness.value(),
+                energy: stats.energy.value(),
+                health: stats.health.value(),
+                hygiene: stats.hygiene.value(),
+            }),
+            egg: (!hatched).then(|| EggSummary {
+                warmth: pet.get_warmth(),
+                incubation: pet.get_incubation(),
+                health: pet.get_egg_health(),
+            }),
+            status: pet.status_message(),
+        }
+    }
+}
+
+/// What a command prints with `--json`
+#[derive(Debug, Serialize)]
+struct Output {
+    ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pet: Option<PetSummary>,
+}
+
+/// Run a command, returning the process exit code
+pub async fn run(command: Command, json: bool) -> i32 {
+    if let Ok(client) = Client::connect(&rpc::socket_path()).await {
+        return run_remote(client, command, json).await;
+    }
+
+    let _lock = match SaveLock::acquire() {
+        Ok(Some(lock)) => lock,
+        Ok(None) => return fail(json, tr!("command.game_running"), None),
+        Err(err) => return fail(json, tr!("command.bad_save", error = err), None),
+    };
+    let path = save::state_path();
+    let mut app = App::new();
+    match SaveState::load(&path) {
+        Ok(Some(state)) => app.restore(state),
+        Ok(None) => return fail(json, tr!("command.no_save"), None),
+        Err(err) => return fail(json, tr!("command.bad_save", error = err), None),
+    }
+
+    let result = perform(&mut app, command);
+    if command != Command::Status
+        && let Ok(message) = &result
+    {
+        let mut journal = Journal::open(paths::data_dir().join("journal.jsonl"));
+        journal.record(JournalCategory::Action, None, message.clone());
+    }
+
+    if let Some(state) = app.save_state()
+        && let Err(err) = state.write(&path)
+    {
+        return fail(json, tr!("command.save_failed", error = err), None);
+    }
+    app.status_cache = Some(prompt::cache_path());
+    app.write_status_cache();
+
+    report(&app, command, json, result.map_err(|err| err.to_string()))
+}
+
+/// Run a command through the daemon, which journals and saves it
+async fn run_remote(mut client: Client, command: Command, json: bool) -> i32 {
+    let action = match command {
+        Command::Feed => Some(RemoteAction::Feed),
+        Command::Play => Some(RemoteAction::Play),
+        Command::Clean => Some(RemoteAction::Clean),
+        Command::Sleep => Some(RemoteAction::Sleep),
+        Command::Status | Command::Prompt | Command::Daemon => None,
+    };
+    let result = match action {
+        Some(action) => client
+            .act(ActionParams {
+                action,
+                pet: None,
+                setup: None,
+            })
+            .await
+            .map_err(|err| err.to_string()),
+        None => Ok(String::new()),
+    };
+
+    let mut app = App::new();
+    match client.state().await {
+        Ok(state) => app.mirror(state),
+        Err(err) => return fail(json, tr!("command.daemon_failed", error = err), None),
+    }
+    let result = result.map(|message| match command {
+        Command::Status => app.pet().status_message(),
+        _ => message,
+    });
+    report(&app, command, json, result)
+}
+
+/// Print the outcome of a command, returning the exit code
+fn report(app: &App, command: Command, json: bool, result: Result<String, String>) -> i32 {
+    let pet = PetSummary::new(app.pet());
+    match result {
+        Ok(message) if json => print_json(Output {
+            ok: true,
+            message: Some(message),
+            error: None,
+            pet: Some(pet),
+        }),
+        Ok(_) if command == Command::Status => println!("{}", app.status_report().join("\n")),
+        Ok(message) => println!("{}", message),
+        Err(err) => return fail(json, err, Some(pet)),
+    }
+    0
+}
+
+/// Apply the command to the selected pet, describing what happened
+fn perform(app: &mut App, command: Command) -> Result<String, ActionError> {
+    let pet = app.pet_mut();
+    let name = pet.name.clone();
+    match command {
+        Command::Status => Ok(pet.status_message()),
+        Command::Feed => pet.feed().map(|()| tr!("action.fed", name = name)),
+        Command::Play => pet.play().map(|()| tr!("action.played", name = name)),
+        Command::Clean => pet.clean().map(|()| tr!("action.cleaned", name = name)),
+        Command::Sleep if pet.state.is_sleeping() => {
+            pet.wake().map(|()| tr!("action.woke", name = name))
+        }
+        Command::Sleep => pet.sleep().map(|()| tr!("action.slept", name = name)),
+        // Handled before the game is loaded
+        Command::Prompt | Command::Daemon => Ok(String::new()),
+    }
+}
+
+/// Report a failure, returning the exit code for it
+fn fail(json: bool, error: String, pet: Option<PetSummary>) -> i32 {
+    if json {
+        print_json(Output {
+            ok: false,
+            message: None,
+            error: Some(error),
+            pet,
+        });
+    } else {
+        eprintln!("mypet-tui: {}", error);
+    }
+    1
+}
+
+fn print_json(output: Output) {
+    match serde_json::to_string(&output) {
+        Ok(json) => println!("{}", json),
+        Err(err) => eprintln!("mypet-tui: {}", err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::Genome;
+
+    #[test]
+    fn commands_act_on_the_selected_pet() {
+        let mut app = App::new();
+        app.pet_mut().stage = LifeStage::Child;
+
+        assert!(perform(&mut app, Command::Feed).is_ok());
+        assert!(perform(&mut app, Command::Sleep).is_ok());
+        assert!(app.pet().state.is_sleeping());
+        assert_eq!(perform(&mut app, Command::Feed), Err(ActionError::Asleep));
+        assert!(perform(&mut app, Command::Sleep).is_ok());
+        assert!(!app.pet().state.is_sleeping());
+    }
+
+    #[test]
+    fn summaries_show_eggs_and_stats() {
+        let egg = PetSummary::new(&Pet::new("Pip", Genome::default()));
+        let json = serde_json::to_value(&egg).unwrap();
+        assert_eq!(json["stage"], "egg");
+        assert_eq!(json["state"], "normal");
+        assert_eq!(json["egg"]["warmth"], 20);
+        assert!(json.get("stats").is_none());
+    }
+}
@@ -0,0 +1,460 @@
+//! The simulation running in the background
+//!
+//! `mypet-tui daemon` loads the saved household and keeps ticking it without
+//! a screen. The game and the one-shot commands find its socket in the data
+//! directory and connect as clients (see [`crate::rpc`]), so the pets live on
+//! after the last window closes. The household is saved every minute and
+//! when the daemon is stopped.
+
+use std::fs;
+use std::io;
+use std::net::SocketAddr;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+use tokio::signal::unix::{SignalKind, signal};
+use tokio::sync::{broadcast, mpsc};
+use tokio::task::JoinHandle;
+
+use crate::app::{App, GameState};
+use crate::events::{self, EventTable};
+use crate::hooks::Hooks;
+use crate::http;
+use crate::i18n::tr;
+use crate::journal::{Journal, JournalCategory, JournalEntry};
+use crate::notify::Notifier;
+use crate::paths;
+use crate::prompt;
+use crate::rpc::{
+    self, ActionParams, ActionResult, INVALID_PARAMS, METHOD_NOT_FOUND, Message, PARSE_ERROR,
+    REFUSED, RemoteAction, RemoteState, Request, SetupChoices,
+};
+use crate::save::{self, SaveLock, SaveState};
+use crate::scripts::Scripts;
+use crate::setup;
+
+/// How often the game is advanced
+const TICK_RATE: Duration = Duration::from_millis(250);
+/// Journal entries kept for subscribers that fall behind
+const EVENT_BUFFER: usize = 64;
+
+/// The game and what subscribers have been told about it
+#[derive(Debug)]
+struct Daemon {
+    app: App,
+    /// Journal entries already sent to subscribers
+    published: usize,
+}
+
+impl Daemon {
+    /// Send new journal entries to every subscriber
+    fn publish(&mut self, events: &broadcast::Sender<JournalEntry>) {
+        for entry in self.app.journal.since(self.published) {
+            // Nobody listening is fine
+            let _ = events.send(entry.clone());
+        }
+        self.published = self.app.journal.len();
+    }
+}
+
+type Shared = Arc<Mutex<Daemon>>;
+
+fn lock(shared: &Shared) -> MutexGuard<'_, Daemon> {
+    shared
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Run the daemon until it is told to stop, returning the exit code
+pub async fn run(http: Option<SocketAddr>) -> i32 {
+    let mut app = App::new();
+    match SaveLock::acquire() {
+        Ok(Some(lock)) => app.save_lock = Some(lock),
+        // Either another daemon or a game open in a terminal
+        Ok(None) => {
+            let path = rpc::socket_path();
+            return fail(if UnixStream::connect(&path).await.is_ok() {
+                tr!("daemon.already_running", path = path.display())
+            } else {
+                tr!("command.game_running")
+            });
+        }
+        Err(err) => return fail(tr!("command.bad_save", error = err)),
+    }
+    match SaveState::load(&save::state_path()) {
+        Ok(Some(state)) => app.restore(state),
+        Ok(None) => return fail(tr!("command.no_save")),
+        Err(err) => return fail(tr!("command.bad_save", error = err)),
+    }
+    app.journal = Journal::open(paths::data_dir().join("journal.jsonl"));
+    app.status_cache = Some(prompt::cache_path());
+    app.hooks = Hooks::load();
+    for warning in &app.hooks.warnings {
+        eprintln!("hooks: {}", warning);
+    }
+    app.notifier = Notifier::load();
+    for warning in &app.notifier.warnings {
+        eprintln!("notifications: {}", warning);
+    }
+    let table = EventTable::load();
+    for warning in &table.warnings {
+        eprintln!("events: {}", warning);
+    }
+    events::install(table.definitions);
+    app.scripts = Scripts::load();
+    for warning in &app.scripts.warnings {
+        eprintln!("scripts: {}", warning);
+    }
+
+    let path = rpc::socket_path();
+    let listener = match listen(&path).await {
+        Ok(listener) => listener,
+        Err(err) => return fail(err.to_string()),
+    };
+    if let Some(addr) = http {
+        match http::start(addr).await {
+            Ok(bridge) => {
+                eprintln!("{}", tr!("http.listening", addr = bridge.addr));
+                app.http = Some(bridge);
+            }
+            Err(err) => return fail(format!("http: {}", err)),
+        }
+    }
+    let mut terminate = match signal(SignalKind::terminate()) {
+        Ok(terminate) => terminate,
+        Err(err) => return fail(err.to_string()),
+    };
+    eprintln!("{}", tr!("daemon.listening", path = path.display()));
+
+    app.journal
+        .record(JournalCategory::System, None, tr!("journal.daemon_started"));
+    let (events, _) = broadcast::channel(EVENT_BUFFER);
+    let shared = Arc::new(Mutex::new(Daemon {
+        published: app.journal.len(),
+        app,
+    }));
+
+    let mut ticks = tokio::time::interval(TICK_RATE);
+    let mut autosave = tokio::time::interval(save::AUTOSAVE_INTERVAL);
+    loop {
+        tokio::select! {
+            _ = ticks.tick() => {
+                let mut daemon = lock(&shared);
+                daemon.app.tick();
+                daemon.publish(&events);
+            }
+            _ = autosave.tick() => {
+                if let Err(err) = lock(&shared).app.save() {
+                    eprintln!("save: {}", err);
+                }
+            }
+            accepted = listener.accept() => match accepted {
+                Ok((stream, _)) => {
+                    tokio::spawn(serve(stream, shared.clone(), events.clone()));
+                }
+                Err(err) => tracing::warn!("could not accept a client: {}", err),
+            },
+            _ = tokio::signal::ctrl_c() => break,
+            _ = terminate.recv() => break,
+        }
+    }
+
+    let mut daemon = lock(&shared);
+    daemon
+        .app
+        .journal
+        .record(JournalCategory::System, None, tr!("journal.daemon_stopped"));
+    daemon.publish(&events);
+    let _ = fs::remove_file(&path);
+    match daemon.app.save() {
+        Ok(()) => 0,
+        Err(err) => fail(tr!("command.save_failed", error = err)),
+    }
+}
+
+/// Take over the socket, unless another daemon is still answering on it
+async fn listen(path: &Path) -> io::Result<UnixListener> {
+    if UnixStream::connect(path).await.is_ok() {
+        return Err(io::Error::new(
+            io::ErrorKind::AddrInUse,
+            tr!("daemon.already_running", path = path.display()),
+        ));
+    }
+    // Left behind by a daemon that didn't get to clean up
+    match fs::remove_file(path) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+        _ => {}
+    }
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let listener = UnixListener::bind(path)?;
+    // Whoever can connect can play, so only the owner may
+    fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+    Ok(listener)
+}
+
+/// Answer one client until it disconnects
+async fn serve(stream: UnixStream, shared: Shared, events: broadcast::Sender<JournalEntry>) {
+    let (reader, mut writer) = stream.into_split();
+    let (replies, mut outgoing) = mpsc::unbounded_channel::<Message>();
+    let writing = tokio::spawn(async move {
+        while let Some(message) = outgoing.recv().await {
+            let Ok(mut line) = serde_json::to_string(&message) else {
+                continue;
+            };
+            line.push('\n');
+            if writer.write_all(line.as_bytes()).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut subscription = None;
+    let mut lines = BufReader::new(reader).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        let reply = handle(&line, &shared, &events, &replies, &mut subscription);
+        if replies.send(reply).is_err() {
+            break;
+        }
+    }
+
+    if let Some(subscription) = subscription {
+        subscription.abort();
+    }
+    drop(replies);
+    let _ = writing.await;
+}
+
+/// Answer one line from a client
+fn handle(
+    line: &str,
+    shared: &Shared,
+    events: &broadcast::Sender<JournalEntry>,
+    replies: &mpsc::UnboundedSender<Message>,
+    subscription: &mut Option<JoinHandle<()>>,
+) -> Message {
+    let request: Request = match serde_json::from_str(line) {
+        Ok(request) => request,
+        Err(err) => return Message::error(None, PARSE_ERROR, err.to_string()),
+    };
+    let id = request.id;
+
+    match request.method.as_str() {
+        "get_state" => reply(id, state(&lock(shared).app)),
+        "perform_action" => {
+            let params: ActionParams = match serde_json::from_value(request.params) {
+                Ok(params) => params,
+                Err(err) => return Message::error(Some(id), INVALID_PARAMS, err.to_string()),
+            };
+            let mut daemon = lock(shared);
+            let outcome = perform(&mut daemon.app, params);
+            daemon.publish(events);
+            match outcome {
+                Ok(message) => reply(id, ActionResult { message }),
+                Err(message) => Message::error(Some(id), REFUSED, message),
+            }
+        }
+        "subscribe_events" => {
+            if subscription.is_none() {
+                let mut entries = events.subscribe();
+                let replies = replies.clone();
+                *subscription = Some(tokio::spawn(async move {
+                    loop {
+                        match entries.recv().await {
+                            Ok(entry) => {
+                                let Ok(params) = serde_json::to_value(entry) else {
+                                    continue;
+                                };
+                                if replies
+                                    .send(Message::notification("event", params))
+                                    .is_err()
+                                {
+                                    break;
+                                }
+                            }
+                            Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                            Err(broadcast::error::RecvError::Closed) => break,
+                        }
+                    }
+                }));
+            }
+            Message::result(id, serde_json::json!({ "subscribed": true }))
+        }
+        method => Message::error(
+            Some(id),
+            METHOD_NOT_FOUND,
+            format!("unknown method '{}'", method),
+        ),
+    }
+}
+
+/// Successful answer carrying `result`
+fn reply(id: u64, result: impl serde::Serialize) -> Message {
+    match serde_json::to_value(result) {
+        Ok(result) => Message::result(id, result),
+        Err(err) => Message::error(Some(id), REFUSED, err.to_string()),
+    }
+}
+
+/// The game as clients see it
+pub fn state(app: &App) -> RemoteState {
+    RemoteState {
+        game_state: app.game_state,
+        selected: app.household.selected_index(),
+        pets: app
+            .household
+            .pets
+            .iter()
+            .map(|companion| companion.pet.clone())
+            .collect(),
+        vacation: app.vacation.clone(),
+        status: app.status_message.clone(),
+    }
+}
+
+/// Run an action for a client: what happened, or why nothing did
+pub fn perform(app: &mut App, params: ActionParams) -> Result<String, String> {
+    if let Some(index) = params.pet {
+        if index >= app.household.len() {
+            return Err(tr!("daemon.no_such_pet", index = index));
+        }
+        app.household.select(index);
+    }
+
+    let action = params.action;
+    match (app.game_state, action) {
+        (GameState::GameOver, RemoteAction::Restart) => {}
+        (GameState::GameOver, _) => return Err(tr!("journal.game_over")),
+        (_, RemoteAction::Restart) => return Err(tr!("daemon.not_over")),
+        (GameState::Paused, RemoteAction::Pause) => {}
+        (GameState::Paused, _) => return Err(app.status_message.clone()),
+        _ => {}
+    }
+
+    // Every action that goes through is journaled; refusals only change
+    // the status line
+    let recorded = app.journal.len();
+    match action {
+        RemoteAction::Warm => app.warm_egg(),
+        RemoteAction::Feed => app.feed_pet(),
+        RemoteAction::Play => app.play_with_pet(),
+        RemoteAction::Clean => app.clean_pet(),
+        RemoteAction::Sleep => app.toggle_sleep(),
+        RemoteAction::Medicine => app.give_medicine(),
+        RemoteAction::Pat => app.pat_pet(),
+        RemoteAction::Pause => app.toggle_pause(),
+        RemoteAction::Vacation => app.toggle_vacation(),
+        RemoteAction::Match => app.match_pets(),
+        RemoteAction::Adopt | RemoteAction::Restart => start(app, action, params.setup)?,
+    }
+
+    match app.journal.since(recorded).last() {
+        Some(entry) => Ok(entry.message.clone()),
+        None => Err(app.status_message.clone()),
+    }
+}
+
+/// Hatch a pet from a client's wizard, either adopted or starting over
+fn start(app: &mut App, action: RemoteAction, setup: Option<SetupChoices>) -> Result<(), String> {
+    let choices = setup.ok_or_else(|| tr!("daemon.missing_setup"))?;
+    let name = setup::validate_name(&choices.name).map_err(|err| err.to_string())?;
+
+    if action == RemoteAction::Adopt {
+        app.adopt();
+    } else {
+        app.restart();
+    }
+    if app.game_state != GameState::Setup {
+        return Err(app.status_message.clone());
+    }
+
+    app.setup.name = name.to_string();
+    app.setup.species = choices.species;
+    app.setup.difficulty = choices.difficulty;
+    app.start_game();
+    Ok(())
+}
+
+/// Report a failure to start, returning the exit code for it
+fn fail(error: String) -> i32 {
+    eprintln!("mypet-tui: {}", error);
+    1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::Client;
+    use crate::pet::{LifeStage, Species};
+
+    fn params(action: RemoteAction) -> ActionParams {
+        ActionParams {
+            action,
+            pet: None,
+            setup: None,
+        }
+    }
+
+    #[test]
+    fn actions_report_what_happened() {
+        let mut app = App::new();
+        assert!(perform(&mut app, params(RemoteAction::Feed)).is_err());
+        assert!(perform(&mut app, params(RemoteAction::Warm)).is_ok());
+        assert!(
+            perform(
+                &mut app,
+                ActionParams {
+                    pet: Some(3),
+                    ..params(RemoteAction::Warm)
+                }
+            )
+            .is_err()
+        );
+
+        let adopt = ActionParams {
+            setup: Some(SetupChoices {
+                name: "Mochi".to_string(),
+                species: Species::Bunny,
+                difficulty: Default::default(),
+            }),
+            ..params(RemoteAction::Adopt)
+        };
+        assert!(perform(&mut app, adopt).is_ok());
+        assert_eq!(app.household.len(), 2);
+        assert_eq!(app.pet().species(), Species::Bunny);
+        assert!(perform(&mut app, params(RemoteAction::Restart)).is_err());
+    }
+
+    #[tokio::test]
+    async fn clients_act_through_the_socket() {
+        let mut app = App::new();
+        app.pet_mut().stage = LifeStage::Child;
+        let shared = Arc::new(Mutex::new(Daemon { app, published: 0 }));
+        let (events, _) = broadcast::channel(EVENT_BUFFER);
+        let (ours, theirs) = UnixStream::pair().unwrap();
+        tokio::spawn(serve(theirs, shared.clone(), events.clone()));
+
+        let mut client = Client::new(ours);
+        client.subscribe().await.unwrap();
+        assert!(client.act(params(RemoteAction::Feed)).await.is_ok());
+        assert!(client.act(params(RemoteAction::Warm)).await.is_err());
+
+        let state = client.state().await.unwrap();
+        assert_eq!(state.game_state, GameState::Playing);
+        assert_eq!(state.pets[0].stage, LifeStage::Child);
+
+        // The feeding was journaled and sent to the subscriber
+        let fed = lock(&shared).app.journal.since(0)[0].clone();
+        let mut events = Vec::new();
+        while events.is_empty() {
+            tokio::task::yield_now().await;
+            events = client.poll();
+        }
+        assert!(matches!(&events[0], crate::client::Update::Event(entry) if *entry == fed));
+    }
+}
@@ -0,0 +1,471 @@
+//! Just enough of the D-Bus wire protocol to show a desktop notification
+//!
+//! Connects to the session bus named by `DBUS_SESSION_BUS_ADDRESS`,
+//! authenticates as the current user and calls
+//! `org.freedesktop.Notifications.Notify`. Only `unix:path=` addresses are
+//! understood, and messages are always written little-endian.
+
+use std::env;
+use std::fs;
+use std::io;
+use std::os::unix::fs::MetadataExt;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::UnixStream;
+
+/// Message type of a method call
+const METHOD_CALL: u8 = 1;
+/// Message type of a successful reply
+const METHOD_RETURN: u8 = 2;
+/// Message type of a failed reply
+const ERROR: u8 = 3;
+/// Longest a notification may take, from connecting to the reply
+const NOTIFY_TIMEOUT: Duration = Duration::from_secs(10);
+/// Largest message the D-Bus specification allows
+const MAX_MESSAGE: usize = 128 * 1024 * 1024;
+
+/// How urgent a notification is, as the notification server understands it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Urgency {
+    Normal = 1,
+    Critical = 2,
+}
+
+/// A desktop notification
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    pub summary: String,
+    pub body: String,
+    pub urgency: Urgency,
+}
+
+/// Address of the session bus, if there is one
+pub fn session_address() -> Option<String> {
+    env::var("DBUS_SESSION_BUS_ADDRESS")
+        .ok()
+        .filter(|address| !address.is_empty())
+}
+
+/// Show a notification, returning the id the server gave it
+pub async fn notify(address: &str, notification: &Notification) -> io::Result<u32> {
+    tokio::time::timeout(NOTIFY_TIMEOUT, send(address, notification))
+        .await
+        .unwrap_or_else(|_| Err(io::Error::new(io::ErrorKind::TimedOut, "timed out")))
+}
+
+async fn send(address: &str, notification: &Notification) -> io::Result<u32> {
+    let mut bus = Connection::open(address).await?;
+    let mut body = Writer::default();
+    body.string("MyPet");
+    body.u32(0);
+    body.string("");
+    body.string(&notification.summary);
+    body.string(&notification.body);
+    body.array(4, |_| {});
+    body.array(8, |hints| {
+        hints.pad(8);
+        hints.string("urgency");
+        hints.signature("y");
+        hints.byte(notification.urgency as u8);
+    });
+    body.i32(-1);
+
+    let reply = bus
+        .call(
+            &[
+                Field::Path("/org/freedesktop/Notifications"),
+                Field::Interface("org.freedesktop.Notifications"),
+                Field::Member("Notify"),
+                Field::Destination("org.freedesktop.Notifications"),
+                Field::Signature("susssasa{sv}i"),
+            ],
+            &body.buf,
+        )
+        .await?;
+    Ok(reply.u32_at(0).unwrap_or_default())
+}
+
+/// An authenticated connection to a bus
+struct Connection {
+    stream: BufReader<UnixStream>,
+    serial: u32,
+}
+
+impl Connection {
+    /// Connect, authenticate and say hello
+    async fn open(address: &str) -> io::Result<Self> {
+        let stream = UnixStream::connect(socket_path(address)?).await?;
+        let mut stream = BufReader::new(stream);
+
+        let uid = fs::metadata("/proc/self")?.uid().to_string();
+        let uid: String = uid.bytes().map(|byte| format!("{:02x}", byte)).collect();
+        let auth = format!("\0AUTH EXTERNAL {}\r\n", uid);
+        stream.get_mut().write_all(auth.as_bytes()).await?;
+        let mut answer = String::new();
+        stream.read_line(&mut answer).await?;
+        if !answer.starts_with("OK ") {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                format!("bus refused authentication: {}", answer.trim()),
+            ));
+        }
+        stream.get_mut().write_all(b"BEGIN\r\n").await?;
+
+        let mut bus = Self { stream, serial: 0 };
+        bus.call(
+            &[
+                Field::Path("/org/freedesktop/DBus"),
+                Field::Interface("org.freedesktop.DBus"),
+                Field::Member("Hello"),
+                Field::Destination("org.freedesktop.DBus"),
+            ],
+            &[],
+        )
+        .await?;
+        Ok(bus)
+    }
+
+    /// Call a method and wait for its reply, skipping signals meanwhile
+    async fn call(&mut self, fields: &[Field<'_>], body: &[u8]) -> io::Result<Received> {
+        self.serial += 1;
+        let message = encode(METHOD_CALL, self.serial, fields, body);
+        self.stream.get_mut().write_all(&message).await?;
+
+        loop {
+            let received = read_message(&mut self.stream).await?;
+            if received.reply_serial != Some(self.serial) {
+                continue;
+            }
+            match received.kind {
+                METHOD_RETURN => return Ok(received),
+                ERROR => {
+                    return Err(io::Error::other(
+                        received
+                            .error_name
+                            .unwrap_or_else(|| "D-Bus error".to_string()),
+                    ));
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// The socket named by a bus address
+fn socket_path(address: &str) -> io::Result<PathBuf> {
+    address
+        .split(';')
+        .filter_map(|entry| entry.strip_prefix("unix:"))
+        .flat_map(|options| options.split(','))
+        .find_map(|option| option.strip_prefix("path="))
+        .map(PathBuf::from)
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!("unsupported bus address '{}'", address),
+            )
+        })
+}
+
+/// A header field
+#[derive(Debug, Clone, Copy)]
+pub enum Field<'a> {
+    Path(&'a str),
+    Interface(&'a str),
+    Member(&'a str),
+    /// Only buses reply
+    #[allow(dead_code)]
+    ReplySerial(u32),
+    Destination(&'a str),
+    Signature(&'a str),
+}
+
+/// Marshals values, padding each to its alignment from the start
+#[derive(Debug, Default)]
+pub struct Writer {
+    pub buf: Vec<u8>,
+}
+
+impl Writer {
+    pub fn pad(&mut self, alignment: usize) {
+        self.buf
+            .resize(self.buf.len().next_multiple_of(alignment), 0);
+    }
+
+    pub fn byte(&mut self, value: u8) {
+        self.buf.push(value);
+    }
+
+    pub fn u32(&mut self, value: u32) {
+        self.pad(4);
+        self.buf.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn i32(&mut self, value: i32) {
+        self.pad(4);
+        self.buf.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn string(&mut self, value: &str) {
+        self.u32(value.len() as u32);
+        self.buf.extend_from_slice(value.as_bytes());
+        self.buf.push(0);
+    }
+
+    pub fn signature(&mut self, value: &str) {
+        self.buf.push(value.len() as u8);
+        self.buf.extend_from_slice(value.as_bytes());
+        self.buf.push(0);
+    }
+
+    /// An array whose elements align to `alignment`, written by `elements`
+    pub fn array(&mut self, alignment: usize, elements: impl FnOnce(&mut Self)) {
+        self.u32(0);
+        let length_at = self.buf.len() - 4;
+        self.pad(alignment);
+        let start = self.buf.len();
+        elements(self);
+        let length = (self.buf.len() - start) as u32;
+        self.buf[length_at..length_at + 4].copy_from_slice(&length.to_le_bytes());
+    }
+}
+
+/// A whole message: header, then body
+pub fn encode(kind: u8, serial: u32, fields: &[Field], body: &[u8]) -> Vec<u8> {
+    let mut message = Writer::default();
+    message.byte(b'l');
+    message.byte(kind);
+    message.byte(0);
+    message.byte(1);
+    message.u32(body.len() as u32);
+    message.u32(serial);
+    message.array(8, |header| {
+        for field in fields {
+            header.pad(8);
+            match *field {
+                Field::Path(path) => {
+                    header.byte(1);
+                    header.signature("o");
+                    header.string(path);
+                }
+                Field::Interface(name) => {
+                    header.byte(2);
+                    header.signature("s");
+                    header.string(name);
+                }
+                Field::Member(name) => {
+                    header.byte(3);
+                    header.signature("s");
+                    header.string(name);
+                }
+                Field::ReplySerial(serial) => {
+                    header.byte(5);
+                    header.signature("u");
+                    header.u32(serial);
+                }
+                Field::Destination(name) => {
+                    header.byte(6);
+                    header.signature("s");
+                    header.string(name);
+                }
+                Field::Signature(signature) => {
+                    header.byte(8);
+                    header.signature("g");
+                    header.signature(signature);
+                }
+            }
+        }
+    });
+    message.pad(8);
+    message.buf.extend_from_slice(body);
+    message.buf
+}
+
+/// A message read from the bus
+#[derive(Debug, Clone, Default)]
+pub struct Received {
+    pub kind: u8,
+    /// Only needed to reply, which this never does
+    #[allow(dead_code)]
+    pub serial: u32,
+    pub member: Option<String>,
+    pub reply_serial: Option<u32>,
+    pub error_name: Option<String>,
+    pub body: Vec<u8>,
+    big_endian: bool,
+}
+
+impl Received {
+    /// The `u32` at `offset` in the body
+    fn u32_at(&self, offset: usize) -> Option<u32> {
+        let bytes: [u8; 4] = self.body.get(offset..offset + 4)?.try_into().ok()?;
+        Some(if self.big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        })
+    }
+}
+
+/// Read one message, in either byte order
+pub async fn read_message(stream: &mut (impl AsyncReadExt + Unpin)) -> io::Result<Received> {
+    let mut fixed = [0; 16];
+    stream.read_exact(&mut fixed).await?;
+    let big_endian = fixed[0] == b'B';
+    let number = |bytes: &[u8]| {
+        let bytes: [u8; 4] = bytes.try_into().unwrap_or_default();
+        if big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        }
+    };
+    let body_length = number(&fixed[4..8]) as usize;
+    let fields_length = number(&fixed[12..16]) as usize;
+    let header_length = (16 + fields_length).next_multiple_of(8);
+    if header_length + body_length > MAX_MESSAGE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "D-Bus message is too long",
+        ));
+    }
+
+    let mut rest = vec![0; header_length - 16 + body_length];
+    stream.read_exact(&mut rest).await?;
+    let mut message = fixed.to_vec();
+    message.extend_from_slice(&rest);
+
+    let mut received = Received {
+        kind: fixed[1],
+        serial: number(&fixed[8..12]),
+        body: message.split_off(header_length),
+        big_endian,
+        ..Received::default()
+    };
+
+    let malformed = || io::Error::new(io::ErrorKind::InvalidData, "malformed D-Bus header");
+    let mut at = 16;
+    while at < 16 + fields_length {
+        at = at.next_multiple_of(8);
+        let code = *message.get(at).ok_or_else(malformed)?;
+        let signature_length = *message.get(at + 1).ok_or_else(malformed)? as usize;
+        let signature = message
+            .get(at + 2..at + 2 + signature_length)
+            .ok_or_else(malformed)?
+            .to_vec();
+        at += 3 + signature_length;
+        match signature.as_slice() {
+            b"u" => {
+                at = at.next_multiple_of(4);
+                let value = number(message.get(at..at + 4).ok_or_else(malformed)?);
+                at += 4;
+                if code == 5 {
+                    received.reply_serial = Some(value);
+                }
+            }
+            b"s" | b"o" => {
+                at = at.next_multiple_of(4);
+                let length = number(message.get(at..at + 4).ok_or_else(malformed)?) as usize;
+                let value = message.get(at + 4..at + 4 + length).ok_or_else(malformed)?;
+                let value = String::from_utf8_lossy(value).into_owned();
+                at += 5 + length;
+                match code {
+                    3 => received.member = Some(value),
+                    4 => received.error_name = Some(value),
+                    _ => {}
+                }
+            }
+            b"g" => {
+                let length = *message.get(at).ok_or_else(malformed)? as usize;
+                at += 2 + length;
+            }
+            _ => return Err(malformed()),
+        }
+    }
+    Ok(received)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::UnixListener;
+
+    /// Pretend to be a session bus with a notification server on it,
+    /// returning the members called and the last body received
+    async fn stand_in(listener: UnixListener) -> (Vec<String>, Vec<u8>) {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut stream = BufReader::new(stream);
+        let mut auth = Vec::new();
+        stream.read_until(b'\n', &mut auth).await.unwrap();
+        assert!(auth.starts_with(b"\0AUTH EXTERNAL "));
+        stream
+            .get_mut()
+            .write_all(b"OK 0123456789abcdef\r\n")
+            .await
+            .unwrap();
+        let mut begin = String::new();
+        stream.read_line(&mut begin).await.unwrap();
+        assert_eq!(begin, "BEGIN\r\n");
+
+        let mut members = Vec::new();
+        let mut body = Vec::new();
+        for id in 1..=2 {
+            let call = read_message(&mut stream).await.unwrap();
+            members.push(call.member.unwrap_or_default());
+            body = call.body;
+            let mut reply = Writer::default();
+            reply.u32(id);
+            let reply = encode(
+                METHOD_RETURN,
+                100 + id,
+                &[Field::ReplySerial(call.serial)],
+                &reply.buf,
+            );
+            stream.get_mut().write_all(&reply).await.unwrap();
+        }
+        (members, body)
+    }
+
+    #[tokio::test]
+    async fn notifies_over_the_session_bus() {
+        let dir = env::temp_dir().join(format!("mypet-dbus-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("bus");
+        let _ = fs::remove_file(&path);
+        let bus = tokio::spawn(stand_in(UnixListener::bind(&path).unwrap()));
+
+        let notification = Notification {
+            summary: "MyPet".to_string(),
+            body: "Fluffy is very hungry!".to_string(),
+            urgency: Urgency::Critical,
+        };
+        let address = format!("unix:path={},guid=0123", path.display());
+        assert_eq!(notify(&address, &notification).await.unwrap(), 2);
+
+        let (members, body) = bus.await.unwrap();
+        assert_eq!(members, ["Hello", "Notify"]);
+        let text = String::from_utf8_lossy(&body);
+        assert!(text.contains("Fluffy is very hungry!"));
+        assert!(text.contains("urgency"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn refuses_huge_messages() {
+        let mut header = encode(METHOD_RETURN, 1, &[], &[]);
+        header[4..8].copy_from_slice(&u32::MAX.to_le_bytes());
+        let err = read_message(&mut header.as_slice()).await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn reads_bus_addresses() {
+        assert_eq!(
+            socket_path("unix:path=/run/user/1000/bus").unwrap(),
+            PathBuf::from("/run/user/1000/bus")
+        );
+        assert!(socket_path("unix:abstract=/tmp/dbus-x").is_err());
+    }
+}
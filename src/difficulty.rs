@@ -0,0 +1,126 @@
+//! Difficulty chosen when starting a new game
+//!
+//! Each preset scales how fast needs and egg warmth fall, how often random
+//! events happen and how likely the bad ones are.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::i18n;
+
+/// How forgiving the game is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+    /// Hard, without medicine or pausing
+    Hardcore,
+}
+
+impl Difficulty {
+    /// All difficulties, easiest first
+    pub const ALL: [Difficulty; 4] = [
+        Difficulty::Easy,
+        Difficulty::Normal,
+        Difficulty::Hard,
+        Difficulty::Hardcore,
+    ];
+
+    /// Name shown in the new-game wizard and the header
+    pub fn label(self) -> &'static str {
+        i18n::lookup(match self {
+            Difficulty::Easy => "difficulty.easy",
+            Difficulty::Normal => "difficulty.normal",
+            Difficulty::Hard => "difficulty.hard",
+            Difficulty::Hardcore => "difficulty.hardcore",
+        })
+    }
+
+    /// One-line summary of what the preset changes
+    pub fn description(self) -> &'static str {
+        i18n::lookup(match self {
+            Difficulty::Easy => "difficulty.easy_description",
+            Difficulty::Normal => "difficulty.normal_description",
+            Difficulty::Hard => "difficulty.hard_description",
+            Difficulty::Hardcore => "difficulty.hardcore_description",
+        })
+    }
+
+    /// Stat decay and egg cooling, as a percentage of Normal
+    pub fn decay_percent(self) -> u16 {
+        match self {
+            Difficulty::Easy => 50,
+            Difficulty::Normal => 100,
+            Difficulty::Hard => 150,
+            Difficulty::Hardcore => 200,
+        }
+    }
+
+    /// Scale a per-tick loss, rounding to the nearest point
+    pub fn scale(self, amount: u8) -> u8 {
+        let scaled = (amount as u16 * self.decay_percent() + 50) / 100;
+        scaled.min(u8::MAX as u16) as u8
+    }
+
+    /// Minimum time between random events
+    pub fn event_cooldown(self) -> Duration {
+        Duration::from_secs(match self {
+            Difficulty::Easy => 20,
+            Difficulty::Normal => 15,
+            Difficulty::Hard => 10,
+            Difficulty::Hardcore => 8,
+        })
+    }
+
+    /// Chance per update that an event happens once the cooldown is over
+    pub fn event_chance(self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.03,
+            Difficulty::Normal => 0.05,
+            Difficulty::Hard => 0.07,
+            Difficulty::Hardcore => 0.1,
+        }
+    }
+
+    /// Multiplier for the weight of events that hurt the pet
+    pub fn negative_event_weight(self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.5,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 1.5,
+            Difficulty::Hardcore => 2.0,
+        }
+    }
+
+    /// Whether medicine can be given
+    pub fn allows_medicine(self) -> bool {
+        self != Difficulty::Hardcore
+    }
+
+    /// Whether the game can be paused
+    pub fn allows_pause(self) -> bool {
+        self != Difficulty::Hardcore
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn harder_presets_lose_more() {
+        assert_eq!(Difficulty::Normal.scale(3), 3);
+        assert_eq!(Difficulty::Easy.scale(3), 2);
+        assert_eq!(Difficulty::Hard.scale(3), 5);
+        assert_eq!(Difficulty::Hardcore.scale(3), 6);
+
+        for pair in Difficulty::ALL.windows(2) {
+            assert!(pair[0].event_cooldown() > pair[1].event_cooldown());
+            assert!(pair[0].event_chance() < pair[1].event_chance());
+        }
+    }
+}
@@ -1,11 +1,53 @@
 //! Event system for random occurrences and special moments
-
+//!
+//! Random events are data: the built-in ones can be changed, and new ones
+//! added, in `events.json` in the config directory:
+//!
+//! ```json
+//! {
+//!   "events": [
+//!     {"id": "Lonely", "weight": 0},
+//!     {
+//!       "id": "Stargazing",
+//!       "message": "{name} watches the stars",
+//!       "condition": "time_of_day == \"night\" && energy > 40",
+//!       "weight": 0.2,
+//!       "effects": {"happiness": 10, "energy": -5},
+//!       "animation": "EffectSparkles",
+//!       "follow_up": "AskingForFood"
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! Entries with a built-in id change only the fields they give, though new
+//! effects need a message to go with them, since the built-in messages name
+//! the stat changes they make. Conditions
+//! read the stats, `stage`, `state`, `hour` and `time_of_day`; messages can
+//! fill in `{name}` and the stats. A follow-up happens the next time an
+//! event is due.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::sync::{Arc, LazyLock, RwLock};
 use std::time::{Duration, Instant};
 
-use crate::pet::{LifeStage, Pet, PetState};
+use chrono::{Local, Timelike};
+use rhai::{AST, Engine, ImmutableString, Scope};
+use serde::{Deserialize, Serialize};
+
+use crate::animation::types::AnimationType;
+use crate::i18n::{self, tr};
+use crate::paths;
+use crate::pet::Pet;
+use crate::stats::Stats;
+
+/// File name of the event definitions inside the config directory
+const EVENTS_FILE: &str = "events.json";
 
 /// Generate a random float between 0.0 and 1.0
-fn random_float() -> f32 {
+pub fn random_float() -> f32 {
     use std::collections::hash_map::DefaultHasher;
     use std::hash::{Hash, Hasher};
     use std::time::{SystemTime, UNIX_EPOCH};
@@ -23,7 +65,8 @@ fn random_float() -> f32 {
 }
 
 /// Types of events that can occur
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
 pub enum EventType {
     /// Pet made a mess (hygiene drop)
     MadeMess,
@@ -41,32 +84,100 @@ pub enum EventType {
     Lonely,
     /// Pet is hungry and asking for food
     AskingForFood,
+    /// Two pets played together (household only)
+    PlayedTogether,
+    /// A sick pet passed its cold on (household only)
+    CaughtSickness,
+    /// An event from the definitions file or a script, by its id
+    Custom(String),
 }
 
 impl EventType {
-    /// Get the message for this event
-    pub fn message(&self, pet_name: &str) -> String {
+    /// Every event type
+    pub const ALL: [EventType; 10] = [
+        EventType::MadeMess,
+        EventType::FoundTreasure,
+        EventType::BadDream,
+        EventType::LearnedTrick,
+        EventType::Evolved,
+        EventType::HappyMoment,
+        EventType::Lonely,
+        EventType::AskingForFood,
+        EventType::PlayedTogether,
+        EventType::CaughtSickness,
+    ];
+
+    /// Identifier used in files and payloads
+    pub fn id(&self) -> &str {
         match self {
-            EventType::MadeMess => format!("{} made a mess! (-20 hygiene)", pet_name),
-            EventType::FoundTreasure => {
-                format!("{} found a shiny object! (+10 happiness)", pet_name)
-            }
-            EventType::BadDream => format!("{} had a bad dream... (-10 happiness)", pet_name),
-            EventType::LearnedTrick => format!("{} learned a new trick! (+15 happiness)", pet_name),
-            EventType::Evolved => format!("{} evolved! 🎉", pet_name),
-            EventType::HappyMoment => {
-                format!("{} is having a wonderful time! (+20 happiness)", pet_name)
-            }
-            EventType::Lonely => format!("{} seems lonely...", pet_name),
-            EventType::AskingForFood => {
-                format!("{} is looking at you with hungry eyes...", pet_name)
-            }
+            EventType::MadeMess => "MadeMess",
+            EventType::FoundTreasure => "FoundTreasure",
+            EventType::BadDream => "BadDream",
+            EventType::LearnedTrick => "LearnedTrick",
+            EventType::Evolved => "Evolved",
+            EventType::HappyMoment => "HappyMoment",
+            EventType::Lonely => "Lonely",
+            EventType::AskingForFood => "AskingForFood",
+            EventType::PlayedTogether => "PlayedTogether",
+            EventType::CaughtSickness => "CaughtSickness",
+            EventType::Custom(id) => id,
         }
     }
+
+    /// Short human readable name
+    pub fn display_name(&self) -> &str {
+        i18n::lookup(match self {
+            EventType::MadeMess => "event_name.made_mess",
+            EventType::FoundTreasure => "event_name.found_treasure",
+            EventType::BadDream => "event_name.bad_dream",
+            EventType::LearnedTrick => "event_name.learned_trick",
+            EventType::Evolved => "event_name.evolved",
+            EventType::HappyMoment => "event_name.happy_moment",
+            EventType::Lonely => "event_name.lonely",
+            EventType::AskingForFood => "event_name.asking_for_food",
+            EventType::PlayedTogether => "event_name.played_together",
+            EventType::CaughtSickness => "event_name.caught_sickness",
+            EventType::Custom(id) => return id,
+        })
+    }
+
+    /// Get the message for this event; custom events bring their own
+    pub fn message(&self, pet_name: &str) -> String {
+        let key = match self {
+            EventType::MadeMess => "event.made_mess",
+            EventType::FoundTreasure => "event.found_treasure",
+            EventType::BadDream => "event.bad_dream",
+            EventType::LearnedTrick => "event.learned_trick",
+            EventType::Evolved => "event.evolved",
+            EventType::HappyMoment => "event.happy_moment",
+            EventType::Lonely => "event.lonely",
+            EventType::AskingForFood => "event.asking_for_food",
+            EventType::PlayedTogether => "event.played_together",
+            EventType::CaughtSickness => "event.caught_sickness",
+            EventType::Custom(id) => return id.clone(),
+        };
+        tr!(key, name = pet_name)
+    }
+}
+
+impl From<String> for EventType {
+    fn from(id: String) -> Self {
+        EventType::ALL
+            .into_iter()
+            .find(|event_type| event_type.id() == id)
+            .unwrap_or(EventType::Custom(id))
+    }
+}
+
+impl From<EventType> for String {
+    fn from(event_type: EventType) -> Self {
+        event_type.id().to_string()
+    }
 }
 
 /// A game event with timestamp
 #[derive(Debug, Clone)]
+#[allow(dead_code)]
 pub struct GameEvent {
     pub event_type: EventType,
     pub timestamp: Instant,
@@ -78,14 +189,14 @@ pub struct GameEvent {
 pub struct EventSystem {
     /// Last time an event was triggered
     last_event_time: Instant,
-    /// Minimum time between events
-    event_cooldown: Duration,
     /// History of recent events
     pub event_history: Vec<GameEvent>,
     /// Maximum events to keep in history
     max_history: usize,
     /// Pending event to display
     pub pending_event: Option<GameEvent>,
+    /// Event that happens next, whatever the odds
+    follow_up: Option<EventType>,
 }
 
 impl EventSystem {
@@ -93,192 +204,597 @@ impl EventSystem {
     pub fn new() -> Self {
         Self {
             last_event_time: Instant::now(),
-            event_cooldown: Duration::from_secs(15), // Events every 15 seconds max
             event_history: Vec::new(),
             max_history: 10,
             pending_event: None,
+            follow_up: None,
         }
     }
 
-    /// Update and potentially trigger events
-    pub fn update(&mut self, pet: &mut Pet, _delta_time: Duration) {
+    /// Whether a random event should happen now: the cooldown is over and
+    /// the dice say so
+    pub fn is_due(&self, pet: &Pet) -> bool {
         // Only trigger events if enough time has passed
-        if self.last_event_time.elapsed() < self.event_cooldown {
-            return;
+        if self.last_event_time.elapsed() < pet.difficulty.event_cooldown() {
+            return false;
         }
 
         // Don't trigger events for dead pets
         if !pet.state.is_alive() {
-            return;
+            return false;
         }
 
         // Don't trigger events during sleep
         if pet.state.is_sleeping() {
-            return;
+            return false;
         }
 
-        // Check for random events (5% chance per update after cooldown)
-        if random_float() < 0.05 {
-            self.try_trigger_event(pet);
-        }
+        // Check for random events (5% chance per update after cooldown on Normal)
+        random_float() < pet.difficulty.event_chance()
     }
 
-    /// Try to trigger a random event based on pet state
-    fn try_trigger_event(&mut self, pet: &mut Pet) {
-        let event_type = self.select_event_type(pet);
-
-        if let Some(event_type) = event_type {
-            // Apply event effects
-            self.apply_event_effects(event_type.clone(), pet);
+    /// Add an event to the history, dropping the oldest when it is full
+    pub fn remember(&mut self, event: GameEvent) {
+        self.event_history.push(event);
+        if self.event_history.len() > self.max_history {
+            self.event_history.remove(0);
+        }
+    }
 
-            // Create event
-            let event = GameEvent {
-                event_type: event_type.clone(),
-                timestamp: Instant::now(),
-                message: event_type.message(&pet.name),
-            };
+    /// Push the cooldown forward so a pause doesn't count towards it
+    pub fn shift_clock(&mut self, paused: Duration) {
+        self.last_event_time += paused;
+    }
 
-            // Add to history
-            self.event_history.push(event.clone());
-            if self.event_history.len() > self.max_history {
-                self.event_history.remove(0);
+    /// Let an event from the definitions happen to the pet, returning the
+    /// animation it plays, if any
+    pub fn trigger(&mut self, event_type: EventType, pet: &mut Pet) -> Option<AnimationType> {
+        let definitions = definitions();
+        let definition = definitions
+            .iter()
+            .find(|definition| definition.event_type == event_type);
+
+        let mut animation = None;
+        let mut message = None;
+        if let Some(definition) = definition {
+            for (stat, amount) in &definition.effects {
+                if let Some(value) = pet.stats.get_mut(stat) {
+                    value.change(*amount);
+                }
             }
+            self.follow_up = definition.follow_up.clone();
+            animation = definition.animation;
+            message = definition.render(pet);
+        }
+        let message = message.unwrap_or_else(|| event_type.message(&pet.name));
+        self.record(event_type, message);
+        animation
+    }
 
-            // Set as pending for display
-            self.pending_event = Some(event);
+    /// Remember an event that happened and hold it for display, restarting
+    /// the cooldown
+    pub fn record(&mut self, event_type: EventType, message: String) {
+        let event = GameEvent {
+            event_type,
+            timestamp: Instant::now(),
+            message,
+        };
+        self.remember(event.clone());
+
+        // Set as pending for display
+        self.pending_event = Some(event);
+
+        // Reset cooldown
+        self.last_event_time = Instant::now();
+    }
 
-            // Reset cooldown
-            self.last_event_time = Instant::now();
+    /// Select an appropriate event type based on pet state, among the
+    /// defined events and `custom` ones with their weights; a follow-up
+    /// comes first
+    pub fn select(&mut self, pet: &Pet, custom: Vec<(EventType, f32)>) -> Option<EventType> {
+        if let Some(follow_up) = self.follow_up.take() {
+            return Some(follow_up);
         }
+        let hour = Local::now().hour();
+        choose(&definitions(), pet, hour, custom)
     }
 
-    /// Select an appropriate event type based on pet state
-    fn select_event_type(&self, pet: &Pet) -> Option<EventType> {
-        use EventType::*;
+    /// Clear the pending event (call after displaying)
+    #[allow(dead_code)]
+    pub fn clear_pending(&mut self) {
+        self.pending_event = None;
+    }
+}
 
-        let mut possible_events = Vec::new();
+impl Default for EventSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-        // Check for evolution
-        // This is handled separately in the pet update, but we can add a celebration event
+/// A random event: when it can happen, how likely it is and what it does
+#[derive(Debug, Clone)]
+pub struct EventDefinition {
+    pub event_type: EventType,
+    /// What happened, with `{name}` and stat names in braces; built-in
+    /// events use their translated message when `None`
+    pub message: Option<String>,
+    /// When the event can happen; always when `None`
+    pub condition: Option<Condition>,
+    /// Chance relative to the other possible events; 0 never happens
+    pub weight: f32,
+    /// Whether it hurts the pet, so harder difficulties favour it
+    pub negative: bool,
+    /// Stat changes, by stat name
+    pub effects: Vec<(String, i64)>,
+    /// Animation played when it happens
+    pub animation: Option<AnimationType>,
+    /// Event that happens next time, whatever the odds
+    pub follow_up: Option<EventType>,
+}
 
-        // Mess event (more likely when hygiene is high - making messes!)
-        if pet.stats.hygiene.value() > 50 && pet.stage != LifeStage::Egg {
-            possible_events.push((MadeMess, 0.2));
+impl EventDefinition {
+    /// An event that can always happen and changes nothing
+    fn new(event_type: EventType) -> Self {
+        Self {
+            event_type,
+            message: None,
+            condition: None,
+            weight: 1.0,
+            negative: false,
+            effects: Vec::new(),
+            animation: None,
+            follow_up: None,
         }
+    }
 
-        // Found treasure (random happy event)
-        if pet.stats.happiness.value() > 40 {
-            possible_events.push((FoundTreasure, 0.15));
-        }
+    /// Whether the event can happen to the pet at this hour
+    fn allows(&self, pet: &Pet, hour: u32) -> bool {
+        self.condition
+            .as_ref()
+            .is_none_or(|condition| condition.holds(pet, hour))
+    }
 
-        // Bad dream (baby only)
-        if pet.stage == LifeStage::Baby && pet.stats.happiness.value() < 60 {
-            possible_events.push((BadDream, 0.25));
+    /// The message for the pet, if the definition has its own
+    fn render(&self, pet: &Pet) -> Option<String> {
+        let mut message = self.message.as_ref()?.replace("{name}", &pet.name);
+        for name in Stats::NAMES {
+            if let Some(value) = pet.stats.get(name) {
+                message = message.replace(&format!("{{{}}}", name), &value.to_string());
+            }
         }
+        Some(message)
+    }
+}
 
-        // Learned trick (child/teen/adult with high happiness)
-        if matches!(
-            pet.stage,
-            LifeStage::Child | LifeStage::Teen | LifeStage::Adult
-        ) && pet.stats.happiness.value() > 70
-        {
-            possible_events.push((LearnedTrick, 0.1));
-        }
+/// Evaluates conditions, which can only read the variables they are given
+static CONDITIONS: LazyLock<Engine> = LazyLock::new(|| {
+    let mut engine = Engine::new();
+    engine.set_strict_variables(true);
+    engine.set_max_operations(10_000);
+    engine.set_max_string_size(1024);
+    engine
+});
+
+/// An expression such as `hunger < 30 && stage != "egg"` over the stats,
+/// `stage`, `state`, `hour` (0-23) and `time_of_day`
+#[derive(Debug, Clone)]
+pub struct Condition {
+    ast: AST,
+}
 
-        // Happy moment (when stats are good)
-        if pet.stats.happiness.value() > 60
-            && pet.stats.health.value() > 70
-            && pet.stats.energy.value() > 50
-        {
-            possible_events.push((HappyMoment, 0.1));
-        }
+impl Condition {
+    /// Compile an expression, checking it gives true or false
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let pet = Pet::default();
+        let ast = CONDITIONS
+            .compile_expression_with_scope(&variables(&pet, 12), source)
+            .map_err(|err| err.to_string())?;
+        let condition = Self { ast };
+        condition.eval(&pet, 12)?;
+        Ok(condition)
+    }
 
-        // Lonely (low happiness)
-        if pet.stats.happiness.value() < 30 {
-            possible_events.push((Lonely, 0.3));
-        }
+    fn eval(&self, pet: &Pet, hour: u32) -> Result<bool, String> {
+        CONDITIONS
+            .eval_ast_with_scope::<bool>(&mut variables(pet, hour), &self.ast)
+            .map_err(|err| err.to_string())
+    }
+
+    /// Whether the condition holds; one that fails to evaluate doesn't
+    fn holds(&self, pet: &Pet, hour: u32) -> bool {
+        self.eval(pet, hour).unwrap_or_else(|err| {
+            tracing::warn!("event condition failed: {}", err);
+            false
+        })
+    }
+}
+
+/// What conditions can read about a pet; plain variables, as constants
+/// would be folded into the expression when it is compiled
+fn variables(pet: &Pet, hour: u32) -> Scope<'static> {
+    let mut scope = Scope::new();
+    for name in Stats::NAMES {
+        let value = pet.stats.get(name).unwrap_or_default();
+        scope.push(name, value as i64);
+    }
+    scope.push("stage", ImmutableString::from(pet.stage.id()));
+    scope.push("state", ImmutableString::from(pet.state.id()));
+    scope.push("hour", hour as i64);
+    scope.push("time_of_day", ImmutableString::from(time_of_day(hour)));
+    scope
+}
+
+/// `morning` (5-11), `afternoon` (12-16), `evening` (17-20) or `night`
+fn time_of_day(hour: u32) -> &'static str {
+    match hour {
+        5..=11 => "morning",
+        12..=16 => "afternoon",
+        17..=20 => "evening",
+        _ => "night",
+    }
+}
+
+/// The built-in random events
+fn builtin() -> Vec<EventDefinition> {
+    use EventType::*;
+
+    let define =
+        |event_type, condition: &str, weight, negative, effects: &[(&str, i64)]| EventDefinition {
+            condition: Some(Condition::parse(condition).expect("built-in conditions are valid")),
+            weight,
+            negative,
+            effects: effects
+                .iter()
+                .map(|&(stat, amount)| (stat.to_string(), amount))
+                .collect(),
+            ..EventDefinition::new(event_type)
+        };
+
+    vec![
+        // More likely when hygiene is high - making messes!
+        define(
+            MadeMess,
+            r#"hygiene > 50 && stage != "egg""#,
+            0.2,
+            true,
+            &[("hygiene", -20)],
+        ),
+        define(
+            FoundTreasure,
+            "happiness > 40",
+            0.15,
+            false,
+            &[("happiness", 10)],
+        ),
+        define(
+            BadDream,
+            r#"stage == "baby" && happiness < 60"#,
+            0.25,
+            true,
+            &[("happiness", -10)],
+        ),
+        define(
+            LearnedTrick,
+            r#"(stage == "child" || stage == "teen" || stage == "adult") && happiness > 70"#,
+            0.1,
+            false,
+            &[("happiness", 15)],
+        ),
+        // Journaled when the pet grows rather than drawn
+        EventDefinition {
+            weight: 0.0,
+            ..EventDefinition::new(Evolved)
+        },
+        define(
+            HappyMoment,
+            "happiness > 60 && health > 70 && energy > 50",
+            0.1,
+            false,
+            &[("happiness", 20), ("energy", 5)],
+        ),
+        define(Lonely, "happiness < 30", 0.3, true, &[("happiness", -5)]),
+        define(
+            AskingForFood,
+            r#"hunger < 30 && stage != "egg""#,
+            0.4,
+            false,
+            &[],
+        ),
+    ]
+}
+
+/// Event definitions in use: the built-in ones until others are installed
+static DEFINITIONS: LazyLock<RwLock<Arc<Vec<EventDefinition>>>> =
+    LazyLock::new(|| RwLock::new(Arc::new(builtin())));
+
+/// Use these definitions for every pet from now on
+pub fn install(definitions: Vec<EventDefinition>) {
+    *DEFINITIONS.write().unwrap_or_else(|err| err.into_inner()) = Arc::new(definitions);
+}
+
+/// The definitions in use
+fn definitions() -> Arc<Vec<EventDefinition>> {
+    DEFINITIONS
+        .read()
+        .unwrap_or_else(|err| err.into_inner())
+        .clone()
+}
+
+/// Whether an event is defined, so scripts can't take its id
+pub fn is_defined(event_type: &EventType) -> bool {
+    definitions()
+        .iter()
+        .any(|definition| definition.event_type == *event_type)
+}
 
-        // Asking for food (low hunger)
-        if pet.stats.hunger.value() < 30 && pet.stage != LifeStage::Egg {
-            possible_events.push((AskingForFood, 0.4));
+/// Draw one of the events that can happen to the pet, by weight
+fn choose(
+    definitions: &[EventDefinition],
+    pet: &Pet,
+    hour: u32,
+    custom: Vec<(EventType, f32)>,
+) -> Option<EventType> {
+    let mut possible_events = custom;
+    for definition in definitions {
+        if definition.weight > 0.0 && definition.allows(pet, hour) {
+            let mut weight = definition.weight;
+            // Harder difficulties make bad events more likely
+            if definition.negative {
+                weight *= pet.difficulty.negative_event_weight();
+            }
+            possible_events.push((definition.event_type.clone(), weight));
         }
+    }
+
+    // Weighted random selection
+    let total_weight: f32 = possible_events.iter().map(|(_, w)| w).sum();
+    if total_weight == 0.0 {
+        return None;
+    }
 
-        // Weighted random selection
-        let total_weight: f32 = possible_events.iter().map(|(_, w)| w).sum();
-        if total_weight == 0.0 {
-            return None;
+    let mut random = random_float() * total_weight;
+
+    for (event, weight) in possible_events {
+        random -= weight;
+        if random <= 0.0 {
+            return Some(event);
         }
+    }
 
-        let mut random = random_float() * total_weight;
+    // Fallback - should rarely reach here due to floating point
+    None
+}
 
-        for (event, weight) in possible_events {
-            random -= weight;
-            if random <= 0.0 {
-                return Some(event);
-            }
+/// The event definitions, with problems found loading them
+#[derive(Debug)]
+pub struct EventTable {
+    pub definitions: Vec<EventDefinition>,
+    /// Problems with the definitions file, for the player to fix
+    pub warnings: Vec<String>,
+}
+
+impl EventTable {
+    /// Only the built-in events
+    pub fn new() -> Self {
+        Self {
+            definitions: builtin(),
+            warnings: Vec::new(),
         }
+    }
 
-        // Fallback - should rarely reach here due to floating point
-        None
+    /// The built-in events changed and extended by the definitions file in
+    /// the config directory
+    pub fn load() -> Self {
+        let path = paths::config_dir().join(EVENTS_FILE);
+        match fs::read_to_string(&path) {
+            Ok(json) => Self::from_json(&json),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Self::new(),
+            Err(err) => Self {
+                warnings: vec![format!("could not read {}: {}", path.display(), err)],
+                ..Self::new()
+            },
+        }
     }
 
-    /// Apply effects of an event to the pet
-    fn apply_event_effects(&self, event_type: EventType, pet: &mut Pet) {
-        use EventType::*;
+    /// The built-in events changed and extended by the contents of a
+    /// definitions file
+    ///
+    /// Entries with a built-in id change only the fields they give; new
+    /// ids need a message.
+    pub fn from_json(json: &str) -> Self {
+        #[derive(Deserialize)]
+        struct EventsFile {
+            #[serde(default)]
+            events: Vec<serde_json::Value>,
+        }
 
-        match event_type {
-            MadeMess => {
-                pet.stats.hygiene.sub(20);
+        let mut table = Self::new();
+        let entries = match serde_json::from_str::<EventsFile>(json) {
+            Ok(file) => file.events,
+            Err(err) => {
+                table.warnings.push(format!("invalid events file: {}", err));
+                return table;
             }
-            FoundTreasure => {
-                pet.stats.happiness.add(10);
-            }
-            BadDream => {
-                pet.stats.happiness.sub(10);
-                // Wake up baby if sleeping
-                if pet.state.is_sleeping() {
-                    pet.state = PetState::Normal;
+        };
+
+        let mut seen = Vec::new();
+        for (index, entry) in entries.into_iter().enumerate() {
+            let number = index + 1;
+            match serde_json::from_value::<EventEntry>(entry) {
+                Ok(entry) => {
+                    let id = entry.id.clone();
+                    if seen.contains(&id) {
+                        table
+                            .warnings
+                            .push(format!("event {} ({}): defined twice", number, id));
+                        continue;
+                    }
+                    seen.push(id.clone());
+                    if let Err(problem) = table.define(entry) {
+                        table
+                            .warnings
+                            .push(format!("event {} ({}): {}", number, id, problem));
+                    }
                 }
+                Err(err) => table.warnings.push(format!("event {}: {}", number, err)),
             }
-            LearnedTrick => {
-                pet.stats.happiness.add(15);
-            }
-            HappyMoment => {
-                pet.stats.happiness.add(20);
-                pet.stats.energy.add(5);
-            }
-            Lonely => {
-                pet.stats.happiness.sub(5);
+        }
+        table.check_follow_ups();
+        table
+    }
+
+    /// Add an entry, or apply it to the event it names
+    fn define(&mut self, entry: EventEntry) -> Result<(), String> {
+        let event_type = EventType::from(entry.id);
+        if matches!(
+            event_type,
+            EventType::PlayedTogether | EventType::CaughtSickness
+        ) {
+            return Err("happens between pets and can't be changed".to_string());
+        }
+        if event_type.id().trim().is_empty() {
+            return Err("the id is empty".to_string());
+        }
+        let existing = self
+            .definitions
+            .iter()
+            .position(|definition| definition.event_type == event_type);
+        let mut definition = match existing {
+            Some(index) => self.definitions[index].clone(),
+            None if entry.message.is_none() => return Err("needs a message".to_string()),
+            None => EventDefinition::new(event_type),
+        };
+
+        if let Some(message) = entry.message {
+            check_placeholders(&message)?;
+            definition.message = Some(message);
+        }
+        if let Some(condition) = entry.condition {
+            definition.condition =
+                Some(Condition::parse(&condition).map_err(|err| format!("condition: {}", err))?);
+        }
+        if let Some(weight) = entry.weight {
+            if !weight.is_finite() || weight < 0.0 {
+                return Err(format!("weight {} must be 0 or more", weight));
             }
-            AskingForFood => {
-                // No stat change, just a message
+            definition.weight = weight;
+        }
+        if let Some(negative) = entry.negative {
+            definition.negative = negative;
+        }
+        if let Some(effects) = entry.effects {
+            if definition.message.is_none() {
+                return Err("new effects need a message to go with them".to_string());
             }
-            Evolved => {
-                // Evolution handled separately
+            if let Some(stat) = effects
+                .keys()
+                .find(|stat| !Stats::NAMES.contains(&stat.as_str()))
+            {
+                return Err(format!(
+                    "can't change '{}' (stats are {})",
+                    stat,
+                    Stats::NAMES.join(", ")
+                ));
             }
+            let mut effects: Vec<(String, i64)> = effects.into_iter().collect();
+            effects.sort();
+            definition.effects = effects;
+        }
+        if entry.animation.is_some() {
+            definition.animation = entry.animation;
+        }
+        if let Some(follow_up) = entry.follow_up {
+            definition.follow_up = Some(EventType::from(follow_up));
         }
-    }
 
-    /// Clear the pending event (call after displaying)
-    pub fn clear_pending(&mut self) {
-        self.pending_event = None;
+        match existing {
+            Some(index) => self.definitions[index] = definition,
+            None => self.definitions.push(definition),
+        }
+        Ok(())
     }
 
-    /// Get recent events as formatted strings
-    pub fn recent_events(&self, count: usize) -> Vec<&GameEvent> {
-        self.event_history.iter().rev().take(count).collect()
+    /// Drop follow-ups that name unknown events or go round in circles
+    fn check_follow_ups(&mut self) {
+        for index in 0..self.definitions.len() {
+            let definition = &self.definitions[index];
+            let Some(first) = definition.follow_up.clone() else {
+                continue;
+            };
+            let id = definition.event_type.id().to_string();
+            let follow = |event_type: &EventType| {
+                self.definitions
+                    .iter()
+                    .find(|definition| definition.event_type == *event_type)
+            };
+
+            let problem = if follow(&first).is_none() {
+                Some(format!("follow-up '{}' is not a defined event", first.id()))
+            } else {
+                // A chain longer than the table has to repeat itself
+                let mut next = Some(first);
+                let mut steps = 0;
+                while let Some(event_type) = &next
+                    && steps <= self.definitions.len()
+                {
+                    next = follow(event_type).and_then(|definition| definition.follow_up.clone());
+                    steps += 1;
+                }
+                next.is_some()
+                    .then(|| "follow-ups go round in circles".to_string())
+            };
+            if let Some(problem) = problem {
+                self.warnings.push(format!("event {}: {}", id, problem));
+                self.definitions[index].follow_up = None;
+            }
+        }
     }
 }
 
-impl Default for EventSystem {
+impl Default for EventTable {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// An entry in the definitions file
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EventEntry {
+    id: String,
+    message: Option<String>,
+    condition: Option<String>,
+    weight: Option<f32>,
+    negative: Option<bool>,
+    effects: Option<HashMap<String, i64>>,
+    animation: Option<AnimationType>,
+    follow_up: Option<String>,
+}
+
+/// Check a message only uses `{name}` and stat names in braces
+fn check_placeholders(message: &str) -> Result<(), String> {
+    let mut rest = message;
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}') else {
+            return Err("message has an unclosed '{'".to_string());
+        };
+        let placeholder = &rest[start + 1..start + end];
+        if placeholder != "name" && !Stats::NAMES.contains(&placeholder) {
+            return Err(format!("message can't fill in '{{{}}}'", placeholder));
+        }
+        rest = &rest[start + end + 1..];
+    }
+    Ok(())
+}
+
 #[cfg(test)]
+#[allow(clippy::items_after_test_module)]
 mod tests {
     use super::*;
+    use crate::genome::Genome;
+    use crate::pet::LifeStage;
+    use crate::stats::StatValue;
 
     #[test]
     fn test_event_message() {
@@ -289,12 +805,120 @@ mod tests {
     }
 
     #[test]
+    fn event_types_are_stored_by_id() {
+        let json = serde_json::to_string(&EventType::MadeMess).unwrap();
+        assert_eq!(json, r#""MadeMess""#);
+        let custom: EventType = serde_json::from_str(r#""pumpkin""#).unwrap();
+        assert_eq!(custom, EventType::Custom("pumpkin".to_string()));
+        assert_eq!(serde_json::to_string(&custom).unwrap(), r#""pumpkin""#);
+    }
+
+    /// Ids of the events that can happen to the pet at this hour
+    fn possible(definitions: &[EventDefinition], pet: &Pet, hour: u32) -> Vec<String> {
+        definitions
+            .iter()
+            .filter(|definition| definition.weight > 0.0 && definition.allows(pet, hour))
+            .map(|definition| definition.event_type.id().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn built_in_events_keep_their_conditions() {
+        let definitions = EventTable::new().definitions;
+        assert_eq!(definitions.len(), 8);
+
+        let mut pet = Pet::default();
+        pet.stage = LifeStage::Baby;
+        pet.stats.happiness = StatValue::new(50);
+        pet.stats.hygiene = StatValue::new(60);
+        pet.stats.hunger = StatValue::new(20);
+        assert_eq!(
+            possible(&definitions, &pet, 12),
+            ["MadeMess", "FoundTreasure", "BadDream", "AskingForFood"]
+        );
+
+        let made_mess = &definitions[0];
+        assert!(made_mess.negative);
+        assert_eq!(made_mess.effects, [("hygiene".to_string(), -20)]);
+        assert_eq!(made_mess.render(&pet), None);
+        assert_eq!(
+            EventType::HappyMoment.message("Rex"),
+            "Rex is having a wonderful time! (+20 happiness)"
+        );
+    }
+
+    #[test]
+    fn reads_definitions_and_reports_problems() {
+        let table = EventTable::from_json(
+            r#"{"events": [
+                {"id": "Lonely", "weight": 0},
+                {"id": "Stargazing", "message": "{name} watches the stars ({energy})",
+                 "condition": "time_of_day == \"night\" && energy > 40", "weight": 0.5,
+                 "effects": {"happiness": 10}, "animation": "EffectSparkles",
+                 "follow_up": "MadeMess"},
+                {"id": "Ping", "message": "ping", "follow_up": "Pong"},
+                {"id": "Pong", "message": "pong", "follow_up": "Ping"},
+                {"id": "Typo", "message": "x", "condtion": "true"},
+                {"id": "Nameless"},
+                {"id": "Odd", "message": "x", "condition": "luck > 3"},
+                {"id": "Sum", "message": "x", "condition": "hunger + 1"},
+                {"id": "Lucky", "message": "x", "effects": {"luck": 1}},
+                {"id": "Hello", "message": "hi {owner}"},
+                {"id": "Lost", "message": "x", "follow_up": "Nowhere"},
+                {"id": "HappyMoment", "effects": {"happiness": 5}},
+                {"id": "PlayedTogether", "weight": 2},
+                {"id": "Lonely", "weight": 1}
+            ]}"#,
+        );
+        assert_eq!(table.warnings.len(), 11, "{:#?}", table.warnings);
+
+        let ids: Vec<&str> = table
+            .definitions
+            .iter()
+            .map(|definition| definition.event_type.id())
+            .collect();
+        assert_eq!(ids[8..], ["Stargazing", "Ping", "Pong", "Lost"]);
+        let lonely = &table.definitions[6];
+        assert_eq!(lonely.weight, 0.0);
+        assert!(lonely.negative);
+        // Breaking the circle at its first event is enough
+        assert_eq!(table.definitions[9].follow_up, None);
+        assert!(table.definitions[10].follow_up.is_some());
+        assert_eq!(table.definitions[11].follow_up, None);
+
+        let stargazing = &table.definitions[8];
+        let mut pet = Pet::default();
+        pet.stage = LifeStage::Adult;
+        pet.stats.energy = StatValue::new(50);
+        assert!(stargazing.allows(&pet, 23));
+        assert!(!stargazing.allows(&pet, 12));
+        assert_eq!(
+            stargazing.render(&pet).unwrap(),
+            format!("{} watches the stars (50)", pet.name)
+        );
+        assert_eq!(stargazing.animation, Some(AnimationType::EffectSparkles));
+        assert_eq!(stargazing.follow_up, Some(EventType::MadeMess));
+    }
+
+    #[test]
+    fn follow_ups_come_first() {
+        let mut system = EventSystem::new();
+        system.follow_up = Some(EventType::Custom("Pong".to_string()));
+        let pet = Pet::default();
+        assert_eq!(
+            system.select(&pet, Vec::new()),
+            Some(EventType::Custom("Pong".to_string()))
+        );
+    }
+
+    #[test]
+    #[allow(unused_variables, unused_mut)]
     fn test_event_history_limit() {
         let mut system = EventSystem::new();
         system.max_history = 3;
 
         // Create a dummy pet for testing
-        let mut pet = Pet::new("Test");
+        let mut pet = Pet::new("Test", Genome::default());
 
         // Add 5 events manually
         for i in 0..5 {
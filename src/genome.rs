@@ -0,0 +1,266 @@
+//! Traits a pet inherits
+//!
+//! Eggs from the new-game wizard get a plain genome for the chosen species.
+//! Eggs from a match take each gene from one parent or the other, and every
+//! gene has a small chance to mutate on the way.
+
+use serde::{Deserialize, Serialize};
+
+use crate::events::random_float;
+use crate::i18n;
+use crate::pet::Species;
+use crate::stats::{StatValue, Stats};
+
+/// Chance for each gene to mutate when two genomes are crossed
+pub const MUTATION_CHANCE: f32 = 0.1;
+/// Largest bonus or penalty a stat modifier can reach
+pub const MAX_MODIFIER: i8 = 15;
+/// How far a mutation moves a stat modifier
+const MUTATION_STEP: i8 = 5;
+
+/// Color of the pet's fur
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Coat {
+    /// Drawn in the theme's pet color; the others have theme colors of
+    /// their own
+    #[default]
+    Plain,
+    Ginger,
+    Smoke,
+    Midnight,
+    Snow,
+}
+
+impl Coat {
+    /// All coats
+    pub const ALL: [Coat; 5] = [
+        Coat::Plain,
+        Coat::Ginger,
+        Coat::Smoke,
+        Coat::Midnight,
+        Coat::Snow,
+    ];
+
+    /// A coat picked at random
+    pub fn random() -> Self {
+        choose(&Self::ALL, &mut random_float)
+    }
+
+    /// Display name
+    pub fn label(self) -> &'static str {
+        i18n::lookup(match self {
+            Coat::Plain => "coat.plain",
+            Coat::Ginger => "coat.ginger",
+            Coat::Smoke => "coat.smoke",
+            Coat::Midnight => "coat.midnight",
+            Coat::Snow => "coat.snow",
+        })
+    }
+}
+
+/// Inherited bonuses (or penalties) to the stats a pet hatches with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StatModifiers {
+    pub hunger: i8,
+    pub happiness: i8,
+    pub energy: i8,
+    pub health: i8,
+    pub hygiene: i8,
+}
+
+impl StatModifiers {
+    /// Adjust freshly hatched stats
+    pub fn apply(&self, stats: &mut Stats) {
+        shift(&mut stats.hunger, self.hunger);
+        shift(&mut stats.happiness, self.happiness);
+        shift(&mut stats.energy, self.energy);
+        shift(&mut stats.health, self.health);
+        shift(&mut stats.hygiene, self.hygiene);
+    }
+
+    /// Non-zero modifiers with the i18n key of their stat
+    pub fn entries(&self) -> Vec<(&'static str, i8)> {
+        [
+            ("stat.hunger", self.hunger),
+            ("stat.happiness", self.happiness),
+            ("stat.energy", self.energy),
+            ("stat.health", self.health),
+            ("stat.hygiene", self.hygiene),
+        ]
+        .into_iter()
+        .filter(|(_, value)| *value != 0)
+        .collect()
+    }
+
+    fn genes_mut(&mut self) -> [&mut i8; 5] {
+        [
+            &mut self.hunger,
+            &mut self.happiness,
+            &mut self.energy,
+            &mut self.health,
+            &mut self.hygiene,
+        ]
+    }
+}
+
+/// Everything a pet passes on to its eggs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Genome {
+    pub species: Species,
+    pub coat: Coat,
+    pub modifiers: StatModifiers,
+}
+
+impl Genome {
+    /// A plain genome for a species
+    pub fn new(species: Species) -> Self {
+        Self {
+            species,
+            ..Self::default()
+        }
+    }
+
+    /// Set the coat
+    pub fn with_coat(mut self, coat: Coat) -> Self {
+        self.coat = coat;
+        self
+    }
+
+    /// Mix two parents' genes
+    pub fn cross(first: &Genome, second: &Genome) -> Self {
+        Self::cross_with(first, second, random_float)
+    }
+
+    /// Mix two parents' genes, drawing every random number from `roll`
+    fn cross_with(first: &Genome, second: &Genome, mut roll: impl FnMut() -> f32) -> Self {
+        let mut child = Genome {
+            species: inherit(first.species, second.species, &mut roll),
+            coat: inherit(first.coat, second.coat, &mut roll),
+            modifiers: StatModifiers::default(),
+        };
+
+        if roll() < MUTATION_CHANCE {
+            child.species = choose(&Species::ALL, &mut roll);
+        }
+        if roll() < MUTATION_CHANCE {
+            child.coat = choose(&Coat::ALL, &mut roll);
+        }
+
+        let mut from_first = first.modifiers;
+        let mut from_second = second.modifiers;
+        let genes = child.modifiers.genes_mut().into_iter().zip(
+            from_first
+                .genes_mut()
+                .into_iter()
+                .zip(from_second.genes_mut()),
+        );
+        for (gene, (a, b)) in genes {
+            *gene = inherit(*a, *b, &mut roll);
+            if roll() < MUTATION_CHANCE {
+                let step = if roll() < 0.5 {
+                    -MUTATION_STEP
+                } else {
+                    MUTATION_STEP
+                };
+                *gene = (*gene + step).clamp(-MAX_MODIFIER, MAX_MODIFIER);
+            }
+        }
+        child
+    }
+}
+
+/// One of two parents' versions of a gene
+fn inherit<T>(first: T, second: T, roll: &mut impl FnMut() -> f32) -> T {
+    if roll() < 0.5 { first } else { second }
+}
+
+/// Any of the possible versions of a gene
+fn choose<T: Copy>(all: &[T], roll: &mut impl FnMut() -> f32) -> T {
+    let index = (roll() * all.len() as f32) as usize;
+    all[index.min(all.len() - 1)]
+}
+
+/// Move a stat up or down
+fn shift(stat: &mut StatValue, by: i8) {
+    if by >= 0 {
+        stat.add(by.unsigned_abs());
+    } else {
+        stat.sub(by.unsigned_abs());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rolls(values: &[f32]) -> impl FnMut() -> f32 + '_ {
+        let mut values = values.iter().copied();
+        move || values.next().unwrap_or(0.99)
+    }
+
+    #[test]
+    fn crossing_mixes_parent_genes() {
+        let mut first = Genome::new(Species::Bunny).with_coat(Coat::Snow);
+        first.modifiers.health = 10;
+        let mut second = Genome::new(Species::Bear).with_coat(Coat::Ginger);
+        second.modifiers.health = -5;
+        second.modifiers.energy = 5;
+
+        // Species from the first parent, coat from the second, no mutations;
+        // then hunger, happiness and energy from the first, health from the
+        // second and hygiene from the first
+        let child = Genome::cross_with(
+            &first,
+            &second,
+            rolls(&[
+                0.1, 0.9, 0.5, 0.5, 0.1, 0.5, 0.1, 0.5, 0.1, 0.5, 0.9, 0.5, 0.1, 0.5,
+            ]),
+        );
+        assert_eq!(child.species, Species::Bunny);
+        assert_eq!(child.coat, Coat::Ginger);
+        assert_eq!(child.modifiers.energy, 0);
+        assert_eq!(child.modifiers.health, -5);
+    }
+
+    #[test]
+    fn mutations_stay_in_range() {
+        let mut parent = Genome::new(Species::Cat);
+        parent.modifiers.hunger = MAX_MODIFIER;
+
+        // Every gene mutates: species and coat become the first option and
+        // every modifier moves down
+        let child = Genome::cross_with(&parent, &parent, || 0.05);
+        assert_eq!(child.species, Species::Cat);
+        assert_eq!(child.modifiers.hunger, MAX_MODIFIER - MUTATION_STEP);
+
+        // Species mutates into the last option, hunger mutates up but can't
+        // pass the limit
+        let child = Genome::cross_with(
+            &parent,
+            &parent,
+            rolls(&[0.5, 0.5, 0.0, 0.99, 0.99, 0.5, 0.05, 0.9]),
+        );
+        assert_eq!(child.species, Species::Bear);
+        assert_eq!(child.modifiers.hunger, MAX_MODIFIER);
+    }
+
+    #[test]
+    fn modifiers_adjust_stats() {
+        let mut stats = Stats::new();
+        let modifiers = StatModifiers {
+            hunger: 10,
+            health: -20,
+            ..StatModifiers::default()
+        };
+        modifiers.apply(&mut stats);
+        assert_eq!(stats.hunger.value(), 60);
+        assert_eq!(stats.health.value(), 80);
+        assert_eq!(
+            modifiers.entries(),
+            vec![("stat.hunger", 10), ("stat.health", -20)]
+        );
+    }
+}
@@ -0,0 +1,173 @@
+//! Unicode or ASCII-only rendering
+//!
+//! Some terminals (serial consoles, the Linux console, older tmux) can't
+//! show emoji, box drawing or block characters. In ASCII mode every frame is
+//! passed through [`asciify`] after rendering, which swaps each non-ASCII
+//! symbol for a fallback of the same width, so text and art from anywhere in
+//! the game stay aligned. Accented letters lose their accents rather than
+//! turning into `?`, so translated text stays readable.
+
+use std::env;
+
+use ratatui::buffer::Buffer;
+use unicode_normalization::UnicodeNormalization;
+use unicode_width::UnicodeWidthStr;
+
+/// Which characters the terminal can be expected to show
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GlyphMode {
+    /// Everything, including emoji
+    Unicode,
+    /// Printable ASCII only
+    Ascii,
+}
+
+impl GlyphMode {
+    /// Guess from the environment's `TERM` and locale variables
+    pub fn detect() -> Self {
+        let term = env::var("TERM").unwrap_or_default();
+        let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+            .iter()
+            .find_map(|var| env::var(var).ok().filter(|value| !value.is_empty()))
+            .unwrap_or_default();
+        Self::from_env(&term, &locale)
+    }
+
+    /// Pick a mode for a `TERM` value and locale name
+    pub fn from_env(term: &str, locale: &str) -> Self {
+        let basic_term = matches!(term, "linux" | "dumb" | "vt100" | "vt102" | "vt220");
+        let locale = locale.to_lowercase();
+        let ascii_locale = locale == "c" || locale == "posix";
+        let utf8 = locale.contains("utf-8") || locale.contains("utf8");
+
+        if basic_term || (ascii_locale && !utf8) {
+            GlyphMode::Ascii
+        } else {
+            GlyphMode::Unicode
+        }
+    }
+
+    /// Name shown in the settings view
+    pub fn label(self) -> &'static str {
+        match self {
+            GlyphMode::Unicode => "Unicode",
+            GlyphMode::Ascii => "ASCII",
+        }
+    }
+
+    /// The other mode
+    pub fn toggled(self) -> Self {
+        match self {
+            GlyphMode::Unicode => GlyphMode::Ascii,
+            GlyphMode::Ascii => GlyphMode::Unicode,
+        }
+    }
+}
+
+/// ASCII stand-in for a single character
+fn fallback(c: char) -> char {
+    match c {
+        // Box drawing: lines and corners
+        '─' | '━' | '═' | '┄' | '┅' | '╌' | '╍' => '-',
+        '│' | '┃' | '║' | '┆' | '┇' | '╎' | '╏' => '|',
+        '\u{2500}'..='\u{257f}' => '+',
+        // Sparkline and gauge blocks, roughly by height
+        '▁' => '_',
+        '▂' | '▃' => '.',
+        '▄' | '▅' => ':',
+        '▆' | '▇' => '|',
+        '\u{2580}'..='\u{259f}' => '#',
+        // Glyphs used in messages, art and effects
+        '⚠' => '!',
+        '🎉' => '*',
+        '◕' => '^',
+        '‿' => '_',
+        '♥' => 'v',
+        '✦' => '+',
+        '…' => '.',
+        // Spanish punctuation
+        '¡' => '!',
+        '¿' => '?',
+        '«' | '»' => '"',
+        // Letters keep their base letter without the accent
+        _ => c.nfd().next().filter(char::is_ascii).unwrap_or('?'),
+    }
+}
+
+/// Replace every non-ASCII symbol in a rendered buffer
+///
+/// Wide symbols (emoji) take two cells; the cell they covered is blanked so
+/// the rest of the line doesn't shift.
+pub fn asciify(buf: &mut Buffer) {
+    let area = buf.area;
+
+    for y in area.top()..area.bottom() {
+        let mut pad = 0;
+        for x in area.left()..area.right() {
+            let cell = &mut buf[(x, y)];
+            if pad > 0 {
+                cell.set_symbol(" ");
+                pad -= 1;
+                continue;
+            }
+
+            let symbol = cell.symbol();
+            if symbol.is_ascii() {
+                continue;
+            }
+
+            pad = symbol.width().saturating_sub(1);
+            let replacement = symbol.chars().next().map_or('?', fallback);
+            cell.set_char(replacement);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::layout::Rect;
+    use ratatui::style::Style;
+
+    #[test]
+    fn detects_basic_terminals_and_locales() {
+        assert_eq!(
+            GlyphMode::from_env("xterm-256color", "en_US.UTF-8"),
+            GlyphMode::Unicode
+        );
+        assert_eq!(
+            GlyphMode::from_env("linux", "en_US.UTF-8"),
+            GlyphMode::Ascii
+        );
+        assert_eq!(GlyphMode::from_env("xterm", "C"), GlyphMode::Ascii);
+        assert_eq!(GlyphMode::from_env("xterm", ""), GlyphMode::Unicode);
+    }
+
+    #[test]
+    fn asciify_keeps_line_width() {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 12, 2));
+        buf.set_string(0, 0, "┌─🎉 ok─┐", Style::default());
+        buf.set_string(0, 1, "│(◕‿◕) ⚠", Style::default());
+
+        asciify(&mut buf);
+
+        let line = |y| {
+            (0..12)
+                .map(|x| buf[(x, y)].symbol().to_string())
+                .collect::<String>()
+        };
+        assert_eq!(line(0), "+-*  ok-+   ");
+        assert_eq!(line(1), "|(^_^) !    ");
+    }
+
+    #[test]
+    fn asciify_strips_accents() {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 16, 1));
+        buf.set_string(0, 0, "¡Año nuevo! ¿Ω?", Style::default());
+
+        asciify(&mut buf);
+
+        let line: String = (0..16).map(|x| buf[(x, 0)].symbol().to_string()).collect();
+        assert_eq!(line, "!Ano nuevo! ??? ");
+    }
+}
@@ -0,0 +1,568 @@
+//! Commands and webhooks run when something happens to a pet
+//!
+//! Hooks are read from `hooks.json` in the config directory:
+//!
+//! ```json
+//! {
+//!   "hooks": [
+//!     {"on": "stat_below", "stat": "hunger", "threshold": 20, "command": "notify-send hungry"},
+//!     {"on": "sick", "url": "http://127.0.0.1:9000/pet"},
+//!     {"on": "event", "event": "FoundTreasure", "command": "./treasure.sh"}
+//!   ]
+//! }
+//! ```
+//!
+//! Triggers are `stat_below`, `sick`, `stage_changed`, `hatched`, `died` and
+//! `event` (any random event, or only the one named). Commands run through
+//! `sh -c` with the JSON payload on stdin; URLs get it as a POST body. Only
+//! plain `http://` URLs are supported.
+
+use std::fs;
+use std::io;
+use std::process::Stdio;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::process::Command;
+
+use crate::app::App;
+use crate::commands::PetSummary;
+use crate::events::{EventType, GameEvent};
+use crate::i18n::{self, tr};
+use crate::paths;
+use crate::pet::{LifeStage, Pet, PetState};
+
+/// File name of the hooks inside the config directory
+const HOOKS_FILE: &str = "hooks.json";
+/// How long a command or request may take before it is given up on
+const HOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A value hooks can watch
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Stat {
+    Hunger,
+    Happiness,
+    Energy,
+    Health,
+    Hygiene,
+    /// Of an egg
+    Warmth,
+}
+
+impl Stat {
+    /// The value for a pet, if it has one at its stage
+    fn value(self, pet: &Pet) -> Option<u8> {
+        let stats = &pet.stats;
+        match (self, pet.stage == LifeStage::Egg) {
+            (Stat::Warmth, true) => Some(pet.get_warmth()),
+            (Stat::Health, true) => Some(pet.get_egg_health()),
+            (_, true) | (Stat::Warmth, false) => None,
+            (Stat::Hunger, false) => Some(stats.hunger.value()),
+            (Stat::Happiness, false) => Some(stats.happiness.value()),
+            (Stat::Energy, false) => Some(stats.energy.value()),
+            (Stat::Health, false) => Some(stats.health.value()),
+            (Stat::Hygiene, false) => Some(stats.hygiene.value()),
+        }
+    }
+
+    /// Display name
+    fn label(self) -> &'static str {
+        i18n::lookup(match self {
+            Stat::Hunger => "stat.hunger",
+            Stat::Happiness => "stat.happiness",
+            Stat::Energy => "stat.energy",
+            Stat::Health => "stat.health",
+            Stat::Hygiene => "stat.hygiene",
+            Stat::Warmth => "stat.warmth",
+        })
+    }
+}
+
+/// When a hook runs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "on", rename_all = "snake_case")]
+pub enum Trigger {
+    /// A stat falls below a threshold
+    StatBelow { stat: Stat, threshold: u8 },
+    /// The pet gets sick
+    Sick,
+    /// The pet hatches or grows up
+    StageChanged,
+    /// An egg hatches
+    Hatched,
+    /// The pet dies, or its egg fails
+    Died,
+    /// A random event happens, or only the one named
+    Event {
+        #[serde(default)]
+        event: Option<EventType>,
+    },
+}
+
+impl Trigger {
+    /// Name used in configs and payloads
+    fn name(&self) -> &'static str {
+        match self {
+            Trigger::StatBelow { .. } => "stat_below",
+            Trigger::Sick => "sick",
+            Trigger::StageChanged => "stage_changed",
+            Trigger::Hatched => "hatched",
+            Trigger::Died => "died",
+            Trigger::Event { .. } => "event",
+        }
+    }
+}
+
+/// Where a hook sends its payload
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Target {
+    /// Run through `sh -c`, payload on stdin
+    Command(String),
+    /// POST to a plain HTTP URL
+    Url(String),
+}
+
+/// One entry of the hooks file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hook {
+    pub trigger: Trigger,
+    pub target: Target,
+}
+
+/// A hook entry as written in the file
+#[derive(Debug, Deserialize)]
+struct HookEntry {
+    #[serde(flatten)]
+    trigger: Trigger,
+    command: Option<String>,
+    url: Option<String>,
+}
+
+/// What a hook receives
+#[derive(Debug, Clone, Serialize)]
+pub struct Payload {
+    /// Which trigger fired
+    pub trigger: &'static str,
+    pub timestamp: DateTime<Utc>,
+    /// What happened, in words
+    pub message: String,
+    pub pet: PetSummary,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stat: Option<Stat>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event: Option<EventType>,
+}
+
+/// A pet as it was at the last check
+#[derive(Debug, Clone)]
+struct Seen {
+    pet: Pet,
+    /// Its recent random events, oldest first
+    events: Vec<GameEvent>,
+}
+
+/// The configured hooks and what they have already been told
+#[derive(Debug, Default)]
+pub struct Hooks {
+    pub hooks: Vec<Hook>,
+    /// Problems found in the hooks file
+    pub warnings: Vec<String>,
+    /// Every pet at the last check; `None` before the first one
+    seen: Option<Vec<Seen>>,
+}
+
+impl Hooks {
+    /// Read the hooks file; no file means no hooks
+    pub fn load() -> Self {
+        let path = paths::config_dir().join(HOOKS_FILE);
+        match fs::read_to_string(&path) {
+            Ok(json) => Self::from_json(&json),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Self::default(),
+            Err(err) => Self {
+                warnings: vec![format!("could not read {}: {}", path.display(), err)],
+                ..Self::default()
+            },
+        }
+    }
+
+    /// Build the hooks from the contents of a hooks file
+    pub fn from_json(json: &str) -> Self {
+        #[derive(Deserialize)]
+        struct HooksFile {
+            #[serde(default)]
+            hooks: Vec<Value>,
+        }
+
+        let mut hooks = Self::default();
+        let entries = match serde_json::from_str::<HooksFile>(json) {
+            Ok(file) => file.hooks,
+            Err(err) => {
+                hooks.warnings.push(format!("invalid hooks file: {}", err));
+                return hooks;
+            }
+        };
+
+        for (index, entry) in entries.into_iter().enumerate() {
+            let number = index + 1;
+            let entry: HookEntry = match serde_json::from_value(entry) {
+                Ok(entry) => entry,
+                Err(err) => {
+                    hooks.warnings.push(format!("hook {}: {}", number, err));
+                    continue;
+                }
+            };
+            let target = match (entry.command, entry.url) {
+                (Some(command), None) => Target::Command(command),
+                (None, Some(url)) if url.starts_with("http://") => Target::Url(url),
+                (None, Some(url)) => {
+                    hooks.warnings.push(format!(
+                        "hook {}: only http:// URLs are supported, not '{}'",
+                        number, url
+                    ));
+                    continue;
+                }
+                _ => {
+                    hooks
+                        .warnings
+                        .push(format!("hook {}: needs either a command or a url", number));
+                    continue;
+                }
+            };
+            hooks.hooks.push(Hook {
+                trigger: entry.trigger,
+                target,
+            });
+        }
+        hooks
+    }
+
+    /// Run the hooks for whatever happened since the last check
+    pub fn check(&mut self, app: &App) {
+        for (hook, payload) in self.fired(app) {
+            tokio::spawn(run(hook.target.clone(), payload));
+        }
+    }
+
+    /// Hooks whose trigger fired since the last check, with their payloads
+    fn fired(&mut self, app: &App) -> Vec<(&Hook, Payload)> {
+        if self.hooks.is_empty() {
+            return Vec::new();
+        }
+
+        let now: Vec<Seen> = app
+            .household
+            .pets
+            .iter()
+            .map(|companion| Seen {
+                pet: companion.pet.clone(),
+                events: companion.event_system.event_history.clone(),
+            })
+            .collect();
+        let Some(before) = self.seen.replace(now.clone()) else {
+            return Vec::new();
+        };
+
+        let mut fired = Vec::new();
+        for old in &before {
+            // Pets are told apart by when they were born
+            let current = now
+                .iter()
+                .find(|seen| seen.pet.birth_time == old.pet.birth_time);
+            let reported = old.events.last().map(|event| event.timestamp);
+
+            for hook in &self.hooks {
+                let Some(seen) = current else {
+                    // Only eggs leave the household, when they fail; the last
+                    // one stays until a new game, already reported dead
+                    if hook.trigger == Trigger::Died
+                        && old.pet.stage == LifeStage::Egg
+                        && !is_dead(&old.pet)
+                    {
+                        let message = tr!("journal.egg_lost", name = old.pet.name);
+                        fired.push((hook, payload(&hook.trigger, &old.pet, message)));
+                    }
+                    continue;
+                };
+                let (was, pet) = (&old.pet, &seen.pet);
+                match &hook.trigger {
+                    Trigger::StatBelow { stat, threshold } => {
+                        if let (Some(before), Some(value)) = (stat.value(was), stat.value(pet))
+                            && before >= *threshold
+                            && value < *threshold
+                        {
+                            let message = tr!("stat.dropped", stat = stat.label(), value = value);
+                            fired.push((
+                                hook,
+                                Payload {
+                                    stat: Some(*stat),
+                                    value: Some(value),
+                                    ..payload(&hook.trigger, pet, message)
+                                },
+                            ));
+                        }
+                    }
+                    Trigger::Sick if !was.state.is_sick() && pet.state.is_sick() => {
+                        let message = tr!("journal.got_sick", name = pet.name);
+                        fired.push((hook, payload(&hook.trigger, pet, message)));
+                    }
+                    Trigger::StageChanged if was.stage != pet.stage => {
+                        fired.push((hook, payload(&hook.trigger, pet, stage_message(pet))));
+                    }
+                    Trigger::Hatched if was.stage == LifeStage::Egg && pet.stage != was.stage => {
+                        fired.push((hook, payload(&hook.trigger, pet, stage_message(pet))));
+                    }
+                    Trigger::Died if !is_dead(was) && is_dead(pet) => {
+                        let message = if pet.stage == LifeStage::Egg {
+                            tr!("journal.egg_lost", name = pet.name)
+                        } else {
+                            tr!("status.dead", name = pet.name)
+                        };
+                        fired.push((hook, payload(&hook.trigger, pet, message)));
+                    }
+                    Trigger::Event { event: wanted } => {
+                        let new = seen
+                            .events
+                            .iter()
+                            .filter(|event| reported.is_none_or(|at| event.timestamp > at));
+                        for event in new {
+                            if wanted
+                                .as_ref()
+                                .is_none_or(|wanted| *wanted == event.event_type)
+                            {
+                                fired.push((
+                                    hook,
+                                    Payload {
+                                        event: Some(event.event_type.clone()),
+                                        ..payload(&hook.trigger, pet, event.message.clone())
+                                    },
+                                ));
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        fired
+    }
+}
+
+/// A payload with nothing but the basics filled in
+fn payload(trigger: &Trigger, pet: &Pet, message: String) -> Payload {
+    Payload {
+        trigger: trigger.name(),
+        timestamp: Utc::now(),
+        message,
+        pet: PetSummary::new(pet),
+        stat: None,
+        value: None,
+        event: None,
+    }
+}
+
+/// Whether a pet is gone, counting a failed egg
+fn is_dead(pet: &Pet) -> bool {
+    pet.state == PetState::Dead || pet.is_egg_dead()
+}
+
+/// What the journal says about a pet's new stage
+fn stage_message(pet: &Pet) -> String {
+    if pet.stage == LifeStage::Baby {
+        tr!("journal.hatched", name = pet.name)
+    } else {
+        tr!(
+            "journal.evolved",
+            name = pet.name,
+            stage = pet.stage.display_name()
+        )
+    }
+}
+
+/// Deliver a payload, logging anything that goes wrong
+async fn run(target: Target, payload: Payload) {
+    let result = match serde_json::to_vec(&payload) {
+        Ok(body) => tokio::time::timeout(HOOK_TIMEOUT, deliver(&target, payload.trigger, body))
+            .await
+            .unwrap_or_else(|_| Err(io::Error::new(io::ErrorKind::TimedOut, "timed out"))),
+        Err(err) => Err(err.into()),
+    };
+    if let Err(err) = result {
+        tracing::warn!("hook {:?} failed: {}", target, err);
+    }
+}
+
+async fn deliver(target: &Target, trigger: &str, body: Vec<u8>) -> io::Result<()> {
+    match target {
+        Target::Command(command) => {
+            let mut child = Command::new("sh")
+                .arg("-c")
+                .arg(command)
+                .env("MYPET_TRIGGER", trigger)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::null())
+                .kill_on_drop(true)
+                .spawn()?;
+            if let Some(mut stdin) = child.stdin.take() {
+                // Commands that don't read the payload close stdin early
+                let _ = stdin.write_all(&body).await;
+            }
+            let status = child.wait().await?;
+            if status.success() {
+                Ok(())
+            } else {
+                Err(io::Error::other(status.to_string()))
+            }
+        }
+        Target::Url(url) => post(url, &body).await,
+    }
+}
+
+/// POST a JSON body to a plain HTTP URL
+async fn post(url: &str, body: &[u8]) -> io::Result<()> {
+    let invalid = || {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid url '{}'", url),
+        )
+    };
+    let rest = url.strip_prefix("http://").ok_or_else(invalid)?;
+    let (host, path) = match rest.find('/') {
+        Some(slash) => rest.split_at(slash),
+        None => (rest, "/"),
+    };
+    if host.is_empty() {
+        return Err(invalid());
+    }
+    let address = if host.contains(':') {
+        host.to_string()
+    } else {
+        format!("{}:80", host)
+    };
+
+    let mut request = format!(
+        "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        path,
+        host,
+        body.len()
+    )
+    .into_bytes();
+    request.extend_from_slice(body);
+
+    let mut stream = TcpStream::connect(address).await?;
+    stream.write_all(&request).await?;
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await?;
+    let status = response.split_whitespace().nth(1).unwrap_or_default();
+    if status.starts_with('2') {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!("server answered {}", status)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::StatValue;
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn reads_hooks_and_reports_problems() {
+        let hooks = Hooks::from_json(
+            r#"{"hooks": [
+                {"on": "stat_below", "stat": "hunger", "threshold": 20, "command": "true"},
+                {"on": "event", "url": "http://127.0.0.1:9000/"},
+                {"on": "sick", "url": "https://example.com"},
+                {"on": "sick"},
+                {"on": "dancing", "command": "true"}
+            ]}"#,
+        );
+        assert_eq!(hooks.hooks.len(), 2);
+        assert_eq!(hooks.hooks[1].trigger, Trigger::Event { event: None });
+        assert_eq!(hooks.warnings.len(), 3);
+    }
+
+    #[test]
+    fn fires_when_things_change() {
+        let mut hooks = Hooks::from_json(
+            r#"{"hooks": [
+                {"on": "stat_below", "stat": "hunger", "threshold": 20, "command": "true"},
+                {"on": "hatched", "command": "true"},
+                {"on": "stage_changed", "command": "true"},
+                {"on": "sick", "command": "true"},
+                {"on": "event", "event": "FoundTreasure", "command": "true"}
+            ]}"#,
+        );
+        let mut app = App::new();
+        assert!(hooks.fired(&app).is_empty());
+
+        app.pet_mut().stage = LifeStage::Baby;
+        app.pet_mut().stats.hunger = StatValue::new(25);
+        let fired: Vec<&str> = hooks.fired(&app).iter().map(|(_, p)| p.trigger).collect();
+        assert_eq!(fired, ["hatched", "stage_changed"]);
+
+        app.pet_mut().stats.hunger = StatValue::new(15);
+        let fired = hooks.fired(&app);
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].1.stat, Some(Stat::Hunger));
+        assert_eq!(fired[0].1.value, Some(15));
+        assert!(hooks.fired(&app).is_empty());
+
+        app.household
+            .selected_mut()
+            .event_system
+            .remember(GameEvent {
+                event_type: EventType::FoundTreasure,
+                timestamp: std::time::Instant::now(),
+                message: "Fluffy found a shiny pebble".to_string(),
+            });
+        let fired = hooks.fired(&app);
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].1.event, Some(EventType::FoundTreasure));
+    }
+
+    #[test]
+    fn died_fires_once_through_a_new_game() {
+        let mut hooks = Hooks::from_json(r#"{"hooks": [{"on": "died", "command": "true"}]}"#);
+        let mut app = App::new();
+        assert!(hooks.fired(&app).is_empty());
+
+        if let Some(egg) = &mut app.pet_mut().egg_stats {
+            egg.is_dead = true;
+        }
+        let fired: Vec<&str> = hooks.fired(&app).iter().map(|(_, p)| p.trigger).collect();
+        assert_eq!(fired, ["died"]);
+
+        app.start_game();
+        assert!(hooks.fired(&app).is_empty());
+    }
+
+    #[tokio::test]
+    async fn posts_payloads() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = vec![0; 4096];
+            let read = stream.read(&mut request).await.unwrap();
+            stream
+                .write_all(b"HTTP/1.1 204 No Content\r\n\r\n")
+                .await
+                .unwrap();
+            String::from_utf8_lossy(&request[..read]).into_owned()
+        });
+
+        post(&url, br#"{"trigger":"sick"}"#).await.unwrap();
+        let request = server.await.unwrap();
+        assert!(request.starts_with("POST /hook HTTP/1.1"));
+        assert!(request.ends_with(r#"{"trigger":"sick"}"#));
+    }
+}
@@ -0,0 +1,451 @@
+//! Every pet the player looks after, and how they get along
+//!
+//! Each pet is wrapped in a [`Companion`] that owns the systems running
+//! alongside it. Actions go to the selected pet; the household as a whole
+//! occasionally has pets play together or pass on a cold.
+
+use std::mem;
+use std::time::{Duration, Instant};
+
+use crate::animation::types::AnimationType;
+use crate::events::{EventSystem, EventType, GameEvent, random_float};
+use crate::i18n::tr;
+use crate::journal::{Journal, JournalCategory};
+use crate::pet::{ActionError, LifeStage, Pet, PetState};
+use crate::scripts::Scripts;
+use crate::stats::StatHistory;
+use crate::widgets::AnimatedPet;
+
+/// Most pets a household can hold
+pub const MAX_PETS: usize = 4;
+/// How often a stats snapshot is added to the history
+const HISTORY_INTERVAL: Duration = Duration::from_secs(5);
+/// Minimum time between two interactions
+const INTERACTION_COOLDOWN: Duration = Duration::from_secs(20);
+/// Chance per update that pets interact once the cooldown is over
+const INTERACTION_CHANCE: f32 = 0.05;
+
+/// A pet with its own events, animation and history
+#[derive(Debug)]
+pub struct Companion {
+    /// The pet
+    pub pet: Pet,
+    /// Random occurrences for this pet
+    pub event_system: EventSystem,
+    /// How the pet is drawn
+    pub animated_pet: AnimatedPet,
+    /// Snapshots of the pet's stats over time
+    pub stats_history: StatHistory,
+    /// Last time a history snapshot was taken
+    last_history_sample: Instant,
+    /// Stage and state last written to the journal
+    observed: (LifeStage, PetState),
+}
+
+impl Companion {
+    /// Wrap a pet, starting its systems fresh
+    pub fn new(pet: Pet) -> Self {
+        Self {
+            observed: (pet.stage, pet.state),
+            animated_pet: AnimatedPet::new().with_genome(&pet.genome),
+            pet,
+            event_system: EventSystem::new(),
+            stats_history: StatHistory::default(),
+            last_history_sample: Instant::now(),
+        }
+    }
+
+    /// Record a stats snapshot for hatched pets every few seconds
+    pub fn sample_history(&mut self) {
+        if self.pet.stage == LifeStage::Egg {
+            return;
+        }
+
+        if self.last_history_sample.elapsed() >= HISTORY_INTERVAL {
+            self.stats_history.record(&self.pet.stats);
+            self.last_history_sample = Instant::now();
+        }
+    }
+
+    /// Journal stage and state changes the pet went through on its own
+    pub fn journal_state_changes(&mut self, journal: &mut Journal) {
+        let (stage, state) = self.observed;
+        let name = &self.pet.name;
+
+        if self.pet.stage != stage {
+            let message = if stage == LifeStage::Egg {
+                tr!("journal.hatched", name = name)
+            } else {
+                tr!(
+                    "journal.evolved",
+                    name = name,
+                    stage = self.pet.stage.display_name()
+                )
+            };
+            journal.record(JournalCategory::State, Some(EventType::Evolved), message);
+        }
+
+        if mem::discriminant(&self.pet.state) != mem::discriminant(&state) {
+            let message = match self.pet.state {
+                PetState::Normal if state.is_sick() => tr!("journal.recovered", name = name),
+                PetState::Normal => tr!("journal.woke", name = name),
+                PetState::Sleeping { .. } => tr!("journal.fell_asleep", name = name),
+                PetState::Sick { .. } => tr!("journal.got_sick", name = name),
+                PetState::Dead => tr!("status.dead", name = name),
+            };
+            journal.record(JournalCategory::State, None, message);
+        }
+
+        self.sync_observed();
+    }
+
+    /// Remember the current stage and state as already journaled
+    pub fn sync_observed(&mut self) {
+        self.observed = (self.pet.stage, self.pet.state);
+    }
+
+    /// Pick the idle animation matching the pet's state
+    pub fn update_animation_state(&mut self) {
+        let animated = &mut self.animated_pet;
+        match self.pet.state {
+            PetState::Normal => {
+                // Check happiness level for idle animation
+                if self.pet.stats.happiness.value() < 30 {
+                    animated.set_idle_sad();
+                } else if self.pet.stats.happiness.value() > 70 {
+                    animated.set_idle_happy();
+                } else {
+                    animated.set_idle();
+                }
+            }
+            PetState::Sleeping { .. } => animated.set_idle_sleeping(),
+            PetState::Sick { .. } => animated.trigger(AnimationType::TransitionGetSick),
+            PetState::Dead => animated.trigger(AnimationType::TransitionDie),
+        }
+    }
+
+    /// Maybe let a random event happen, defined or registered by a script;
+    /// `index` is where the pet sits in the household
+    pub fn roll_event(&mut self, index: usize, scripts: &mut Scripts) {
+        if !self.event_system.is_due(&self.pet) {
+            return;
+        }
+        let custom = scripts.candidates(index, &self.pet);
+        let Some(event_type) = self.event_system.select(&self.pet, custom) else {
+            return;
+        };
+        match scripts.happen(index, &event_type, &mut self.pet) {
+            Some(message) => self.event_system.record(event_type, message),
+            None => {
+                if let Some(animation) = self.event_system.trigger(event_type, &mut self.pet) {
+                    self.animated_pet.trigger(animation);
+                }
+            }
+        }
+    }
+
+    /// Push every timer forward so a pause doesn't count as time passed
+    pub fn shift_clock(&mut self, paused: Duration) {
+        self.pet.shift_clock(paused);
+        self.event_system.shift_clock(paused);
+        self.last_history_sample += paused;
+    }
+
+    /// The lowest need of a hatched pet, or warmth for an egg
+    pub fn most_urgent_need(&self) -> (&'static str, u8) {
+        let pet = &self.pet;
+        if pet.stage == LifeStage::Egg {
+            return ("stat.warmth", pet.get_warmth());
+        }
+
+        let stats = &pet.stats;
+        [
+            ("stat.hunger", stats.hunger.value()),
+            ("stat.happiness", stats.happiness.value()),
+            ("stat.energy", stats.energy.value()),
+            ("stat.health", stats.health.value()),
+            ("stat.hygiene", stats.hygiene.value()),
+        ]
+        .into_iter()
+        .min_by_key(|(_, value)| *value)
+        .unwrap_or(("stat.health", stats.health.value()))
+    }
+}
+
+/// All the player's pets and which one actions go to
+#[derive(Debug)]
+pub struct Household {
+    /// Pets in the order they joined
+    pub pets: Vec<Companion>,
+    /// Index of the pet actions apply to
+    selected: usize,
+    /// Last time two pets interacted
+    last_interaction: Instant,
+}
+
+impl Household {
+    /// A household of one
+    pub fn new(pet: Pet) -> Self {
+        Self {
+            pets: vec![Companion::new(pet)],
+            selected: 0,
+            last_interaction: Instant::now(),
+        }
+    }
+
+    /// A household of the given pets, with one selected; `None` without pets
+    pub fn from_pets(pets: impl IntoIterator<Item = Pet>, selected: usize) -> Option<Self> {
+        let mut pets = pets.into_iter();
+        let mut household = Self::new(pets.next()?);
+        household.pets.extend(pets.map(Companion::new));
+        household.select(selected);
+        Some(household)
+    }
+
+    /// Number of pets
+    pub fn len(&self) -> usize {
+        self.pets.len()
+    }
+
+    /// Whether another pet fits
+    pub fn has_room(&self) -> bool {
+        self.pets.len() < MAX_PETS
+    }
+
+    /// Index of the selected pet
+    pub fn selected_index(&self) -> usize {
+        self.selected
+    }
+
+    /// The pet actions apply to
+    pub fn selected(&self) -> &Companion {
+        &self.pets[self.selected]
+    }
+
+    /// The pet actions apply to
+    pub fn selected_mut(&mut self) -> &mut Companion {
+        &mut self.pets[self.selected]
+    }
+
+    /// Select a pet by index, ignoring ones that don't exist
+    pub fn select(&mut self, index: usize) {
+        if index < self.pets.len() {
+            self.selected = index;
+        }
+    }
+
+    /// Add a pet and select it; returns false when the household is full
+    pub fn add(&mut self, pet: Pet) -> bool {
+        if !self.has_room() {
+            return false;
+        }
+        self.pets.push(Companion::new(pet));
+        self.selected = self.pets.len() - 1;
+        true
+    }
+
+    /// Have the selected pet lay an egg with the first other pet ready for
+    /// it, returning the egg and the partner's index
+    pub fn match_selected(&mut self, name: &str) -> Result<(Pet, usize), ActionError> {
+        let selected = self.selected;
+        self.pets[selected].pet.check_can_match()?;
+        let partner = (0..self.pets.len())
+            .find(|&index| index != selected && self.pets[index].pet.check_can_match().is_ok())
+            .ok_or(ActionError::NoPartner)?;
+
+        let (low, high) = self.pets.split_at_mut(selected.max(partner));
+        let (first, second) = if selected < partner {
+            (&mut low[selected], &mut high[0])
+        } else {
+            (&mut high[0], &mut low[partner])
+        };
+        let egg = first.pet.match_with(&mut second.pet, name)?;
+        Ok((egg, partner))
+    }
+
+    /// Take a pet out, keeping at least one
+    pub fn remove(&mut self, index: usize) -> Option<Companion> {
+        if self.pets.len() < 2 || index >= self.pets.len() {
+            return None;
+        }
+        let companion = self.pets.remove(index);
+        if self.selected > index || self.selected == self.pets.len() {
+            self.selected -= 1;
+        }
+        Some(companion)
+    }
+
+    /// Push every timer forward so a pause doesn't count as time passed
+    pub fn shift_clock(&mut self, paused: Duration) {
+        self.last_interaction += paused;
+        for companion in &mut self.pets {
+            companion.shift_clock(paused);
+        }
+    }
+
+    /// Maybe have two pets interact, returning what happened
+    pub fn interact(&mut self) -> Option<GameEvent> {
+        if self.pets.len() < 2
+            || self.last_interaction.elapsed() < INTERACTION_COOLDOWN
+            || random_float() >= INTERACTION_CHANCE
+        {
+            return None;
+        }
+
+        let (event_type, first, second) = self.select_interaction()?;
+        let names = tr!(
+            "household.pair",
+            first = self.pets[first].pet.name,
+            second = self.pets[second].pet.name
+        );
+
+        match event_type {
+            EventType::PlayedTogether => {
+                for index in [first, second] {
+                    let stats = &mut self.pets[index].pet.stats;
+                    stats.happiness.add(10);
+                    stats.energy.sub(5);
+                }
+            }
+            EventType::CaughtSickness => {
+                self.pets[second].pet.state = PetState::Sick {
+                    since: Instant::now(),
+                };
+            }
+            _ => return None,
+        }
+
+        // The event explains any state change; don't journal it twice
+        for companion in &mut self.pets {
+            companion.sync_observed();
+        }
+        self.last_interaction = Instant::now();
+
+        let event = GameEvent {
+            timestamp: Instant::now(),
+            message: event_type.message(&names),
+            event_type,
+        };
+        for index in [first, second] {
+            self.pets[index].event_system.remember(event.clone());
+        }
+        Some(event)
+    }
+
+    /// Weighted pick among the interactions the pets' states allow
+    fn select_interaction(&self) -> Option<(EventType, usize, usize)> {
+        let awake = |index: usize| {
+            let pet = &self.pets[index].pet;
+            pet.stage != LifeStage::Egg && matches!(pet.state, PetState::Normal)
+        };
+
+        let mut possible = Vec::new();
+        for first in 0..self.pets.len() {
+            for second in 0..self.pets.len() {
+                if first == second || !awake(second) {
+                    continue;
+                }
+                if first < second && awake(first) {
+                    possible.push((EventType::PlayedTogether, first, second, 1.0));
+                }
+                if self.pets[first].pet.state.is_sick() {
+                    let weight = 0.5 * self.pets[second].pet.difficulty.negative_event_weight();
+                    possible.push((EventType::CaughtSickness, first, second, weight));
+                }
+            }
+        }
+
+        let total: f32 = possible.iter().map(|(.., weight)| weight).sum();
+        let mut random = random_float() * total;
+        for (event_type, first, second, weight) in possible {
+            random -= weight;
+            if random <= 0.0 {
+                return Some((event_type, first, second));
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::Genome;
+    use crate::stats::StatValue;
+
+    fn hatched(name: &str) -> Pet {
+        let mut pet = Pet::new(name, Genome::default());
+        pet.stage = LifeStage::Child;
+        pet
+    }
+
+    #[test]
+    fn selection_follows_adds_and_removals() {
+        let mut household = Household::new(hatched("Pip"));
+        assert!(household.add(hatched("Mochi")));
+        assert_eq!(household.selected().pet.name, "Mochi");
+
+        household.select(0);
+        assert_eq!(household.selected().pet.name, "Pip");
+        household.select(5);
+        assert_eq!(household.selected().pet.name, "Pip");
+
+        household.select(1);
+        household.remove(0);
+        assert_eq!(household.selected().pet.name, "Mochi");
+        assert!(household.remove(0).is_none());
+
+        while household.has_room() {
+            household.add(hatched("Bean"));
+        }
+        assert!(!household.add(hatched("Tofu")));
+    }
+
+    #[test]
+    fn matching_finds_a_ready_partner() {
+        let mut household = Household::new(hatched("Pip"));
+        household.add(hatched("Mochi"));
+        household.add(hatched("Bean"));
+        for companion in &mut household.pets {
+            companion.pet.stage = LifeStage::Adult;
+            companion.pet.stats.happiness = StatValue::new(90);
+        }
+        household.pets[0].pet.stats.happiness = StatValue::new(10);
+
+        // Bean is selected; Pip is too unhappy so Mochi is picked
+        let (egg, partner) = household.match_selected("Tofu").unwrap();
+        assert_eq!(partner, 1);
+        assert_eq!(egg.name, "Tofu");
+
+        household.select(0);
+        assert!(matches!(
+            household.match_selected("Tofu"),
+            Err(ActionError::Unhappy { .. })
+        ));
+        household.pets[0].pet.stats.happiness = StatValue::new(90);
+        assert_eq!(
+            household.match_selected("Tofu").unwrap_err(),
+            ActionError::NoPartner
+        );
+    }
+
+    #[test]
+    fn only_healthy_awake_pets_catch_colds() {
+        let mut household = Household::new(hatched("Pip"));
+        household.add(hatched("Mochi"));
+        household.pets[0].pet.state = PetState::Sick {
+            since: Instant::now(),
+        };
+
+        // Only a sick pet and a healthy one: the cold is all that can happen
+        assert_eq!(
+            household.select_interaction(),
+            Some((EventType::CaughtSickness, 0, 1))
+        );
+
+        household.pets[1].pet.state = PetState::Sleeping {
+            since: Instant::now(),
+        };
+        assert_eq!(household.select_interaction(), None);
+    }
+}
@@ -0,0 +1,370 @@
+//! Optional HTTP API for dashboards
+//!
+//! `--http 127.0.0.1:PORT` serves the selected pet over plain HTTP/1.1:
+//!
+//! - `GET /pet`: the pet as `mypet-tui status --json` describes it
+//! - `POST /actions/{feed,play,clean,sleep,medicine,warm}`: act on it, with
+//!   `409 Conflict` when the pet refuses
+//! - `GET /events`: journal entries as Server-Sent Events
+//!
+//! Connections are handled in the background; whatever needs the game is
+//! passed to the [`Bridge`], which the game answers on its next tick.
+
+use std::io;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use serde_json::{Value, json};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{broadcast, mpsc, oneshot};
+
+use crate::app::App;
+use crate::commands::PetSummary;
+use crate::daemon;
+use crate::journal::JournalEntry;
+use crate::rpc::{ActionParams, RemoteAction};
+
+/// Journal entries kept for event streams that fall behind
+const EVENT_BUFFER: usize = 64;
+/// Longest request body read (and ignored)
+const MAX_BODY: u64 = 64 * 1024;
+/// Longest request line and headers, together
+const MAX_HEAD: u64 = 8 * 1024;
+/// Most headers a request may have
+const MAX_HEADERS: usize = 64;
+/// Time a client has to send its whole request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Something a connection needs the game for
+#[derive(Debug)]
+enum Call {
+    Pet(oneshot::Sender<PetSummary>),
+    Act(RemoteAction, oneshot::Sender<Outcome>),
+}
+
+/// What an action did (or why not), and the pet afterwards
+type Outcome = (Result<String, String>, PetSummary);
+
+/// The game's end of the server
+#[derive(Debug)]
+pub struct Bridge {
+    /// Where the server listens
+    pub addr: SocketAddr,
+    calls: mpsc::UnboundedReceiver<Call>,
+    events: broadcast::Sender<JournalEntry>,
+    /// Journal entries already streamed; set on the first tick
+    published: Option<usize>,
+}
+
+impl Bridge {
+    /// Answer every waiting request and stream new journal entries
+    pub fn answer(&mut self, app: &mut App) {
+        while let Ok(call) = self.calls.try_recv() {
+            // A client that hung up doesn't need its answer
+            match call {
+                Call::Pet(reply) => {
+                    let _ = reply.send(PetSummary::new(app.pet()));
+                }
+                Call::Act(action, reply) => {
+                    let outcome = daemon::perform(
+                        app,
+                        ActionParams {
+                            action,
+                            pet: None,
+                            setup: None,
+                        },
+                    );
+                    let _ = reply.send((outcome, PetSummary::new(app.pet())));
+                }
+            }
+        }
+
+        let published = *self.published.get_or_insert(app.journal.len());
+        for entry in app.journal.since(published) {
+            let _ = self.events.send(entry.clone());
+        }
+        self.published = Some(app.journal.len());
+    }
+}
+
+/// Start listening on `addr`, which must be a loopback address
+pub async fn start(addr: SocketAddr) -> io::Result<Bridge> {
+    if !addr.ip().is_loopback() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} is not a loopback address", addr.ip()),
+        ));
+    }
+    let listener = TcpListener::bind(addr).await?;
+    let addr = listener.local_addr()?;
+    let (calls, incoming) = mpsc::unbounded_channel();
+    let (events, _) = broadcast::channel(EVENT_BUFFER);
+
+    let streams = events.clone();
+    tokio::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((stream, _)) => {
+                    tokio::spawn(serve(stream, calls.clone(), streams.clone()));
+                }
+                Err(err) => tracing::warn!("could not accept an HTTP client: {}", err),
+            }
+        }
+    });
+
+    Ok(Bridge {
+        addr,
+        calls: incoming,
+        events,
+        published: None,
+    })
+}
+
+/// Answer one request, then close the connection
+async fn serve(
+    stream: TcpStream,
+    calls: mpsc::UnboundedSender<Call>,
+    events: broadcast::Sender<JournalEntry>,
+) {
+    let (reader, mut writer) = stream.into_split();
+    let mut reader = BufReader::new(reader);
+
+    // Slow or broken clients are dropped without an answer
+    let request_line = match tokio::time::timeout(REQUEST_TIMEOUT, read_request(&mut reader)).await
+    {
+        Ok(Ok(Some(request_line))) => request_line,
+        Ok(Ok(None)) => {
+            let body = json!({"ok": false, "error": "request too large"});
+            let _ = writer.write_all(response(431, &body).as_bytes()).await;
+            return;
+        }
+        Ok(Err(_)) | Err(_) => return,
+    };
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let path = parts.next().unwrap_or_default();
+    let path = path.split('?').next().unwrap_or_default();
+
+    // Once the game has stopped, calls are dropped along with their reply
+    let (status, body) = match route(method, path) {
+        Route::Pet => {
+            let (reply, answer) = oneshot::channel();
+            let _ = calls.send(Call::Pet(reply));
+            match answer.await {
+                Ok(pet) => (200, json!(pet)),
+                Err(_) => unavailable(),
+            }
+        }
+        Route::Act(action) => {
+            let (reply, answer) = oneshot::channel();
+            let _ = calls.send(Call::Act(action, reply));
+            match answer.await {
+                Ok((Ok(message), pet)) => {
+                    (200, json!({"ok": true, "message": message, "pet": pet}))
+                }
+                Ok((Err(error), pet)) => (409, json!({"ok": false, "error": error, "pet": pet})),
+                Err(_) => unavailable(),
+            }
+        }
+        Route::Events => {
+            stream_events(&mut writer, events.subscribe()).await;
+            return;
+        }
+        Route::WrongMethod => (405, json!({"ok": false, "error": "method not allowed"})),
+        Route::NotFound => (404, json!({"ok": false, "error": "not found"})),
+    };
+
+    let _ = writer.write_all(response(status, &body).as_bytes()).await;
+}
+
+/// Read a request to the end of its body, returning the request line;
+/// `None` when the line and headers are too big
+async fn read_request(reader: &mut (impl AsyncBufRead + Unpin)) -> io::Result<Option<String>> {
+    let mut head = (&mut *reader).take(MAX_HEAD);
+    let mut request_line = String::new();
+    let mut length = 0;
+    for count in 0..=MAX_HEADERS + 1 {
+        let mut line = String::new();
+        if head.read_line(&mut line).await? == 0 {
+            if head.limit() == 0 {
+                return Ok(None);
+            }
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        // Cut short by the limit
+        if !line.ends_with('\n') {
+            return Ok(None);
+        }
+        if count == 0 {
+            request_line = line;
+        } else if line.trim().is_empty() {
+            // Nothing takes a body, but it has to be read before answering
+            let mut body = Vec::new();
+            (&mut *reader)
+                .take(length.min(MAX_BODY))
+                .read_to_end(&mut body)
+                .await?;
+            return Ok(Some(request_line));
+        } else if let Some((name, value)) = line.split_once(':')
+            && name.trim().eq_ignore_ascii_case("content-length")
+        {
+            length = value.trim().parse().unwrap_or(0);
+        }
+    }
+    Ok(None)
+}
+
+/// What a request asks for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Route {
+    Pet,
+    Act(RemoteAction),
+    Events,
+    WrongMethod,
+    NotFound,
+}
+
+/// Match a request to what it asks for
+fn route(method: &str, path: &str) -> Route {
+    let wanted = match path {
+        "/pet" => ("GET", Route::Pet),
+        "/events" => ("GET", Route::Events),
+        _ => {
+            let action = match path.strip_prefix("/actions/") {
+                Some("feed") => RemoteAction::Feed,
+                Some("play") => RemoteAction::Play,
+                Some("clean") => RemoteAction::Clean,
+                Some("sleep") => RemoteAction::Sleep,
+                Some("medicine") => RemoteAction::Medicine,
+                Some("warm") => RemoteAction::Warm,
+                _ => return Route::NotFound,
+            };
+            ("POST", Route::Act(action))
+        }
+    };
+    if method == wanted.0 {
+        wanted.1
+    } else {
+        Route::WrongMethod
+    }
+}
+
+/// Answer for when the game has stopped
+fn unavailable() -> (u16, Value) {
+    (
+        503,
+        json!({"ok": false, "error": "the game is not running"}),
+    )
+}
+
+/// A complete response with a JSON body
+fn response(status: u16, body: &Value) -> String {
+    let reason = match status {
+        200 => "OK",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        431 => "Request Header Fields Too Large",
+        _ => "Service Unavailable",
+    };
+    let body = body.to_string();
+    format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        body
+    )
+}
+
+/// Send journal entries as they are written until the client goes away
+async fn stream_events(
+    writer: &mut (impl AsyncWriteExt + Unpin),
+    mut entries: broadcast::Receiver<JournalEntry>,
+) {
+    let head = "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: keep-alive\r\n\r\n";
+    if writer.write_all(head.as_bytes()).await.is_err() {
+        return;
+    }
+    loop {
+        let entry = match entries.recv().await {
+            Ok(entry) => entry,
+            Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(broadcast::error::RecvError::Closed) => return,
+        };
+        let Ok(data) = serde_json::to_string(&entry) else {
+            continue;
+        };
+        let event = format!("event: {}\ndata: {}\n\n", entry.category.label(), data);
+        if writer.write_all(event.as_bytes()).await.is_err() {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pet::LifeStage;
+    use std::net::Ipv4Addr;
+
+    #[test]
+    fn routes_requests() {
+        assert_eq!(route("GET", "/pet"), Route::Pet);
+        assert_eq!(
+            route("POST", "/actions/medicine"),
+            Route::Act(RemoteAction::Medicine)
+        );
+        assert_eq!(route("GET", "/actions/feed"), Route::WrongMethod);
+        assert_eq!(route("POST", "/actions/pause"), Route::NotFound);
+        assert_eq!(route("GET", "/events"), Route::Events);
+    }
+
+    /// Send a request while the game ticks, returning the response
+    async fn request(bridge: &mut Bridge, app: &mut App, request: &str) -> String {
+        let mut stream = TcpStream::connect(bridge.addr).await.unwrap();
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let reading = tokio::spawn(async move {
+            let mut response = String::new();
+            stream.read_to_string(&mut response).await.unwrap();
+            response
+        });
+        while !reading.is_finished() {
+            bridge.answer(app);
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        }
+        reading.await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn serves_the_pet_and_actions() {
+        let mut app = App::new();
+        app.pet_mut().stage = LifeStage::Child;
+        let mut bridge = start((Ipv4Addr::LOCALHOST, 0).into()).await.unwrap();
+
+        let pet = request(&mut bridge, &mut app, "GET /pet HTTP/1.1\r\n\r\n").await;
+        assert!(pet.starts_with("HTTP/1.1 200 OK"));
+        assert!(pet.contains(r#""stage":"child""#));
+
+        let fed = request(&mut bridge, &mut app, "POST /actions/feed HTTP/1.1\r\n\r\n").await;
+        assert!(fed.starts_with("HTTP/1.1 200 OK"));
+        let warmed = request(
+            &mut bridge,
+            &mut app,
+            "POST /actions/warm HTTP/1.1\r\nContent-Length: 2\r\n\r\n{}",
+        )
+        .await;
+        assert!(warmed.starts_with("HTTP/1.1 409 Conflict"));
+
+        let long = format!("GET /pet HTTP/1.1\r\nX-Pad: {}\r\n\r\n", "a".repeat(9000));
+        let refused = request(&mut bridge, &mut app, &long).await;
+        assert!(refused.starts_with("HTTP/1.1 431"));
+        let many = format!("GET /pet HTTP/1.1\r\n{}\r\n", "X-Pad: a\r\n".repeat(100));
+        let refused = request(&mut bridge, &mut app, &many).await;
+        assert!(refused.starts_with("HTTP/1.1 431"));
+
+        assert!(start((Ipv4Addr::UNSPECIFIED, 0).into()).await.is_err());
+    }
+}
@@ -0,0 +1,402 @@
+//! English messages, also the fallback for keys missing elsewhere
+
+pub const MESSAGES: &[(&str, &str)] = &[
+    // Random events
+    ("event.made_mess", "{name} made a mess! (-20 hygiene)"),
+    (
+        "event.found_treasure",
+        "{name} found a shiny object! (+10 happiness)",
+    ),
+    (
+        "event.bad_dream",
+        "{name} had a bad dream... (-10 happiness)",
+    ),
+    (
+        "event.learned_trick",
+        "{name} learned a new trick! (+15 happiness)",
+    ),
+    ("event.evolved", "{name} evolved! 🎉"),
+    (
+        "event.happy_moment",
+        "{name} is having a wonderful time! (+20 happiness)",
+    ),
+    ("event.lonely", "{name} seems lonely..."),
+    (
+        "event.asking_for_food",
+        "{name} is looking at you with hungry eyes...",
+    ),
+    ("event_name.made_mess", "Made a mess"),
+    ("event_name.found_treasure", "Found treasure"),
+    ("event_name.bad_dream", "Bad dream"),
+    ("event_name.learned_trick", "Learned a trick"),
+    ("event_name.evolved", "Evolved"),
+    ("event_name.happy_moment", "Happy moment"),
+    ("event_name.lonely", "Lonely"),
+    ("event_name.asking_for_food", "Asking for food"),
+    (
+        "event.played_together",
+        "{name} played together! (+10 happiness)",
+    ),
+    (
+        "event.caught_sickness",
+        "{name} are both sick now - colds spread!",
+    ),
+    ("event_name.played_together", "Played together"),
+    ("event_name.caught_sickness", "Caught a cold"),
+    // Life stages
+    ("stage.egg", "Egg"),
+    ("stage.baby", "Baby"),
+    ("stage.child", "Child"),
+    ("stage.teen", "Teen"),
+    ("stage.adult", "Adult"),
+    // Pet status line
+    ("status.dead", "{name} has passed away..."),
+    (
+        "status.egg_failed",
+        "The egg failed to hatch... It was too cold.",
+    ),
+    (
+        "status.egg_dying",
+        "⚠ CRITICAL: The egg is dying! Warm it NOW!",
+    ),
+    (
+        "status.egg_freezing",
+        "⚠ The egg is FREEZING! Warm it quickly or it will die!",
+    ),
+    (
+        "status.egg_cold",
+        "The egg feels cold... Try pressing [W] to warm it!",
+    ),
+    ("status.egg_warming", "The egg is getting warmer..."),
+    ("status.egg_cozy", "The egg is cozy and warm!"),
+    (
+        "status.egg_very_warm",
+        "The egg is very warm! The baby will be healthy!",
+    ),
+    (
+        "status.egg_ready",
+        "🎉 The egg is ready to hatch any moment!",
+    ),
+    ("status.paused", "Paused - press {keys} to resume"),
+    ("status.sleeping", "{name} is sleeping peacefully"),
+    ("status.sick", "{name} is not feeling well"),
+    ("status.starving", "{name} is very hungry!"),
+    ("status.depressed", "{name} seems sad..."),
+    ("status.exhausted", "{name} is exhausted"),
+    ("status.filthy", "{name} needs a bath"),
+    ("status.baby", "{name} is a cute baby!"),
+    ("status.well", "{name} is doing well!"),
+    // Refused actions
+    ("error.already_hatched", "The pet has already hatched!"),
+    ("error.warm_enough", "The egg is warm enough!"),
+    ("error.dead", "Pet is dead"),
+    ("error.already_sleeping", "Pet is already sleeping"),
+    ("error.not_sleeping", "Pet is not sleeping"),
+    ("error.not_sick", "Pet is not sick"),
+    ("error.pat_cooldown", "Pet is still enjoying the last pat"),
+    ("error.not_hatched", "That doesn't work on an egg!"),
+    ("error.is_egg", "Eggs don't sleep! Try warming it."),
+    ("error.too_tired", "Too tired to play (energy {energy})"),
+    ("error.asleep", "Pet is asleep right now"),
+    ("error.no_medicine", "There is no medicine on Hardcore"),
+    ("error.no_pause", "There is no pausing on Hardcore"),
+    (
+        "error.household_full",
+        "There is room for {max} pets at most",
+    ),
+    ("error.not_adult", "{name} is too young to have an egg"),
+    ("error.unhappy", "{name} needs {min}% happiness for a match"),
+    (
+        "error.match_cooldown",
+        "{name} needs to rest before another egg",
+    ),
+    ("error.no_partner", "No other adult is ready for a match"),
+    (
+        "command.no_save",
+        "No saved pet yet - start the game to hatch one",
+    ),
+    ("command.bad_save", "Couldn't read the saved game: {error}"),
+    (
+        "command.game_running",
+        "The game is open in another terminal - use it there, or run the daemon to share it",
+    ),
+    ("command.save_failed", "Couldn't save the game: {error}"),
+    (
+        "command.daemon_failed",
+        "Couldn't reach the daemon: {error}",
+    ),
+    ("daemon.listening", "Daemon listening on {path}"),
+    (
+        "daemon.already_running",
+        "a daemon is already running at {path}",
+    ),
+    ("daemon.no_such_pet", "There is no pet number {index}"),
+    (
+        "daemon.missing_setup",
+        "A new pet needs a name, species and difficulty",
+    ),
+    ("daemon.not_over", "The game isn't over"),
+    (
+        "daemon.disconnected",
+        "Lost the daemon - the game now runs here",
+    ),
+    ("http.listening", "HTTP API on http://{addr}"),
+    (
+        "http.use_daemon",
+        "the daemon is running - start it with --http instead",
+    ),
+    ("alert.critical", "{name}'s health is critical!"),
+    ("alert.egg_danger", "{name}'s egg is in danger!"),
+    ("attention.title", "MyPet – {message}"),
+    ("error.hint", "{error} - try {hint}"),
+    // Results of player actions
+    ("action.warmed", "You warmed the egg! Warmth: {warmth}%"),
+    ("action.fed", "You fed {name}!"),
+    ("action.played", "You played with {name}!"),
+    ("action.cleaned", "You cleaned {name}!"),
+    ("action.woke", "{name} woke up!"),
+    ("action.slept", "{name} went to sleep!"),
+    ("action.patted", "You patted {name}!"),
+    ("action.medicine", "You gave {name} medicine!"),
+    // Journal entries for things the pet did on its own
+    ("journal.hatched", "{name} hatched!"),
+    ("journal.evolved", "{name} evolved! 🎉 Now a {stage}."),
+    ("journal.recovered", "{name} recovered"),
+    ("journal.woke", "{name} woke up"),
+    ("journal.fell_asleep", "{name} fell asleep"),
+    ("journal.got_sick", "{name} got sick"),
+    ("journal.game_over", "The egg failed to hatch... Game Over!"),
+    (
+        "journal.new_game",
+        "{name} the {species} arrived as an egg ({difficulty})",
+    ),
+    ("journal.paused", "Game paused"),
+    ("journal.resumed", "Game resumed"),
+    (
+        "journal.vacation_started",
+        "A pet-sitter is looking after {name} for {hours} h",
+    ),
+    (
+        "journal.vacation_ended",
+        "You're back! {name} is happy to see you",
+    ),
+    (
+        "journal.vacation_over",
+        "The pet-sitter went home - {name} missed you",
+    ),
+    (
+        "journal.adopted",
+        "{name} the {species} joined the household as an egg ({difficulty})",
+    ),
+    ("journal.egg_lost", "{name}'s egg failed to hatch..."),
+    (
+        "journal.matched",
+        "{first} and {second} had an egg: {name} the {species}!",
+    ),
+    ("journal.daemon_started", "Daemon started"),
+    ("journal.daemon_stopped", "Daemon stopped"),
+    ("journal.session_started", "Session started"),
+    // Stat names
+    ("stat.hunger", "Hunger"),
+    ("stat.happiness", "Happiness"),
+    ("stat.energy", "Energy"),
+    ("stat.health", "Health"),
+    ("stat.hygiene", "Hygiene"),
+    ("stat.warmth", "Warmth"),
+    ("stat.incubation", "Incubation"),
+    ("stat.age", "Age: {age}"),
+    ("stat.dropped", "{stat} dropped to {value}"),
+    ("stat.egg_health", "Egg health"),
+    ("stat.low", "! {stat} - LOW"),
+    // Buttons in the actions bar
+    ("button.warm_egg", "Warm Egg"),
+    ("button.feed", "Feed"),
+    ("button.play", "Play"),
+    ("button.play_gentle", "Play (Gentle)"),
+    ("button.clean", "Clean"),
+    ("button.sleep", "Sleep"),
+    ("button.medicine", "Medicine"),
+    ("button.restart", "Restart"),
+    ("button.quit", "Quit"),
+    // Key binding descriptions in the help overlay
+    ("help.quit", "Quit the game"),
+    ("help.restart", "Restart after game over"),
+    ("help.warm", "Warm the egg"),
+    ("help.feed", "Feed your pet"),
+    ("help.play", "Play with your pet"),
+    ("help.clean", "Clean your pet"),
+    ("help.sleep", "Sleep / wake up"),
+    ("help.medicine", "Give medicine"),
+    (
+        "help.read_status",
+        "Read out all stats (screen reader mode)",
+    ),
+    ("help.pause", "Pause / resume"),
+    ("help.vacation", "Start / end pet-sitter mode"),
+    ("help.next_pet", "Switch to the next pet"),
+    ("help.adopt", "Adopt another pet"),
+    ("help.match", "Match two adult pets for an egg"),
+    ("help.back", "Go back"),
+    ("help.help", "Show / hide this help"),
+    ("help.show_main", "Pet view"),
+    ("help.show_stats", "Stats history"),
+    ("help.show_pet_info", "Pet info"),
+    ("help.show_event_log", "Event log"),
+    ("help.show_settings", "Settings"),
+    ("help.up", "Move up"),
+    ("help.down", "Move down"),
+    ("help.left", "Previous option / filter"),
+    ("help.right", "Next option / filter"),
+    ("help.select", "Select / toggle"),
+    ("help.search", "Search the event log"),
+    // Menu bar and panel titles
+    ("view.main", "Pet"),
+    ("view.help", "Help"),
+    ("view.stats_history", "Stats"),
+    ("view.settings", "Settings"),
+    ("view.pet_info", "Info"),
+    ("view.setup", "New game"),
+    ("view.event_log", "Events"),
+    ("panel.stats", " Stats "),
+    ("panel.event_log", " Event Log "),
+    ("panel.actions", " Actions "),
+    ("panel.no_events", "No events yet..."),
+    ("panel.no_matching_events", "No matching events..."),
+    (
+        "panel.no_history",
+        "No history yet... check back once your egg hatches.",
+    ),
+    ("panel.pet_info", " Pet Info "),
+    (
+        "panel.too_small",
+        "Terminal too small\n{width}x{height}, need at least {min_width}x{min_height}\n[{keys}] Quit",
+    ),
+    // Pet info view
+    ("info.name", "Name"),
+    ("info.species", "Species"),
+    ("info.coat", "Coat"),
+    ("info.traits", "Traits"),
+    ("info.stage", "Stage"),
+    ("info.state", "State"),
+    ("info.age", "Age"),
+    ("info.next", "Next"),
+    ("info.no_traits", "None"),
+    ("info.awake", "Awake"),
+    ("info.sleeping", "Sleeping ({seconds}s)"),
+    ("info.sick", "Sick ({seconds}s)"),
+    ("info.dead", "Passed away"),
+    ("info.next_stage", "{stage} in about {seconds}s"),
+    (
+        "info.next_hatch",
+        "{stage} once incubated ({percent}% so far)",
+    ),
+    ("info.fully_grown", "Fully grown"),
+    // Problems found in config files at startup
+    (
+        "notice.keymap_problems",
+        "Keymap has {count} problem(s) - see help [{keys}]",
+    ),
+    (
+        "notice.theme_problems",
+        "Theme files have {count} problem(s) - see settings [{keys}]",
+    ),
+    // Screen reader mode
+    ("report.egg", "{name} is an egg"),
+    ("report.pet", "{name} is a {stage}, {state}, age {age}"),
+    ("report.awake", "awake"),
+    ("report.asleep", "asleep"),
+    ("report.sick", "sick"),
+    ("report.dead", "dead"),
+    ("report.game_over", "Game over. Press {keys} to restart"),
+    ("linear.intro", "MyPet, screen reader mode. {status}"),
+    (
+        "linear.hint",
+        "Type {help} for commands or {status} to read the status",
+    ),
+    ("linear.command", "{keys} or {name}: {description}"),
+    (
+        "linear.unknown",
+        "Unknown command '{command}'. Type {help} for a list of commands",
+    ),
+    (
+        "linear.unavailable",
+        "'{command}' is not available in screen reader mode",
+    ),
+    ("linear.goodbye", "Goodbye"),
+    // Settings view
+    ("setting.language", "Language"),
+    ("setting.theme", "Theme"),
+    ("setting.high_contrast", "High contrast"),
+    ("setting.glyphs", "Characters"),
+    ("setting.animations", "Animations"),
+    ("setting.event_log_panel", "Event log panel"),
+    ("setting.vacation_length", "Pet-sitter time"),
+    ("setting.hours", "{hours} h"),
+    ("setting.on", "On"),
+    ("setting.off", "Off"),
+    // Pet-sitter mode
+    ("sitter.warmed", "The pet-sitter warmed the egg"),
+    ("sitter.fed", "The pet-sitter fed {name}"),
+    ("sitter.cleaned", "The pet-sitter cleaned {name}"),
+    ("sitter.medicine", "The pet-sitter gave {name} medicine"),
+    ("sitter.put_to_bed", "The pet-sitter put {name} to bed"),
+    ("sitter.woke", "The pet-sitter woke {name} up"),
+    ("sitter.patted", "The pet-sitter patted {name}"),
+    ("header.vacation", "{difficulty} - pet-sitter {time}"),
+    // Households with several pets
+    ("household.pair", "{first} and {second}"),
+    ("household.selected", "Now looking after {name}"),
+    ("panel.household", " Household "),
+    // New-game wizard
+    ("setup.title", " New Game "),
+    ("setup.step", "Step {number} of {total}: {title}"),
+    ("setup.step_name", "Name your pet"),
+    ("setup.step_species", "Choose an egg"),
+    ("setup.step_difficulty", "Choose a difficulty"),
+    (
+        "setup.name_hint",
+        "Enter to continue, Tab for a random name",
+    ),
+    (
+        "setup.choice_hint",
+        "Arrows to choose, Enter to continue, Esc to go back",
+    ),
+    ("setup.name_empty", "Your pet needs a name"),
+    (
+        "setup.name_too_long",
+        "Names can be at most {max} characters",
+    ),
+    ("setup.name_invalid", "Names can't contain '{char}'"),
+    (
+        "setup.name_prompt",
+        "Type a name, or press Enter for a random one",
+    ),
+    ("setup.choice_prompt", "{title}: {choices}"),
+    ("setup.unknown_choice", "There is no choice '{choice}'"),
+    ("species.cat", "Cat"),
+    ("species.bunny", "Bunny"),
+    ("species.bear", "Bear"),
+    ("difficulty.easy", "Easy"),
+    ("difficulty.normal", "Normal"),
+    ("difficulty.hard", "Hard"),
+    ("difficulty.hardcore", "Hardcore"),
+    (
+        "difficulty.easy_description",
+        "Needs fall slowly and bad events are rare",
+    ),
+    ("difficulty.normal_description", "The game as intended"),
+    (
+        "difficulty.hard_description",
+        "Needs fall fast and bad events are common",
+    ),
+    (
+        "difficulty.hardcore_description",
+        "Like Hard, with no medicine and no pausing",
+    ),
+    // Coats
+    ("coat.plain", "Plain"),
+    ("coat.ginger", "Ginger"),
+    ("coat.smoke", "Smoke"),
+    ("coat.midnight", "Midnight"),
+    ("coat.snow", "Snow"),
+];
@@ -0,0 +1,453 @@
+//! Spanish messages
+
+pub const MESSAGES: &[(&str, &str)] = &[
+    // Random events
+    (
+        "event.made_mess",
+        "¡{name} ha hecho un desastre! (-20 higiene)",
+    ),
+    (
+        "event.found_treasure",
+        "¡{name} ha encontrado algo brillante! (+10 felicidad)",
+    ),
+    (
+        "event.bad_dream",
+        "{name} ha tenido una pesadilla... (-10 felicidad)",
+    ),
+    (
+        "event.learned_trick",
+        "¡{name} ha aprendido un truco nuevo! (+15 felicidad)",
+    ),
+    ("event.evolved", "¡{name} ha evolucionado! 🎉"),
+    (
+        "event.happy_moment",
+        "¡{name} se lo está pasando en grande! (+20 felicidad)",
+    ),
+    ("event.lonely", "{name} parece sentirse solo..."),
+    (
+        "event.asking_for_food",
+        "{name} te mira con ojos hambrientos...",
+    ),
+    ("event_name.made_mess", "Desastre"),
+    ("event_name.found_treasure", "Tesoro encontrado"),
+    ("event_name.bad_dream", "Pesadilla"),
+    ("event_name.learned_trick", "Truco aprendido"),
+    ("event_name.evolved", "Evolución"),
+    ("event_name.happy_moment", "Momento feliz"),
+    ("event_name.lonely", "Soledad"),
+    ("event_name.asking_for_food", "Pide comida"),
+    (
+        "event.played_together",
+        "¡{name} han jugado juntos! (+10 felicidad)",
+    ),
+    (
+        "event.caught_sickness",
+        "{name} están enfermos - ¡los resfriados se contagian!",
+    ),
+    ("event_name.played_together", "Juegan juntos"),
+    ("event_name.caught_sickness", "Resfriado"),
+    // Life stages
+    ("stage.egg", "Huevo"),
+    ("stage.baby", "Bebé"),
+    ("stage.child", "Niño"),
+    ("stage.teen", "Adolescente"),
+    ("stage.adult", "Adulto"),
+    // Pet status line
+    ("status.dead", "{name} ha fallecido..."),
+    (
+        "status.egg_failed",
+        "El huevo no llegó a eclosionar... Hacía demasiado frío.",
+    ),
+    (
+        "status.egg_dying",
+        "⚠ CRÍTICO: ¡El huevo se muere! ¡Caliéntalo YA!",
+    ),
+    (
+        "status.egg_freezing",
+        "⚠ ¡El huevo se está CONGELANDO! ¡Caliéntalo rápido o morirá!",
+    ),
+    (
+        "status.egg_cold",
+        "El huevo está frío... ¡Pulsa [W] para calentarlo!",
+    ),
+    ("status.egg_warming", "El huevo se está calentando..."),
+    ("status.egg_cozy", "¡El huevo está calentito!"),
+    (
+        "status.egg_very_warm",
+        "¡El huevo está muy caliente! ¡El bebé nacerá sano!",
+    ),
+    (
+        "status.egg_ready",
+        "🎉 ¡El huevo está a punto de eclosionar!",
+    ),
+    ("status.paused", "En pausa - pulsa {keys} para seguir"),
+    ("status.sleeping", "{name} duerme tranquilamente"),
+    ("status.sick", "{name} no se encuentra bien"),
+    ("status.starving", "¡{name} tiene mucha hambre!"),
+    ("status.depressed", "{name} parece triste..."),
+    ("status.exhausted", "{name} está agotado"),
+    ("status.filthy", "{name} necesita un baño"),
+    ("status.baby", "¡{name} es un bebé adorable!"),
+    ("status.well", "¡{name} está estupendamente!"),
+    // Refused actions
+    ("error.already_hatched", "¡La mascota ya ha eclosionado!"),
+    ("error.warm_enough", "¡El huevo ya está bastante caliente!"),
+    ("error.dead", "La mascota ha muerto"),
+    ("error.already_sleeping", "La mascota ya está durmiendo"),
+    ("error.not_sleeping", "La mascota no está durmiendo"),
+    ("error.not_sick", "La mascota no está enferma"),
+    (
+        "error.pat_cooldown",
+        "La mascota todavía disfruta de la última caricia",
+    ),
+    ("error.not_hatched", "¡Eso no funciona con un huevo!"),
+    (
+        "error.is_egg",
+        "¡Los huevos no duermen! Prueba a darle calor.",
+    ),
+    (
+        "error.too_tired",
+        "Demasiado cansado para jugar (energía {energy})",
+    ),
+    ("error.asleep", "La mascota está durmiendo"),
+    ("error.no_medicine", "No hay medicina en modo Extremo"),
+    ("error.no_pause", "No se puede pausar en modo Extremo"),
+    (
+        "error.household_full",
+        "Solo caben {max} mascotas como mucho",
+    ),
+    (
+        "error.not_adult",
+        "{name} es demasiado joven para tener un huevo",
+    ),
+    (
+        "error.unhappy",
+        "{name} necesita {min}% de felicidad para emparejarse",
+    ),
+    (
+        "error.match_cooldown",
+        "{name} necesita descansar antes de otro huevo",
+    ),
+    (
+        "error.no_partner",
+        "Ningún otro adulto está listo para emparejarse",
+    ),
+    (
+        "command.no_save",
+        "Aún no hay mascota guardada: inicia el juego para incubar una",
+    ),
+    (
+        "command.bad_save",
+        "No se pudo leer la partida guardada: {error}",
+    ),
+    (
+        "command.game_running",
+        "La partida está abierta en otra terminal: úsala allí o inicia el daemon para compartirla",
+    ),
+    (
+        "command.save_failed",
+        "No se pudo guardar la partida: {error}",
+    ),
+    (
+        "command.daemon_failed",
+        "No se pudo contactar con el daemon: {error}",
+    ),
+    ("daemon.listening", "Daemon escuchando en {path}"),
+    (
+        "daemon.already_running",
+        "ya hay un daemon en marcha en {path}",
+    ),
+    ("daemon.no_such_pet", "No hay mascota número {index}"),
+    (
+        "daemon.missing_setup",
+        "Una mascota nueva necesita nombre, especie y dificultad",
+    ),
+    ("daemon.not_over", "La partida no ha terminado"),
+    (
+        "daemon.disconnected",
+        "Se perdió el daemon - la partida sigue aquí",
+    ),
+    ("http.listening", "API HTTP en http://{addr}"),
+    (
+        "http.use_daemon",
+        "el daemon está en marcha - arráncalo con --http",
+    ),
+    ("alert.critical", "¡La salud de {name} es crítica!"),
+    ("alert.egg_danger", "¡El huevo de {name} está en peligro!"),
+    ("attention.title", "MyPet – {message}"),
+    ("error.hint", "{error} - prueba {hint}"),
+    // Results of player actions
+    ("action.warmed", "¡Has calentado el huevo! Calor: {warmth}%"),
+    ("action.fed", "¡Has dado de comer a {name}!"),
+    ("action.played", "¡Has jugado con {name}!"),
+    ("action.cleaned", "¡Has limpiado a {name}!"),
+    ("action.woke", "¡{name} se ha despertado!"),
+    ("action.slept", "¡{name} se ha ido a dormir!"),
+    ("action.patted", "¡Has acariciado a {name}!"),
+    ("action.medicine", "¡Has dado una medicina a {name}!"),
+    // Journal entries for things the pet did on its own
+    ("journal.hatched", "¡{name} ha eclosionado!"),
+    (
+        "journal.evolved",
+        "¡{name} ha evolucionado! 🎉 Ahora es {stage}.",
+    ),
+    ("journal.recovered", "{name} se ha recuperado"),
+    ("journal.woke", "{name} se ha despertado"),
+    ("journal.fell_asleep", "{name} se ha dormido"),
+    ("journal.got_sick", "{name} se ha puesto enfermo"),
+    (
+        "journal.game_over",
+        "El huevo no llegó a eclosionar... ¡Fin de la partida!",
+    ),
+    (
+        "journal.new_game",
+        "{name} ({species}) llega como un huevo ({difficulty})",
+    ),
+    ("journal.paused", "Partida en pausa"),
+    ("journal.resumed", "Partida reanudada"),
+    (
+        "journal.vacation_started",
+        "Un cuidador se ocupa de {name} durante {hours} h",
+    ),
+    (
+        "journal.vacation_ended",
+        "¡Has vuelto! {name} se alegra de verte",
+    ),
+    (
+        "journal.vacation_over",
+        "El cuidador se ha ido a casa - {name} te ha echado de menos",
+    ),
+    (
+        "journal.adopted",
+        "{name} ({species}) llega a la familia como un huevo ({difficulty})",
+    ),
+    (
+        "journal.egg_lost",
+        "El huevo de {name} no llegó a eclosionar...",
+    ),
+    (
+        "journal.matched",
+        "¡{first} y {second} tuvieron un huevo: {name} ({species})!",
+    ),
+    ("journal.daemon_started", "Daemon iniciado"),
+    ("journal.daemon_stopped", "Daemon detenido"),
+    ("journal.session_started", "Sesión iniciada"),
+    // Stat names
+    ("stat.hunger", "Hambre"),
+    ("stat.happiness", "Felicidad"),
+    ("stat.energy", "Energía"),
+    ("stat.health", "Salud"),
+    ("stat.hygiene", "Higiene"),
+    ("stat.warmth", "Calor"),
+    ("stat.incubation", "Incubación"),
+    ("stat.age", "Edad: {age}"),
+    ("stat.dropped", "{stat} ha bajado a {value}"),
+    ("stat.egg_health", "Salud del huevo"),
+    ("stat.low", "! {stat} - BAJO"),
+    // Buttons in the actions bar
+    ("button.warm_egg", "Calentar huevo"),
+    ("button.feed", "Alimentar"),
+    ("button.play", "Jugar"),
+    ("button.play_gentle", "Jugar (con cuidado)"),
+    ("button.clean", "Limpiar"),
+    ("button.sleep", "Dormir"),
+    ("button.medicine", "Medicina"),
+    ("button.restart", "Reiniciar"),
+    ("button.quit", "Salir"),
+    // Key binding descriptions in the help overlay
+    ("help.quit", "Salir del juego"),
+    ("help.restart", "Reiniciar tras el fin de la partida"),
+    ("help.warm", "Calentar el huevo"),
+    ("help.feed", "Alimentar a tu mascota"),
+    ("help.play", "Jugar con tu mascota"),
+    ("help.clean", "Limpiar a tu mascota"),
+    ("help.sleep", "Dormir / despertar"),
+    ("help.medicine", "Dar medicina"),
+    (
+        "help.read_status",
+        "Leer todas las estadísticas (modo lector de pantalla)",
+    ),
+    ("help.pause", "Pausar / reanudar"),
+    ("help.vacation", "Activar / desactivar el cuidador"),
+    ("help.next_pet", "Cambiar a la siguiente mascota"),
+    ("help.adopt", "Adoptar otra mascota"),
+    (
+        "help.match",
+        "Emparejar dos mascotas adultas para tener un huevo",
+    ),
+    ("help.back", "Volver"),
+    ("help.help", "Mostrar / ocultar esta ayuda"),
+    ("help.show_main", "Vista de la mascota"),
+    ("help.show_stats", "Historial de estadísticas"),
+    ("help.show_pet_info", "Información de la mascota"),
+    ("help.show_event_log", "Registro de eventos"),
+    ("help.show_settings", "Ajustes"),
+    ("help.up", "Subir"),
+    ("help.down", "Bajar"),
+    ("help.left", "Opción / filtro anterior"),
+    ("help.right", "Opción / filtro siguiente"),
+    ("help.select", "Seleccionar / alternar"),
+    ("help.search", "Buscar en el registro"),
+    // Menu bar and panel titles
+    ("view.main", "Mascota"),
+    ("view.help", "Ayuda"),
+    ("view.stats_history", "Estadísticas"),
+    ("view.settings", "Ajustes"),
+    ("view.pet_info", "Info"),
+    ("view.setup", "Nueva partida"),
+    ("view.event_log", "Eventos"),
+    ("panel.stats", " Estadísticas "),
+    ("panel.event_log", " Registro de eventos "),
+    ("panel.actions", " Acciones "),
+    ("panel.no_events", "Todavía no hay eventos..."),
+    ("panel.no_matching_events", "Ningún evento coincide..."),
+    (
+        "panel.no_history",
+        "Todavía no hay historial... vuelve cuando el huevo eclosione.",
+    ),
+    ("panel.pet_info", " Información "),
+    (
+        "panel.too_small",
+        "Terminal demasiado pequeña\n{width}x{height}, se necesita al menos {min_width}x{min_height}\n[{keys}] Salir",
+    ),
+    // Vista de información
+    ("info.name", "Nombre"),
+    ("info.species", "Especie"),
+    ("info.coat", "Pelaje"),
+    ("info.traits", "Rasgos"),
+    ("info.stage", "Etapa"),
+    ("info.state", "Estado"),
+    ("info.age", "Edad"),
+    ("info.next", "Siguiente"),
+    ("info.no_traits", "Ninguno"),
+    ("info.awake", "Despierto"),
+    ("info.sleeping", "Durmiendo ({seconds} s)"),
+    ("info.sick", "Enfermo ({seconds} s)"),
+    ("info.dead", "Falleció"),
+    ("info.next_stage", "{stage} en unos {seconds} s"),
+    (
+        "info.next_hatch",
+        "{stage} al terminar la incubación ({percent}% por ahora)",
+    ),
+    ("info.fully_grown", "Ya es adulto"),
+    // Problemas en los archivos de configuración
+    (
+        "notice.keymap_problems",
+        "El mapa de teclas tiene {count} problema(s) - ver ayuda [{keys}]",
+    ),
+    (
+        "notice.theme_problems",
+        "Los temas tienen {count} problema(s) - ver ajustes [{keys}]",
+    ),
+    // Screen reader mode
+    ("report.egg", "{name} es un huevo"),
+    ("report.pet", "{name} es {stage}, {state}, edad {age}"),
+    ("report.awake", "despierto"),
+    ("report.asleep", "dormido"),
+    ("report.sick", "enfermo"),
+    ("report.dead", "muerto"),
+    (
+        "report.game_over",
+        "Fin de la partida. Pulsa {keys} para reiniciar",
+    ),
+    ("linear.intro", "MyPet, modo lector de pantalla. {status}"),
+    (
+        "linear.hint",
+        "Escribe {help} para ver los comandos o {status} para leer el estado",
+    ),
+    ("linear.command", "{keys} o {name}: {description}"),
+    (
+        "linear.unknown",
+        "Comando desconocido '{command}'. Escribe {help} para ver la lista de comandos",
+    ),
+    (
+        "linear.unavailable",
+        "'{command}' no está disponible en el modo lector de pantalla",
+    ),
+    ("linear.goodbye", "Adiós"),
+    // Settings view
+    ("setting.language", "Idioma"),
+    ("setting.theme", "Tema"),
+    ("setting.high_contrast", "Alto contraste"),
+    ("setting.glyphs", "Caracteres"),
+    ("setting.animations", "Animaciones"),
+    ("setting.event_log_panel", "Panel de eventos"),
+    ("setting.vacation_length", "Tiempo del cuidador"),
+    ("setting.hours", "{hours} h"),
+    ("setting.on", "Sí"),
+    ("setting.off", "No"),
+    // Pet-sitter mode
+    ("sitter.warmed", "El cuidador ha calentado el huevo"),
+    ("sitter.fed", "El cuidador ha dado de comer a {name}"),
+    ("sitter.cleaned", "El cuidador ha limpiado a {name}"),
+    (
+        "sitter.medicine",
+        "El cuidador ha dado una medicina a {name}",
+    ),
+    ("sitter.put_to_bed", "El cuidador ha acostado a {name}"),
+    ("sitter.woke", "El cuidador ha despertado a {name}"),
+    ("sitter.patted", "El cuidador ha acariciado a {name}"),
+    ("header.vacation", "{difficulty} - cuidador {time}"),
+    // Households with several pets
+    ("household.pair", "{first} y {second}"),
+    ("household.selected", "Ahora cuidas de {name}"),
+    ("panel.household", " Familia "),
+    // New-game wizard
+    ("setup.title", " Nueva partida "),
+    ("setup.step", "Paso {number} de {total}: {title}"),
+    ("setup.step_name", "Ponle nombre a tu mascota"),
+    ("setup.step_species", "Elige un huevo"),
+    ("setup.step_difficulty", "Elige la dificultad"),
+    (
+        "setup.name_hint",
+        "Intro para continuar, Tab para un nombre al azar",
+    ),
+    (
+        "setup.choice_hint",
+        "Flechas para elegir, Intro para continuar, Esc para volver",
+    ),
+    ("setup.name_empty", "Tu mascota necesita un nombre"),
+    (
+        "setup.name_too_long",
+        "Los nombres pueden tener como mucho {max} caracteres",
+    ),
+    (
+        "setup.name_invalid",
+        "Los nombres no pueden contener '{char}'",
+    ),
+    (
+        "setup.name_prompt",
+        "Escribe un nombre o pulsa Intro para uno al azar",
+    ),
+    ("setup.choice_prompt", "{title}: {choices}"),
+    ("setup.unknown_choice", "No existe la opción '{choice}'"),
+    ("species.cat", "Gato"),
+    ("species.bunny", "Conejo"),
+    ("species.bear", "Oso"),
+    ("difficulty.easy", "Fácil"),
+    ("difficulty.normal", "Normal"),
+    ("difficulty.hard", "Difícil"),
+    ("difficulty.hardcore", "Extremo"),
+    (
+        "difficulty.easy_description",
+        "Las necesidades bajan despacio y los eventos malos son raros",
+    ),
+    (
+        "difficulty.normal_description",
+        "El juego tal y como se pensó",
+    ),
+    (
+        "difficulty.hard_description",
+        "Las necesidades bajan rápido y los eventos malos son frecuentes",
+    ),
+    (
+        "difficulty.hardcore_description",
+        "Como Difícil, sin medicina y sin pausa",
+    ),
+    // Coats
+    ("coat.plain", "Liso"),
+    ("coat.ginger", "Canela"),
+    ("coat.smoke", "Humo"),
+    ("coat.midnight", "Medianoche"),
+    ("coat.snow", "Nieve"),
+];
@@ -0,0 +1,189 @@
+//! Translated user-facing text
+//!
+//! Every message lives in a per-locale catalogue under a key such as
+//! `event.made_mess`. Values may contain `{name}`-style placeholders, which
+//! [`tr!`] fills in:
+//!
+//! ```ignore
+//! tr!("action.fed", name = pet.name)
+//! ```
+//!
+//! The locale is taken from `LC_ALL`, `LC_MESSAGES` or `LANG` at startup and
+//! can be changed from the settings view. Keys missing from a locale fall
+//! back to English.
+
+mod en;
+mod es;
+
+use std::env;
+use std::fmt::Display;
+use std::sync::RwLock;
+
+/// The active locale
+static CURRENT: RwLock<Locale> = RwLock::new(Locale::English);
+
+/// A language the game is translated into
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    English,
+    Spanish,
+}
+
+impl Locale {
+    /// All locales, in the order the settings view cycles through them
+    pub const ALL: [Locale; 2] = [Locale::English, Locale::Spanish];
+
+    /// Pick the locale named by a `LANG`-style value such as `es_ES.UTF-8`
+    pub fn from_lang(lang: &str) -> Option<Self> {
+        let code = lang.split(['_', '.', '@', '-']).next()?.to_lowercase();
+        match code.as_str() {
+            "en" => Some(Locale::English),
+            "es" => Some(Locale::Spanish),
+            _ => None,
+        }
+    }
+
+    /// The locale asked for by the environment, English if none matches
+    pub fn detect() -> Self {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .find_map(|var| env::var(var).ok().filter(|value| !value.is_empty()))
+            .and_then(|lang| Self::from_lang(&lang))
+            .unwrap_or(Locale::English)
+    }
+
+    /// Language code, as read back by [`Locale::from_lang`]
+    pub fn code(self) -> &'static str {
+        match self {
+            Locale::English => "en",
+            Locale::Spanish => "es",
+        }
+    }
+
+    /// Name of the language in that language
+    pub fn label(self) -> &'static str {
+        match self {
+            Locale::English => "English",
+            Locale::Spanish => "Español",
+        }
+    }
+
+    /// The locale after this one, wrapping around
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|l| *l == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    fn messages(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Locale::English => en::MESSAGES,
+            Locale::Spanish => es::MESSAGES,
+        }
+    }
+
+    /// Message for a key in this locale only
+    fn get(self, key: &str) -> Option<&'static str> {
+        self.messages()
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, text)| *text)
+    }
+}
+
+/// The active locale
+pub fn locale() -> Locale {
+    *CURRENT.read().unwrap_or_else(|err| err.into_inner())
+}
+
+/// Switch the active locale
+pub fn set_locale(locale: Locale) {
+    *CURRENT.write().unwrap_or_else(|err| err.into_inner()) = locale;
+}
+
+/// Message for a key without placeholders filled in
+///
+/// Falls back to English, then to the key itself so a missing entry is
+/// visible instead of blank.
+pub fn lookup(key: &'static str) -> &'static str {
+    locale()
+        .get(key)
+        .or_else(|| Locale::English.get(key))
+        .unwrap_or(key)
+}
+
+/// Message for a key with `{placeholder}`s replaced by `args`
+pub fn translate(key: &'static str, args: &[(&str, &dyn Display)]) -> String {
+    let mut text = lookup(key).to_string();
+    for (name, value) in args {
+        text = text.replace(&format!("{{{}}}", name), &value.to_string());
+    }
+    text
+}
+
+/// Translate a message key, filling in named placeholders
+///
+/// `tr!("action.fed", name = pet.name)`
+macro_rules! tr {
+    ($key:expr $(, $name:ident = $value:expr)* $(,)?) => {
+        $crate::i18n::translate(
+            $key,
+            &[$((stringify!($name), &$value as &dyn ::std::fmt::Display)),*],
+        )
+    };
+}
+pub(crate) use tr;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    /// `{placeholder}` names used in a message
+    fn placeholders(text: &str) -> BTreeSet<&str> {
+        text.split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+            .collect()
+    }
+
+    #[test]
+    fn every_key_exists_in_every_locale() {
+        for locale in Locale::ALL {
+            for (key, english) in en::MESSAGES {
+                let text = locale
+                    .get(key)
+                    .unwrap_or_else(|| panic!("{:?} is missing '{}'", locale, key));
+                assert_eq!(
+                    placeholders(text),
+                    placeholders(english),
+                    "{:?} '{}' has different placeholders",
+                    locale,
+                    key
+                );
+            }
+            let mut seen = BTreeSet::new();
+            for (key, _) in locale.messages() {
+                assert!(
+                    Locale::English.get(key).is_some(),
+                    "{:?} has unknown key '{}'",
+                    locale,
+                    key
+                );
+                assert!(seen.insert(key), "{:?} repeats key '{}'", locale, key);
+            }
+        }
+    }
+
+    #[test]
+    fn reads_locale_from_lang() {
+        assert_eq!(Locale::from_lang("es_ES.UTF-8"), Some(Locale::Spanish));
+        assert_eq!(Locale::from_lang("en"), Some(Locale::English));
+        assert_eq!(Locale::from_lang("C"), None);
+        assert_eq!(
+            Locale::English
+                .get("action.fed")
+                .map(|text| text.replace("{name}", "Rex")),
+            Some("You fed Rex!".to_string())
+        );
+    }
+}
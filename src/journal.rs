@@ -0,0 +1,337 @@
+//! Persistent journal of everything that happens to the pet
+//!
+//! Entries are appended to `journal.jsonl` (one JSON object per line) in the
+//! data directory. When the file grows past [`MAX_FILE_BYTES`] it is rotated
+//! to `journal.1.jsonl`, `journal.2.jsonl`, ... keeping [`MAX_ROTATIONS`] old
+//! files. All files are read back on startup, oldest first.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::events::{EventType, GameEvent};
+
+/// Size at which the journal file is rotated
+const MAX_FILE_BYTES: u64 = 256 * 1024;
+/// Number of rotated files kept next to the current one
+const MAX_ROTATIONS: usize = 3;
+
+/// Broad kind of a journal entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JournalCategory {
+    /// A random event from the event system
+    Event,
+    /// Something the player did
+    Action,
+    /// The pet changed stage or state on its own
+    State,
+    /// Game-level happenings (session start, restart, game over)
+    System,
+}
+
+impl JournalCategory {
+    /// Short tag shown in the journal view
+    pub fn label(self) -> &'static str {
+        match self {
+            JournalCategory::Event => "event",
+            JournalCategory::Action => "action",
+            JournalCategory::State => "state",
+            JournalCategory::System => "system",
+        }
+    }
+}
+
+/// A single line of the journal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub timestamp: DateTime<Utc>,
+    pub category: JournalCategory,
+    /// Which random event this was, for [`JournalCategory::Event`] entries
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_type: Option<EventType>,
+    pub message: String,
+}
+
+impl JournalEntry {
+    /// Timestamp formatted in the local time zone
+    pub fn local_time(&self) -> String {
+        self.timestamp
+            .with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string()
+    }
+}
+
+/// Which entries the journal view shows
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JournalFilter {
+    All,
+    Category(JournalCategory),
+    Event(EventType),
+}
+
+impl JournalFilter {
+    /// Filters in the order they are cycled through
+    pub fn all() -> Vec<JournalFilter> {
+        let mut filters = vec![
+            JournalFilter::All,
+            JournalFilter::Category(JournalCategory::Event),
+            JournalFilter::Category(JournalCategory::Action),
+            JournalFilter::Category(JournalCategory::State),
+            JournalFilter::Category(JournalCategory::System),
+        ];
+        filters.extend(EventType::ALL.iter().cloned().map(JournalFilter::Event));
+        filters
+    }
+
+    /// Name shown in the journal view title
+    pub fn label(&self) -> String {
+        match self {
+            JournalFilter::All => "all".to_string(),
+            JournalFilter::Category(category) => category.label().to_string(),
+            JournalFilter::Event(event_type) => format!("event: {}", event_type.display_name()),
+        }
+    }
+
+    /// Whether an entry passes the filter
+    pub fn matches(&self, entry: &JournalEntry) -> bool {
+        match self {
+            JournalFilter::All => true,
+            JournalFilter::Category(category) => entry.category == *category,
+            JournalFilter::Event(event_type) => entry.event_type.as_ref() == Some(event_type),
+        }
+    }
+}
+
+/// The journal: every entry of this and previous sessions
+#[derive(Debug, Default)]
+pub struct Journal {
+    entries: Vec<JournalEntry>,
+    /// File entries are appended to; `None` keeps the journal in memory
+    path: Option<PathBuf>,
+}
+
+impl Journal {
+    /// A journal that is never written to disk
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    /// Open the journal at `path`, loading existing and rotated entries
+    pub fn open(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let mut entries = Vec::new();
+
+        for index in (0..=MAX_ROTATIONS).rev() {
+            let file = rotated_path(&path, index);
+            if let Ok(file) = File::open(&file) {
+                entries.extend(
+                    BufReader::new(file)
+                        .lines()
+                        .map_while(Result::ok)
+                        .filter_map(|line| serde_json::from_str(&line).ok()),
+                );
+            }
+        }
+
+        Self {
+            entries,
+            path: Some(path),
+        }
+    }
+
+    /// Add an entry, appending it to the journal file
+    pub fn record(
+        &mut self,
+        category: JournalCategory,
+        event_type: Option<EventType>,
+        message: impl Into<String>,
+    ) {
+        let entry = JournalEntry {
+            timestamp: Utc::now(),
+            category,
+            event_type,
+            message: message.into(),
+        };
+
+        if let Some(path) = &self.path
+            && let Err(err) = append(path, &entry)
+        {
+            tracing::warn!("could not write journal: {}", err);
+        }
+
+        self.entries.push(entry);
+    }
+
+    /// Add an entry written somewhere else, without appending it again
+    pub fn push(&mut self, entry: JournalEntry) {
+        self.entries.push(entry);
+    }
+
+    /// Record a random event
+    pub fn record_event(&mut self, event: &GameEvent) {
+        self.record(
+            JournalCategory::Event,
+            Some(event.event_type.clone()),
+            event.message.clone(),
+        );
+    }
+
+    /// Number of entries
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if nothing has been recorded
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Entries newest first, matching a filter and a case-insensitive search
+    pub fn query<'a>(
+        &'a self,
+        filter: &'a JournalFilter,
+        search: &str,
+    ) -> impl Iterator<Item = &'a JournalEntry> + 'a {
+        let search = search.to_lowercase();
+        self.entries
+            .iter()
+            .rev()
+            .filter(move |entry| filter.matches(entry))
+            .filter(move |entry| {
+                search.is_empty() || entry.message.to_lowercase().contains(&search)
+            })
+    }
+
+    /// Entries after the first `count`, oldest first
+    pub fn since(&self, count: usize) -> &[JournalEntry] {
+        self.entries.get(count..).unwrap_or_default()
+    }
+
+    /// The most recent entries, newest first
+    pub fn recent(&self, count: usize) -> impl Iterator<Item = &JournalEntry> {
+        self.entries.iter().rev().take(count)
+    }
+}
+
+/// `journal.jsonl` for index 0, `journal.N.jsonl` for rotated files
+fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    if index == 0 {
+        return path.to_path_buf();
+    }
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("journal");
+    let ext = path.extension().and_then(|s| s.to_str()).unwrap_or("jsonl");
+    path.with_file_name(format!("{}.{}.{}", stem, index, ext))
+}
+
+/// Shift `journal.jsonl` -> `journal.1.jsonl` -> ... dropping the oldest
+fn rotate(path: &Path) -> io::Result<()> {
+    for index in (1..=MAX_ROTATIONS).rev() {
+        let from = rotated_path(path, index - 1);
+        if from.exists() {
+            fs::rename(&from, rotated_path(path, index))?;
+        }
+    }
+    Ok(())
+}
+
+fn append(path: &Path, entry: &JournalEntry) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    if fs::metadata(path).is_ok_and(|meta| meta.len() >= MAX_FILE_BYTES) {
+        rotate(path)?;
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_journal_path(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("mypet-journal-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir.join("journal.jsonl")
+    }
+
+    #[test]
+    fn entries_survive_reopening() {
+        let path = temp_journal_path("reopen");
+
+        let mut journal = Journal::open(&path);
+        journal.record(JournalCategory::Action, None, "You fed Fluffy!");
+        journal.record(
+            JournalCategory::Event,
+            Some(EventType::MadeMess),
+            "Fluffy made a mess!",
+        );
+
+        let reopened = Journal::open(&path);
+        assert_eq!(reopened.len(), 2);
+        assert_eq!(
+            reopened.recent(1).next().unwrap().event_type,
+            Some(EventType::MadeMess)
+        );
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn rotation_keeps_old_entries_readable() {
+        let path = temp_journal_path("rotate");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+
+        // Pretend the current file is full
+        let filler = JournalEntry {
+            timestamp: Utc::now(),
+            category: JournalCategory::System,
+            event_type: None,
+            message: "x".repeat(MAX_FILE_BYTES as usize),
+        };
+        fs::write(
+            &path,
+            format!("{}\n", serde_json::to_string(&filler).unwrap()),
+        )
+        .unwrap();
+
+        let mut journal = Journal::open(&path);
+        journal.record(JournalCategory::System, None, "after rotation");
+
+        assert!(rotated_path(&path, 1).exists());
+        assert_eq!(Journal::open(&path).len(), 2);
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn query_filters_and_searches() {
+        let mut journal = Journal::in_memory();
+        journal.record(JournalCategory::Action, None, "You fed Fluffy!");
+        journal.record(
+            JournalCategory::Event,
+            Some(EventType::Lonely),
+            "Fluffy seems lonely...",
+        );
+        journal.record(JournalCategory::Action, None, "You played with Fluffy!");
+
+        let actions = JournalFilter::Category(JournalCategory::Action);
+        assert_eq!(journal.query(&actions, "").count(), 2);
+        assert_eq!(journal.query(&actions, "PLAYED").count(), 1);
+
+        let lonely = JournalFilter::Event(EventType::Lonely);
+        assert_eq!(journal.query(&lonely, "").count(), 1);
+        assert_eq!(journal.query(&JournalFilter::All, "fluffy").count(), 3);
+    }
+}
@@ -0,0 +1,571 @@
+//! Player actions and the key bindings that trigger them
+//!
+//! Bindings come from `keymap.json` in the config directory, which maps
+//! action names to one or more keys:
+//!
+//! ```json
+//! { "feed": ["f", "ctrl+f"], "quit": "q" }
+//! ```
+//!
+//! Actions missing from the file keep their default keys.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Serialize};
+
+use crate::i18n;
+use crate::paths;
+
+/// File name of the keymap inside the config directory
+const KEYMAP_FILE: &str = "keymap.json";
+
+/// Everything the player can ask the game to do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    /// Quit the application
+    Quit,
+    /// Start over after game over
+    Restart,
+    /// Warm the egg
+    Warm,
+    /// Feed the pet
+    Feed,
+    /// Play with the pet
+    Play,
+    /// Clean the pet
+    Clean,
+    /// Put the pet to sleep or wake it up
+    Sleep,
+    /// Give the pet medicine
+    Medicine,
+    /// Read out every stat at once (screen reader mode)
+    ReadStatus,
+    /// Stop or restart time
+    Pause,
+    /// Start or end pet-sitter mode
+    Vacation,
+    /// Switch actions to the next pet
+    NextPet,
+    /// Add another pet to the household
+    Adopt,
+    /// Have two adult pets lay an egg
+    Match,
+    /// Go back to the previous view
+    Back,
+    /// Toggle the help overlay
+    Help,
+    /// Open the main pet view
+    ShowMain,
+    /// Open the stats history view
+    ShowStats,
+    /// Open the pet info view
+    ShowPetInfo,
+    /// Open the event log view
+    ShowEventLog,
+    /// Open the settings view
+    ShowSettings,
+    /// Move the selection/scroll up
+    Up,
+    /// Move the selection/scroll down
+    Down,
+    /// Move the selection left / previous option
+    Left,
+    /// Move the selection right / next option
+    Right,
+    /// Activate the selected item
+    Select,
+    /// Start typing a search
+    Search,
+}
+
+impl Action {
+    /// All actions, in the order shown in the help overlay
+    pub const ALL: [Action; 27] = [
+        Action::Warm,
+        Action::Feed,
+        Action::Play,
+        Action::Clean,
+        Action::Sleep,
+        Action::Medicine,
+        Action::ReadStatus,
+        Action::Pause,
+        Action::Vacation,
+        Action::NextPet,
+        Action::Adopt,
+        Action::Match,
+        Action::Restart,
+        Action::ShowMain,
+        Action::ShowStats,
+        Action::ShowPetInfo,
+        Action::ShowEventLog,
+        Action::ShowSettings,
+        Action::Up,
+        Action::Down,
+        Action::Left,
+        Action::Right,
+        Action::Select,
+        Action::Search,
+        Action::Help,
+        Action::Back,
+        Action::Quit,
+    ];
+
+    /// Short description shown in the help overlay
+    pub fn description(self) -> &'static str {
+        i18n::lookup(match self {
+            Action::Quit => "help.quit",
+            Action::Restart => "help.restart",
+            Action::Warm => "help.warm",
+            Action::Feed => "help.feed",
+            Action::Play => "help.play",
+            Action::Clean => "help.clean",
+            Action::Sleep => "help.sleep",
+            Action::Medicine => "help.medicine",
+            Action::ReadStatus => "help.read_status",
+            Action::Pause => "help.pause",
+            Action::Vacation => "help.vacation",
+            Action::NextPet => "help.next_pet",
+            Action::Adopt => "help.adopt",
+            Action::Match => "help.match",
+            Action::Back => "help.back",
+            Action::Help => "help.help",
+            Action::ShowMain => "help.show_main",
+            Action::ShowStats => "help.show_stats",
+            Action::ShowPetInfo => "help.show_pet_info",
+            Action::ShowEventLog => "help.show_event_log",
+            Action::ShowSettings => "help.show_settings",
+            Action::Up => "help.up",
+            Action::Down => "help.down",
+            Action::Left => "help.left",
+            Action::Right => "help.right",
+            Action::Select => "help.select",
+            Action::Search => "help.search",
+        })
+    }
+
+    /// Keys bound to the action when the keymap file doesn't say otherwise
+    fn default_keys(self) -> &'static [&'static str] {
+        match self {
+            Action::Quit => &["q", "ctrl+c"],
+            Action::Restart => &["r"],
+            Action::Warm => &["w"],
+            Action::Feed => &["f"],
+            Action::Play => &["p"],
+            Action::Clean => &["c"],
+            Action::Sleep => &["s"],
+            Action::Medicine => &["m"],
+            Action::ReadStatus => &["t"],
+            Action::Pause => &["space"],
+            Action::Vacation => &["v"],
+            Action::NextPet => &["tab"],
+            Action::Adopt => &["a"],
+            Action::Match => &["b"],
+            Action::Back => &["esc"],
+            Action::Help => &["?"],
+            Action::ShowMain => &["1"],
+            Action::ShowStats => &["2"],
+            Action::ShowPetInfo => &["3"],
+            Action::ShowEventLog => &["4"],
+            Action::ShowSettings => &["5"],
+            Action::Up => &["up", "k"],
+            Action::Down => &["down", "j"],
+            Action::Left => &["left", "h"],
+            Action::Right => &["right", "l"],
+            Action::Select => &["enter"],
+            Action::Search => &["/"],
+        }
+    }
+}
+
+/// A key together with the modifiers held down
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    /// Create a chord; Shift is folded into character keys
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let mut modifiers =
+            modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT);
+        let code = match code {
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::Char(c.to_ascii_uppercase())
+            }
+            KeyCode::BackTab => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::BackTab
+            }
+            other => other,
+        };
+        Self { code, modifiers }
+    }
+
+    /// Chord for a key press
+    pub fn from_event(event: &KeyEvent) -> Self {
+        Self::new(event.code, event.modifiers)
+    }
+
+    /// Parse a key description such as `f`, `ctrl+f`, `shift+tab` or `f5`
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let text = text.trim();
+        // A lone "+" is a key, not a separator
+        let (mods, key) = match text.rsplit_once('+') {
+            Some((mods, "")) => (mods.strip_suffix('+').unwrap_or(mods), "+"),
+            Some((mods, key)) => (mods, key),
+            None => ("", text),
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        for part in mods.split('+').filter(|p| !p.is_empty()) {
+            modifiers |= match part.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" | "meta" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                other => return Err(format!("unknown modifier '{}' in '{}'", other, text)),
+            };
+        }
+
+        let code = match key.to_ascii_lowercase().as_str() {
+            "esc" | "escape" => KeyCode::Esc,
+            "enter" | "return" => KeyCode::Enter,
+            "space" => KeyCode::Char(' '),
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "backspace" => KeyCode::Backspace,
+            "delete" | "del" => KeyCode::Delete,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            lower => {
+                let mut chars = key.chars();
+                match (chars.next(), chars.next()) {
+                    // Terminals report Ctrl and Alt chords with the lowercase
+                    // letter unless Shift is held, so "ctrl+F" means ctrl+f
+                    (Some(c), None)
+                        if modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+                            && !modifiers.contains(KeyModifiers::SHIFT) =>
+                    {
+                        KeyCode::Char(c.to_ascii_lowercase())
+                    }
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => match lower.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                        Some(n) if (1..=12).contains(&n) => KeyCode::F(n),
+                        _ => return Err(format!("unknown key '{}'", text)),
+                    },
+                }
+            }
+        };
+
+        Ok(Self::new(code, modifiers))
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{}", c.to_ascii_uppercase()),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            KeyCode::BackTab => write!(f, "Shift+Tab"),
+            other => write!(f, "{:?}", other),
+        }
+    }
+}
+
+/// A single key bound to an action
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyBinding {
+    pub chord: KeyChord,
+    pub action: Action,
+}
+
+/// One key or a list of keys in the keymap file
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum KeyList {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// The active set of key bindings
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: Vec<KeyBinding>,
+    /// Problems found while loading (bad keys, conflicts)
+    pub warnings: Vec<String>,
+}
+
+impl Keymap {
+    /// The built-in bindings
+    pub fn new() -> Self {
+        Self::build(&HashMap::new(), Vec::new())
+    }
+
+    /// Load the keymap file from the config directory, writing the
+    /// defaults there first if it doesn't exist yet
+    pub fn load() -> Self {
+        let path = paths::config_dir().join(KEYMAP_FILE);
+
+        if !path.exists() {
+            let keymap = Self::new();
+            if let Err(err) = keymap.write_defaults(&path) {
+                tracing::warn!("could not write default keymap: {}", err);
+            }
+            return keymap;
+        }
+
+        match fs::read_to_string(&path) {
+            Ok(json) => Self::from_json(&json),
+            Err(err) => {
+                let mut keymap = Self::new();
+                keymap
+                    .warnings
+                    .push(format!("could not read {}: {}", path.display(), err));
+                keymap
+            }
+        }
+    }
+
+    /// Build a keymap from the contents of a keymap file
+    pub fn from_json(json: &str) -> Self {
+        let mut warnings = Vec::new();
+        let mut overrides = HashMap::new();
+
+        match serde_json::from_str::<HashMap<String, KeyList>>(json) {
+            Ok(entries) => {
+                for (name, keys) in entries {
+                    let action = match serde_json::from_value::<Action>(name.clone().into()) {
+                        Ok(action) => action,
+                        Err(_) => {
+                            warnings.push(format!("unknown action '{}'", name));
+                            continue;
+                        }
+                    };
+                    let keys = match keys {
+                        KeyList::One(key) => vec![key],
+                        KeyList::Many(keys) => keys,
+                    };
+                    overrides.insert(action, keys);
+                }
+            }
+            Err(err) => warnings.push(format!("invalid keymap file, using defaults: {}", err)),
+        }
+
+        Self::build(&overrides, warnings)
+    }
+
+    fn build(overrides: &HashMap<Action, Vec<String>>, mut warnings: Vec<String>) -> Self {
+        let mut bindings: Vec<KeyBinding> = Vec::new();
+
+        for action in Action::ALL {
+            let keys: Vec<&str> = match overrides.get(&action) {
+                Some(keys) => keys.iter().map(String::as_str).collect(),
+                None => action.default_keys().to_vec(),
+            };
+
+            for key in keys {
+                let chord = match KeyChord::parse(key) {
+                    Ok(chord) => chord,
+                    Err(err) => {
+                        warnings.push(format!("{:?}: {}", action, err));
+                        continue;
+                    }
+                };
+
+                // First binding wins; later ones are reported and dropped
+                if let Some(existing) = bindings.iter().find(|b| b.chord == chord) {
+                    if existing.action != action {
+                        warnings.push(format!(
+                            "key {} is bound to both {:?} and {:?}; using {:?}",
+                            chord, existing.action, action, existing.action
+                        ));
+                    }
+                    continue;
+                }
+
+                bindings.push(KeyBinding { chord, action });
+            }
+        }
+
+        for action in Action::ALL {
+            if !bindings.iter().any(|b| b.action == action) {
+                warnings.push(format!("{:?} has no key bound", action));
+            }
+        }
+
+        Self { bindings, warnings }
+    }
+
+    fn write_defaults(&self, path: &Path) -> std::io::Result<()> {
+        let entries: serde_json::Map<String, serde_json::Value> = Action::ALL
+            .iter()
+            .map(|action| {
+                let name = serde_json::to_value(action)
+                    .ok()
+                    .and_then(|v| v.as_str().map(str::to_string))
+                    .unwrap_or_default();
+                let keys = action
+                    .default_keys()
+                    .iter()
+                    .map(|k| serde_json::Value::from(*k))
+                    .collect();
+                (name, serde_json::Value::Array(keys))
+            })
+            .collect();
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(&entries)?)
+    }
+
+    /// Look up the action bound to a key press
+    pub fn action_for(&self, event: &KeyEvent) -> Option<Action> {
+        self.action_for_chord(KeyChord::from_event(event))
+    }
+
+    /// Look up the action bound to a chord
+    pub fn action_for_chord(&self, chord: KeyChord) -> Option<Action> {
+        self.bindings
+            .iter()
+            .find(|binding| binding.chord == chord)
+            .map(|binding| binding.action)
+    }
+
+    /// Keys bound to an action, joined for display (e.g. `Q / Ctrl+C`)
+    pub fn keys_label(&self, action: Action) -> String {
+        self.bindings
+            .iter()
+            .filter(|b| b.action == action)
+            .map(|b| b.chord.to_string())
+            .collect::<Vec<_>>()
+            .join(" / ")
+    }
+
+    /// Footer hint for an action, e.g. `[F]eed` or `[Ctrl+F] Feed`
+    pub fn hint(&self, action: Action, label: &str) -> String {
+        let Some(binding) = self.bindings.iter().find(|b| b.action == action) else {
+            return String::new();
+        };
+
+        let mut label_chars = label.chars();
+        if let (KeyCode::Char(key), true) = (binding.chord.code, binding.chord.modifiers.is_empty())
+            && let Some(first) = label_chars.next()
+            && first.eq_ignore_ascii_case(&key)
+        {
+            return format!("[{}]{}", first.to_ascii_uppercase(), label_chars.as_str());
+        }
+
+        format!("[{}] {}", binding.chord, label)
+    }
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn parses_modifiers_and_named_keys() {
+        let chord = KeyChord::parse("ctrl+alt+x").unwrap();
+        assert_eq!(chord.code, KeyCode::Char('x'));
+        assert_eq!(chord.modifiers, KeyModifiers::CONTROL | KeyModifiers::ALT);
+
+        assert_eq!(KeyChord::parse("F5").unwrap().code, KeyCode::F(5));
+        assert_eq!(KeyChord::parse("ctrl++").unwrap().code, KeyCode::Char('+'));
+        assert!(KeyChord::parse("hyper+x").is_err());
+    }
+
+    #[test]
+    fn shift_is_folded_into_characters() {
+        assert_eq!(
+            KeyChord::parse("shift+a").unwrap(),
+            KeyChord::new(KeyCode::Char('A'), KeyModifiers::NONE)
+        );
+    }
+
+    #[test]
+    fn modifier_chords_ignore_letter_case() {
+        assert_eq!(
+            KeyChord::parse("ctrl+F").unwrap(),
+            KeyChord::parse("ctrl+f").unwrap()
+        );
+        assert_eq!(
+            KeyChord::parse("ctrl+shift+f").unwrap(),
+            KeyChord::new(KeyCode::Char('F'), KeyModifiers::CONTROL)
+        );
+        assert_eq!(KeyChord::parse("F").unwrap().code, KeyCode::Char('F'));
+    }
+
+    #[test]
+    fn file_overrides_replace_defaults() {
+        let keymap = Keymap::from_json(r#"{ "feed": ["e", "ctrl+f"] }"#);
+
+        let feed = press(KeyCode::Char('f'), KeyModifiers::CONTROL);
+        assert_eq!(keymap.action_for(&feed), Some(Action::Feed));
+        assert_eq!(
+            keymap.action_for(&press(KeyCode::Char('e'), KeyModifiers::NONE)),
+            Some(Action::Feed)
+        );
+        assert_eq!(
+            keymap.action_for(&press(KeyCode::Char('f'), KeyModifiers::NONE)),
+            None
+        );
+        assert!(keymap.warnings.is_empty());
+    }
+
+    #[test]
+    fn conflicts_are_reported() {
+        let keymap = Keymap::from_json(r#"{ "play": "f", "bogus": "x" }"#);
+
+        assert_eq!(
+            keymap.action_for(&press(KeyCode::Char('f'), KeyModifiers::NONE)),
+            Some(Action::Feed)
+        );
+        assert!(
+            keymap
+                .warnings
+                .iter()
+                .any(|w| w.contains("both Feed and Play"))
+        );
+        assert!(keymap.warnings.iter().any(|w| w.contains("bogus")));
+    }
+
+    #[test]
+    fn hint_underlines_first_letter_when_possible() {
+        let keymap = Keymap::from_json(r#"{ "play": "ctrl+p" }"#);
+        assert_eq!(keymap.hint(Action::Feed, "Feed"), "[F]eed");
+        assert_eq!(keymap.hint(Action::Play, "Play"), "[Ctrl+P] Play");
+    }
+}
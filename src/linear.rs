@@ -0,0 +1,376 @@
+//! Screen-reader friendly linear frontend
+//!
+//! Instead of redrawing a screen, this frontend writes what happens as plain
+//! sentences, one per line, so screen readers and braille displays read them
+//! in order. Commands are read from stdin a line at a time: either a key
+//! from the keymap (`f`) or an action name (`feed`, `read status`).
+
+use std::io::{self, Write};
+use std::time::Duration;
+
+use tokio::io::{AsyncBufReadExt, BufReader};
+
+use crate::app::{App, GameState};
+use crate::difficulty::Difficulty;
+use crate::i18n::{self, tr};
+use crate::keymap::{Action, KeyChord};
+use crate::pet::{LifeStage, Species};
+use crate::save;
+use crate::setup::SetupStep;
+use crate::views;
+
+/// How often the game is advanced
+const TICK_RATE: Duration = Duration::from_millis(250);
+
+/// Levels that are announced when a stat falls below them
+const THRESHOLDS: [u8; 3] = [50, 30, 10];
+
+/// Actions that mean something without a screen
+const COMMANDS: [Action; 15] = [
+    Action::Warm,
+    Action::Feed,
+    Action::Play,
+    Action::Clean,
+    Action::Sleep,
+    Action::Medicine,
+    Action::ReadStatus,
+    Action::Pause,
+    Action::Vacation,
+    Action::NextPet,
+    Action::Adopt,
+    Action::Match,
+    Action::Restart,
+    Action::Help,
+    Action::Quit,
+];
+
+/// Turns changes in the game into sentences
+#[derive(Debug)]
+pub struct Narrator {
+    /// Journal entries already announced
+    seen: usize,
+    /// Stat values at the last observation
+    levels: Vec<(&'static str, u8)>,
+}
+
+impl Narrator {
+    /// Start narrating from the current state, without replaying history
+    pub fn new(app: &App) -> Self {
+        Self {
+            seen: app.journal.len(),
+            levels: levels(app),
+        }
+    }
+
+    /// Sentences for everything that happened since the last call
+    pub fn observe(&mut self, app: &App) -> Vec<String> {
+        let mut lines: Vec<String> = app
+            .journal
+            .since(self.seen)
+            .iter()
+            .map(|entry| entry.message.clone())
+            .collect();
+        self.seen = app.journal.len();
+
+        let levels = levels(app);
+        for &(label, value) in &levels {
+            let Some(&(_, before)) = self.levels.iter().find(|(l, _)| *l == label) else {
+                continue;
+            };
+            if THRESHOLDS.iter().any(|&t| before >= t && value < t) {
+                lines.push(tr!("stat.dropped", stat = label, value = value));
+            }
+        }
+        self.levels = levels;
+
+        lines
+    }
+}
+
+/// The stats worth announcing for the pet's stage
+fn levels(app: &App) -> Vec<(&'static str, u8)> {
+    let pet = app.pet();
+    if pet.stage == LifeStage::Egg {
+        return vec![
+            (i18n::lookup("stat.warmth"), pet.get_warmth()),
+            (i18n::lookup("stat.health"), pet.get_egg_health()),
+        ];
+    }
+
+    let stats = &pet.stats;
+    vec![
+        (i18n::lookup("stat.hunger"), stats.hunger.value()),
+        (i18n::lookup("stat.happiness"), stats.happiness.value()),
+        (i18n::lookup("stat.energy"), stats.energy.value()),
+        (i18n::lookup("stat.health"), stats.health.value()),
+        (i18n::lookup("stat.hygiene"), stats.hygiene.value()),
+    ]
+}
+
+/// Find the action for a typed line: an action name or a key
+pub fn parse_command(app: &App, line: &str) -> Option<Action> {
+    let line = line.trim();
+    let name = line.to_lowercase().replace(' ', "_");
+    if let Ok(action) = serde_json::from_value::<Action>(name.into()) {
+        return Some(action);
+    }
+
+    KeyChord::parse(line)
+        .ok()
+        .and_then(|chord| app.keymap.action_for_chord(chord))
+}
+
+/// Run a typed command, returning what to say about it
+pub fn handle_command(app: &mut App, line: &str) -> Vec<String> {
+    if app.game_state == GameState::Setup {
+        return handle_setup_line(app, line);
+    }
+    if line.trim().is_empty() {
+        return Vec::new();
+    }
+
+    let Some(action) = parse_command(app, line) else {
+        return vec![tr!(
+            "linear.unknown",
+            command = line.trim(),
+            help = app.keymap.keys_label(Action::Help)
+        )];
+    };
+
+    match action {
+        Action::Quit => {
+            app.quit();
+            vec![tr!("linear.goodbye")]
+        }
+        Action::Help => COMMANDS
+            .iter()
+            .map(|action| {
+                tr!(
+                    "linear.command",
+                    keys = app.keymap.keys_label(*action),
+                    name = command_name(*action),
+                    description = action.description()
+                )
+            })
+            .collect(),
+        Action::ReadStatus => app.status_report(),
+        action if COMMANDS.contains(&action) => {
+            let before = (app.journal.len(), app.requests_sent());
+            views::handle_action(app, action);
+            // Successful actions are journaled and announced by the narrator;
+            // anything else left its reason in the status line, except for
+            // actions sent to the daemon, which are announced when it answers
+            if app.game_state == GameState::Setup {
+                setup_prompt(app)
+            } else if (app.journal.len(), app.requests_sent()) == before {
+                vec![app.status_message.clone()]
+            } else {
+                Vec::new()
+            }
+        }
+        _ => vec![tr!("linear.unavailable", command = line.trim())],
+    }
+}
+
+/// Answer the current step of the new-game wizard
+///
+/// An empty line picks a random name or keeps the current choice.
+fn handle_setup_line(app: &mut App, line: &str) -> Vec<String> {
+    let line = line.trim();
+    let setup = &mut app.setup;
+    let picked = match setup.step {
+        SetupStep::Name if line.is_empty() => {
+            setup.randomize_name();
+            true
+        }
+        SetupStep::Name => {
+            setup.name = line.to_string();
+            true
+        }
+        _ if line.is_empty() => true,
+        SetupStep::Species => pick(&Species::ALL, Species::label, line)
+            .map(|species| setup.species = species)
+            .is_some(),
+        SetupStep::Difficulty => pick(&Difficulty::ALL, Difficulty::label, line)
+            .map(|difficulty| setup.difficulty = difficulty)
+            .is_some(),
+    };
+    if !picked {
+        return vec![tr!("setup.unknown_choice", choice = line)];
+    }
+
+    match app.setup.confirm() {
+        Ok(true) => {
+            // The narrator announces the new egg from the journal
+            app.start_game();
+            Vec::new()
+        }
+        Ok(false) => setup_prompt(app),
+        Err(err) => vec![err.to_string()],
+    }
+}
+
+/// What the current wizard step asks for
+pub fn setup_prompt(app: &App) -> Vec<String> {
+    let setup = &app.setup;
+    let title = setup.step.title();
+    let step = tr!(
+        "setup.step",
+        number = setup.step.number(),
+        total = SetupStep::ALL.len(),
+        title = title
+    );
+    let prompt = match setup.step {
+        SetupStep::Name => tr!("setup.name_prompt"),
+        SetupStep::Species => tr!(
+            "setup.choice_prompt",
+            title = title,
+            choices = numbered(&Species::ALL, Species::label)
+        ),
+        SetupStep::Difficulty => tr!(
+            "setup.choice_prompt",
+            title = title,
+            choices = numbered(&Difficulty::ALL, Difficulty::label)
+        ),
+    };
+    vec![step, prompt]
+}
+
+/// Options as `1 Cat, 2 Bunny, ...`
+fn numbered<T: Copy>(all: &[T], label: fn(T) -> &'static str) -> String {
+    all.iter()
+        .enumerate()
+        .map(|(i, item)| format!("{} {}", i + 1, label(*item)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The option typed by number or by name
+fn pick<T: Copy>(all: &[T], label: fn(T) -> &'static str, line: &str) -> Option<T> {
+    if let Ok(number) = line.parse::<usize>() {
+        return number.checked_sub(1).and_then(|i| all.get(i)).copied();
+    }
+    all.iter()
+        .copied()
+        .find(|item| label(*item).eq_ignore_ascii_case(line))
+}
+
+/// Name of an action as it can be typed
+fn command_name(action: Action) -> String {
+    serde_json::to_value(action)
+        .ok()
+        .and_then(|value| value.as_str().map(|name| name.replace('_', " ")))
+        .unwrap_or_default()
+}
+
+/// Print lines and flush them so they are read out right away
+fn say(out: &mut impl Write, lines: &[String]) -> io::Result<()> {
+    for line in lines {
+        writeln!(out, "{}", line)?;
+    }
+    out.flush()
+}
+
+/// Run the game until the player quits or stdin closes
+pub async fn run(app: &mut App) -> io::Result<()> {
+    let mut out = io::stdout();
+    let mut input = BufReader::new(tokio::io::stdin()).lines();
+    let mut ticker = tokio::time::interval(TICK_RATE);
+    let mut autosave = tokio::time::interval_at(
+        tokio::time::Instant::now() + save::AUTOSAVE_INTERVAL,
+        save::AUTOSAVE_INTERVAL,
+    );
+    let mut narrator = Narrator::new(app);
+
+    let mut intro = if app.game_state == GameState::Setup {
+        let mut lines = vec![tr!("linear.intro", status = i18n::lookup("view.setup"))];
+        lines.extend(setup_prompt(app));
+        lines
+    } else {
+        vec![tr!("linear.intro", status = app.pet().status_message())]
+    };
+    intro.insert(
+        1,
+        tr!(
+            "linear.hint",
+            help = app.keymap.keys_label(Action::Help),
+            status = app.keymap.keys_label(Action::ReadStatus)
+        ),
+    );
+    say(&mut out, &intro)?;
+
+    while !app.should_quit {
+        let mut lines = tokio::select! {
+            _ = ticker.tick() => {
+                app.tick();
+                app.refused.take().into_iter().collect()
+            }
+            _ = autosave.tick() => match app.save_if_owned() {
+                Ok(()) => Vec::new(),
+                Err(err) => vec![tr!("command.save_failed", error = err)],
+            },
+            line = input.next_line() => match line? {
+                Some(line) => handle_command(app, &line),
+                None => break,
+            },
+        };
+        lines.extend(narrator.observe(app));
+        say(&mut out, &lines)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commands_are_keys_or_action_names() {
+        let app = App::new();
+
+        assert_eq!(parse_command(&app, "w"), Some(Action::Warm));
+        assert_eq!(parse_command(&app, "Feed"), Some(Action::Feed));
+        assert_eq!(parse_command(&app, "read status"), Some(Action::ReadStatus));
+        assert_eq!(parse_command(&app, "dance"), None);
+    }
+
+    #[test]
+    fn narrator_announces_actions_and_drops() {
+        let mut app = App::new();
+        let mut narrator = Narrator::new(&app);
+
+        let said = handle_command(&mut app, "w");
+        assert!(said.is_empty());
+        let lines = narrator.observe(&app);
+        assert!(lines[0].starts_with("You warmed the egg"));
+
+        app.pet_mut()
+            .egg_stats
+            .as_mut()
+            .unwrap()
+            .warmth_level
+            .set(29);
+        assert_eq!(narrator.observe(&app), vec!["Warmth dropped to 29"]);
+        assert!(narrator.observe(&app).is_empty());
+    }
+
+    #[test]
+    fn setup_is_answered_line_by_line() {
+        let mut app = App::new();
+        app.begin_setup(crate::setup::Setup::new());
+
+        assert_eq!(handle_command(&mut app, "Pip").len(), 2);
+        assert_eq!(
+            handle_command(&mut app, "dragon"),
+            vec!["There is no choice 'dragon'"]
+        );
+        handle_command(&mut app, "bunny");
+        assert!(handle_command(&mut app, "3").is_empty());
+
+        assert_eq!(app.game_state, GameState::Playing);
+        assert_eq!(app.pet().name, "Pip");
+        assert_eq!(app.pet().species(), Species::Bunny);
+        assert_eq!(app.pet().difficulty, Difficulty::Hard);
+    }
+}
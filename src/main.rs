@@ -1,44 +1,273 @@
 //! MyPet TUI - A terminal-based virtual pet game
 
-use std::io;
+use std::env;
+use std::io::{self, Write};
+use std::process;
 use std::time::Duration;
 
-use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::crossterm::event::{
+    self, DisableFocusChange, DisableMouseCapture, EnableFocusChange, EnableMouseCapture, Event,
+    KeyEventKind,
+};
+use ratatui::crossterm::execute;
 
+mod alerts;
 mod animation;
 mod app;
+mod attention;
+mod cli;
+mod client;
+mod commands;
+mod daemon;
+mod dbus;
+mod difficulty;
 mod events;
+mod genome;
+mod glyphs;
+mod hooks;
+mod household;
+mod http;
+mod i18n;
+mod journal;
+mod keymap;
+mod linear;
+mod mouse;
+mod notify;
+mod paths;
 mod pet;
+mod prompt;
+mod rpc;
+mod save;
+mod scripts;
+mod settings;
+mod setup;
 mod stats;
+mod theme;
 mod tui;
 mod ui;
+mod vacation;
+mod views;
 mod widgets;
 
 use app::App;
+use attention::Attention;
+use cli::Options;
+use client::Client;
+use events::EventTable;
+use glyphs::GlyphMode;
+use hooks::Hooks;
+use journal::{Journal, JournalCategory};
+use keymap::Keymap;
+use notify::Notifier;
+use save::{SaveLock, SaveState};
+use scripts::Scripts;
+use setup::Setup;
+use theme::ThemeSet;
 use tui::Tui;
 
 #[tokio::main]
 async fn main() -> io::Result<()> {
-    let mut terminal = ratatui::init();
+    let options = match Options::parse(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("mypet-tui: {}\n\n{}", err, cli::USAGE);
+            process::exit(2);
+        }
+    };
+    if options.help {
+        println!("{}", cli::USAGE);
+        return Ok(());
+    }
+
+    // Checked first: prompts run this on every command line
+    if options.command == Some(cli::Command::Prompt) {
+        let glyphs = options.glyphs.unwrap_or_else(GlyphMode::detect);
+        process::exit(prompt::run(options.format.as_deref(), glyphs));
+    }
+
+    i18n::set_locale(i18n::Locale::detect());
+    match options.command {
+        Some(cli::Command::Daemon) => process::exit(daemon::run(options.http).await),
+        Some(command) => process::exit(commands::run(command, options.json).await),
+        None => {}
+    }
+
+    let keymap = Keymap::load();
+    for warning in &keymap.warnings {
+        eprintln!("keymap: {}", warning);
+    }
+    let themes = ThemeSet::load();
+    for warning in &themes.warnings {
+        eprintln!("theme: {}", warning);
+    }
+
     let mut app = App::new();
+    let mut problems = Vec::new();
+    if !keymap.warnings.is_empty() {
+        problems.push(i18n::tr!(
+            "notice.keymap_problems",
+            count = keymap.warnings.len(),
+            keys = keymap.keys_label(keymap::Action::Help)
+        ));
+    }
+    if !themes.warnings.is_empty() {
+        problems.push(i18n::tr!(
+            "notice.theme_problems",
+            count = themes.warnings.len(),
+            keys = keymap.keys_label(keymap::Action::ShowSettings)
+        ));
+    }
+    let hooks = Hooks::load();
+    for warning in &hooks.warnings {
+        eprintln!("hooks: {}", warning);
+    }
+    app.hooks = hooks;
+    let notifier = Notifier::load();
+    for warning in &notifier.warnings {
+        eprintln!("notifications: {}", warning);
+    }
+    app.notifier = notifier;
+    // Scripts can't reuse the ids of defined events
+    let table = EventTable::load();
+    for warning in &table.warnings {
+        eprintln!("events: {}", warning);
+    }
+    events::install(table.definitions);
+    let scripts = Scripts::load();
+    for warning in &scripts.warnings {
+        eprintln!("scripts: {}", warning);
+    }
+    app.scripts = scripts;
+    if !options.screen_reader {
+        let attention = Attention::load();
+        for warning in &attention.warnings {
+            eprintln!("attention: {}", warning);
+        }
+        app.attention = attention;
+    }
+    app.keymap = keymap;
+    app.settings.themes = themes;
+    if let Err(err) = app.settings.load() {
+        eprintln!("settings: {}", err);
+    }
+    // Asked for this run only, so never saved with the settings
+    if theme::no_color_requested() {
+        app.settings.themes.select("no-color");
+        app.settings.themes.set_high_contrast(true);
+    }
+    if options.high_contrast {
+        app.settings.themes.set_high_contrast(true);
+    }
+    app.settings.glyphs = options.glyphs.unwrap_or_else(GlyphMode::detect);
+    app.journal = Journal::open(paths::data_dir().join("journal.jsonl"));
+    app.status_cache = Some(prompt::cache_path());
+    let attached = attach(&mut app).await;
+    if let Some(addr) = options.http {
+        if attached {
+            eprintln!("mypet-tui: {}", i18n::tr!("http.use_daemon"));
+            process::exit(1);
+        }
+        match http::start(addr).await {
+            Ok(bridge) => {
+                eprintln!("{}", i18n::tr!("http.listening", addr = bridge.addr));
+                app.http = Some(bridge);
+            }
+            Err(err) => {
+                eprintln!("mypet-tui: http: {}", err);
+                process::exit(1);
+            }
+        }
+    }
+    if !attached {
+        match SaveLock::acquire() {
+            Ok(Some(lock)) => app.save_lock = Some(lock),
+            Ok(None) => {
+                eprintln!("mypet-tui: {}", i18n::tr!("command.game_running"));
+                process::exit(1);
+            }
+            Err(err) => {
+                eprintln!("mypet-tui: {}", i18n::tr!("command.bad_save", error = err));
+                process::exit(1);
+            }
+        }
+        app.journal.record(
+            JournalCategory::System,
+            None,
+            i18n::tr!("journal.session_started"),
+        );
+        match SaveState::load(&save::state_path()) {
+            Ok(Some(state)) => app.restore(state),
+            Ok(None) => app.begin_setup(Setup::new()),
+            Err(err) => {
+                eprintln!("save: {}", err);
+                app.begin_setup(Setup::new());
+            }
+        }
+    }
+    if !problems.is_empty() {
+        app.notice = Some(problems.join("; "));
+    }
 
-    let result = run_app(&mut terminal, &mut app).await;
+    let result = if options.screen_reader {
+        linear::run(&mut app).await
+    } else {
+        let mut terminal = ratatui::init();
+        execute!(io::stdout(), EnableMouseCapture, EnableFocusChange)?;
+        app.attention.begin();
 
-    ratatui::restore();
+        let result = run_app(&mut terminal, &mut app).await;
+
+        app.attention.finish();
+        let mut stdout = io::stdout();
+        stdout.write_all(app.attention.take().as_bytes())?;
+        execute!(stdout, DisableMouseCapture, DisableFocusChange)?;
+        ratatui::restore();
+        result
+    };
+
+    // The daemon saves its own game
+    if let Err(err) = app.save_if_owned() {
+        eprintln!("save: {}", err);
+    }
     result
 }
 
-async fn run_app(
-    terminal: &mut ratatui::DefaultTerminal,
-    app: &mut App,
-) -> io::Result<()> {
+/// Show the daemon's game if one is running; `false` to run the game here
+async fn attach(app: &mut App) -> bool {
+    let Ok(client) = Client::connect(&rpc::socket_path()).await else {
+        return false;
+    };
+    match app.attach(client).await {
+        Ok(()) => true,
+        Err(err) => {
+            eprintln!("daemon: {}", err);
+            false
+        }
+    }
+}
+
+async fn run_app(terminal: &mut ratatui::DefaultTerminal, app: &mut App) -> io::Result<()> {
     let mut tui = Tui::new(terminal);
     let mut last_tick = tokio::time::Instant::now();
     let tick_rate = Duration::from_millis(250);
+    let mut last_save = tokio::time::Instant::now();
 
     loop {
         // Update app state
         app.tick();
+        let cues = app.attention.take();
+        if !cues.is_empty() {
+            let mut stdout = io::stdout();
+            stdout.write_all(cues.as_bytes())?;
+            stdout.flush()?;
+        }
+
+        if last_save.elapsed() >= save::AUTOSAVE_INTERVAL {
+            last_save = tokio::time::Instant::now();
+            if let Err(err) = app.save_if_owned() {
+                app.notice = Some(i18n::tr!("command.save_failed", error = err));
+            }
+        }
 
         // Draw UI
         tui.draw(app)?;
@@ -50,20 +279,18 @@ async fn run_app(
 
         // Poll for events with timeout
         if event::poll(timeout)? {
-            if let Event::Key(key) = event::read()? {
-                if key.kind == KeyEventKind::Press {
-                    match key.code {
-                        KeyCode::Char('q') => app.quit(),
-                        KeyCode::Char('r') => app.restart(),
-                        KeyCode::Char('w') => app.warm_egg(),
-                        KeyCode::Char('f') => app.feed_pet(),
-                        KeyCode::Char('p') => app.play_with_pet(),
-                        KeyCode::Char('c') => app.clean_pet(),
-                        KeyCode::Char('s') => app.toggle_sleep(),
-                        KeyCode::Char('m') => app.give_medicine(),
-                        _ => {}
+            match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => {
+                    // Chords the text field doesn't use still reach the keymap
+                    let typed = views::wants_text_input(app) && views::handle_text_input(app, key);
+                    if !typed && let Some(action) = app.keymap.action_for(&key) {
+                        views::handle_action(app, action);
                     }
                 }
+                Event::Mouse(mouse) => mouse::handle_mouse(app, mouse),
+                Event::FocusGained => app.attention.set_focused(true),
+                Event::FocusLost => app.attention.set_focused(false),
+                _ => {}
             }
         }
 
@@ -0,0 +1,100 @@
+//! Mouse support: hit-testing of rendered regions and click/scroll handling
+
+use ratatui::crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Position, Rect};
+
+use crate::app::App;
+use crate::keymap::Action;
+use crate::views;
+
+/// Something on screen that reacts to the mouse
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HitTarget {
+    /// A button or tab that performs an action
+    Action(Action),
+    /// The pet itself (click to pat)
+    Pet,
+    /// An event log panel (scroll wheel)
+    EventLog,
+    /// A row in the settings view
+    Setting(usize),
+    /// A pet in the household overview
+    SelectPet(usize),
+}
+
+/// Clickable regions recorded while rendering the last frame
+#[derive(Debug, Clone, Default)]
+pub struct HitMap {
+    regions: Vec<(Rect, HitTarget)>,
+}
+
+impl HitMap {
+    /// Forget all regions (start of a new frame)
+    pub fn clear(&mut self) {
+        self.regions.clear();
+    }
+
+    /// Record a region; later regions are on top of earlier ones
+    pub fn add(&mut self, area: Rect, target: HitTarget) {
+        self.regions.push((area, target));
+    }
+
+    /// Topmost target under a screen position
+    pub fn target_at(&self, position: Position) -> Option<HitTarget> {
+        self.regions
+            .iter()
+            .rev()
+            .find(|(area, _)| area.contains(position))
+            .map(|(_, target)| *target)
+    }
+}
+
+/// React to a mouse event using the regions from the last frame
+pub fn handle_mouse(app: &mut App, event: MouseEvent) {
+    let position = Position::new(event.column, event.row);
+    let target = app.hit_map.borrow().target_at(position);
+
+    match event.kind {
+        MouseEventKind::Moved | MouseEventKind::Drag(_) => app.hover = Some(position),
+        MouseEventKind::Down(MouseButton::Left) => match target {
+            Some(HitTarget::Action(action)) => views::handle_action(app, action),
+            Some(HitTarget::Pet) => app.pat_pet(),
+            Some(HitTarget::Setting(index)) => {
+                app.navigation.settings_selected = index;
+                views::handle_action(app, Action::Select);
+            }
+            Some(HitTarget::SelectPet(index)) => app.select_pet(index),
+            Some(HitTarget::EventLog) | None => {}
+        },
+        MouseEventKind::ScrollUp if target == Some(HitTarget::EventLog) => {
+            app.navigation.scroll_event_log_up();
+        }
+        MouseEventKind::ScrollDown if target == Some(HitTarget::EventLog) => {
+            let len = views::event_log::visible_count(app);
+            app.navigation.scroll_event_log_down(len);
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn topmost_region_wins() {
+        let mut hits = HitMap::default();
+        hits.add(Rect::new(0, 0, 10, 10), HitTarget::EventLog);
+        hits.add(Rect::new(2, 2, 3, 1), HitTarget::Action(Action::Feed));
+
+        assert_eq!(
+            hits.target_at(Position::new(3, 2)),
+            Some(HitTarget::Action(Action::Feed))
+        );
+        assert_eq!(
+            hits.target_at(Position::new(3, 3)),
+            Some(HitTarget::EventLog)
+        );
+        assert_eq!(hits.target_at(Position::new(20, 3)), None);
+    }
+}
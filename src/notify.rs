@@ -0,0 +1,251 @@
+//! Desktop notifications when a pet needs care
+//!
+//! Notifications are off unless `notifications.json` exists in the config
+//! directory:
+//!
+//! ```json
+//! {
+//!   "alerts": ["starving", "critical", "egg_danger", "sick", "event"],
+//!   "min_interval_secs": 300,
+//!   "quiet_hours": {"start": "22:00", "end": "07:00"}
+//! }
+//! ```
+//!
+//! Every field is optional. Each kind of alert is sent at most once per
+//! interval, and nothing is sent during quiet hours.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::time::{Duration, Instant};
+
+use chrono::{Local, NaiveTime};
+use serde::Deserialize;
+
+use crate::alerts::{Alert, AlertKind};
+use crate::dbus::{self, Notification, Urgency};
+use crate::paths;
+
+/// File name of the settings inside the config directory
+const NOTIFICATIONS_FILE: &str = "notifications.json";
+/// Default time between two alerts of the same kind
+const DEFAULT_INTERVAL_SECS: u64 = 300;
+
+/// A stretch of the day, which may wrap past midnight
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuietHours {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl QuietHours {
+    /// Whether `time` falls inside
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+/// Sends alerts to the desktop
+#[derive(Debug, Default)]
+pub struct Notifier {
+    /// Session bus to notify over; `None` when notifications are off
+    pub address: Option<String>,
+    /// Kinds of alert that are sent
+    pub alerts: Vec<AlertKind>,
+    /// Least time between two alerts of the same kind
+    pub min_interval: Duration,
+    pub quiet_hours: Option<QuietHours>,
+    /// Problems with the settings, for the player to fix
+    pub warnings: Vec<String>,
+    last_sent: HashMap<AlertKind, Instant>,
+}
+
+impl Notifier {
+    /// Read the settings from the config directory
+    pub fn load() -> Self {
+        let path = paths::config_dir().join(NOTIFICATIONS_FILE);
+        match fs::read_to_string(&path) {
+            Ok(json) => Self::from_json(&json, dbus::session_address()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Self::default(),
+            Err(err) => Self {
+                warnings: vec![format!("could not read {}: {}", path.display(), err)],
+                ..Self::default()
+            },
+        }
+    }
+
+    /// Build the notifier from the contents of a settings file
+    pub fn from_json(json: &str, address: Option<String>) -> Self {
+        #[derive(Deserialize)]
+        struct Settings {
+            #[serde(default = "enabled")]
+            enabled: bool,
+            #[serde(default = "all_alerts")]
+            alerts: Vec<AlertKind>,
+            #[serde(default = "default_interval")]
+            min_interval_secs: u64,
+            quiet_hours: Option<Hours>,
+        }
+
+        #[derive(Deserialize)]
+        struct Hours {
+            start: String,
+            end: String,
+        }
+
+        fn enabled() -> bool {
+            true
+        }
+        fn all_alerts() -> Vec<AlertKind> {
+            AlertKind::ALL.to_vec()
+        }
+        fn default_interval() -> u64 {
+            DEFAULT_INTERVAL_SECS
+        }
+
+        let mut notifier = Self::default();
+        let settings = match serde_json::from_str::<Settings>(json) {
+            Ok(settings) => settings,
+            Err(err) => {
+                notifier
+                    .warnings
+                    .push(format!("invalid notifications file: {}", err));
+                return notifier;
+            }
+        };
+        if !settings.enabled {
+            return notifier;
+        }
+
+        if let Some(hours) = settings.quiet_hours {
+            let parse = |time: &str| NaiveTime::parse_from_str(time, "%H:%M");
+            match (parse(&hours.start), parse(&hours.end)) {
+                (Ok(start), Ok(end)) => notifier.quiet_hours = Some(QuietHours { start, end }),
+                _ => notifier.warnings.push(format!(
+                    "quiet hours '{}'-'{}' are not HH:MM times",
+                    hours.start, hours.end
+                )),
+            }
+        }
+        if address.is_none() {
+            notifier
+                .warnings
+                .push("no session bus to send notifications over".to_string());
+        }
+        notifier.address = address;
+        notifier.alerts = settings.alerts;
+        notifier.min_interval = Duration::from_secs(settings.min_interval_secs);
+        notifier
+    }
+
+    /// Send the alerts worth sending, in the background
+    pub fn send(&mut self, alerts: &[Alert]) {
+        let Some(address) = self.address.clone() else {
+            return;
+        };
+        for notification in self.due(alerts, Instant::now(), Local::now().time()) {
+            let address = address.clone();
+            tokio::spawn(async move {
+                if let Err(err) = dbus::notify(&address, &notification).await {
+                    tracing::warn!("could not send a notification: {}", err);
+                }
+            });
+        }
+    }
+
+    /// Notifications for the alerts that are wanted and not too soon
+    fn due(&mut self, alerts: &[Alert], now: Instant, time: NaiveTime) -> Vec<Notification> {
+        if self.quiet_hours.is_some_and(|quiet| quiet.contains(time)) {
+            return Vec::new();
+        }
+
+        let mut due = Vec::new();
+        for alert in alerts {
+            if !self.alerts.contains(&alert.kind) {
+                continue;
+            }
+            if let Some(last) = self.last_sent.get(&alert.kind)
+                && now.duration_since(*last) < self.min_interval
+            {
+                continue;
+            }
+            self.last_sent.insert(alert.kind, now);
+            due.push(Notification {
+                summary: alert.name.clone(),
+                body: alert.message.clone(),
+                urgency: if alert.kind.is_urgent() {
+                    Urgency::Critical
+                } else {
+                    Urgency::Normal
+                },
+            });
+        }
+        due
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alert(kind: AlertKind) -> Alert {
+        Alert {
+            kind,
+            name: "Fluffy".to_string(),
+            message: "Fluffy is very hungry!".to_string(),
+        }
+    }
+
+    fn time(hour: u32, minute: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn reads_settings() {
+        let notifier = Notifier::from_json("{}", Some("unix:path=/bus".to_string()));
+        assert_eq!(notifier.alerts, AlertKind::ALL);
+        assert_eq!(notifier.min_interval, Duration::from_secs(300));
+        assert!(notifier.warnings.is_empty());
+
+        let off = Notifier::from_json(r#"{"enabled": false}"#, Some("unix:path=/bus".into()));
+        assert!(off.address.is_none());
+
+        let bad = Notifier::from_json(
+            r#"{"quiet_hours": {"start": "late", "end": "07:00"}}"#,
+            None,
+        );
+        assert!(bad.quiet_hours.is_none());
+        assert_eq!(bad.warnings.len(), 2);
+    }
+
+    #[test]
+    fn respects_quiet_hours_and_rate_limits() {
+        let mut notifier = Notifier::from_json(
+            r#"{"alerts": ["starving", "sick"], "min_interval_secs": 60,
+                "quiet_hours": {"start": "22:00", "end": "07:00"}}"#,
+            Some("unix:path=/bus".to_string()),
+        );
+        let start = Instant::now();
+        let alerts = [alert(AlertKind::Starving), alert(AlertKind::Event)];
+
+        assert!(notifier.due(&alerts, start, time(23, 30)).is_empty());
+        assert!(notifier.due(&alerts, start, time(6, 59)).is_empty());
+
+        let sent = notifier.due(&alerts, start, time(12, 0));
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].body, "Fluffy is very hungry!");
+        assert_eq!(sent[0].urgency, Urgency::Critical);
+
+        let soon = start + Duration::from_secs(30);
+        assert!(notifier.due(&alerts, soon, time(12, 0)).is_empty());
+        let sick = notifier.due(&[alert(AlertKind::Sick)], soon, time(12, 0));
+        assert_eq!(sick.len(), 1);
+
+        let later = start + Duration::from_secs(61);
+        assert_eq!(notifier.due(&alerts, later, time(12, 1)).len(), 1);
+    }
+}
@@ -0,0 +1,32 @@
+//! Locations of config and data files
+//!
+//! Follows the XDG base directory spec, with `MYPET_CONFIG_DIR` and
+//! `MYPET_DATA_DIR` overriding everything (handy for tests and scripts).
+
+use std::env;
+use std::path::PathBuf;
+
+const APP_DIR: &str = "mypet-tui";
+
+/// Directory holding user-editable configuration (keymap, themes, ...)
+pub fn config_dir() -> PathBuf {
+    resolve("MYPET_CONFIG_DIR", "XDG_CONFIG_HOME", ".config")
+}
+
+/// Directory holding data written by the game itself (journal, saves)
+pub fn data_dir() -> PathBuf {
+    resolve("MYPET_DATA_DIR", "XDG_DATA_HOME", ".local/share")
+}
+
+fn resolve(override_var: &str, xdg_var: &str, home_fallback: &str) -> PathBuf {
+    if let Some(dir) = env::var_os(override_var).filter(|v| !v.is_empty()) {
+        return PathBuf::from(dir);
+    }
+
+    if let Some(dir) = env::var_os(xdg_var).filter(|v| !v.is_empty()) {
+        return PathBuf::from(dir).join(APP_DIR);
+    }
+
+    let home = env::var_os("HOME").map(PathBuf::from).unwrap_or_default();
+    home.join(home_fallback).join(APP_DIR)
+}
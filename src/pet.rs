@@ -2,10 +2,134 @@
 
 use std::time::{Duration, Instant};
 
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::difficulty::Difficulty;
+use crate::genome::Genome;
+use crate::i18n::{self, tr};
+use crate::keymap::Action;
+use crate::save::wall_clock;
 use crate::stats::{StatValue, Stats};
 
+/// How often stats decay and eggs incubate
+pub const DECAY_INTERVAL: Duration = Duration::from_secs(5);
+/// Minimum time between pats that still cheer the pet up
+const PAT_COOLDOWN: Duration = Duration::from_secs(3);
+/// Happiness both pets need for a match
+pub const MATCH_HAPPINESS: u8 = 70;
+/// How long an adult rests after laying an egg
+pub const MATCH_COOLDOWN: Duration = Duration::from_secs(10 * 60);
+/// Age at which a baby grows into a child
+pub const CHILD_AGE: Duration = Duration::from_secs(5 * 60);
+/// Age at which a child grows into a teen
+pub const TEEN_AGE: Duration = Duration::from_secs(15 * 60);
+/// Age at which a teen grows into an adult
+pub const ADULT_AGE: Duration = Duration::from_secs(30 * 60);
+
+/// Why the pet refused an action
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ActionError {
+    /// Only eggs can be warmed
+    #[error("{}", i18n::lookup("error.already_hatched"))]
+    AlreadyHatched,
+    /// The egg can't get any warmer
+    #[error("{}", i18n::lookup("error.warm_enough"))]
+    WarmEnough,
+    /// The action needs a hatched pet
+    #[error("{}", i18n::lookup("error.not_hatched"))]
+    NotHatched,
+    /// Eggs neither sleep nor wake up
+    #[error("{}", i18n::lookup("error.is_egg"))]
+    IsEgg,
+    /// Not enough energy to play
+    #[error("{}", tr!("error.too_tired", energy = energy))]
+    TooTired { energy: u8 },
+    /// The pet has to be woken up first
+    #[error("{}", i18n::lookup("error.asleep"))]
+    Asleep,
+    /// The pet is already sleeping
+    #[error("{}", i18n::lookup("error.already_sleeping"))]
+    AlreadyAsleep,
+    /// There is nobody to wake up
+    #[error("{}", i18n::lookup("error.not_sleeping"))]
+    NotAsleep,
+    /// Medicine only helps sick pets
+    #[error("{}", i18n::lookup("error.not_sick"))]
+    NotSick,
+    /// The pet has passed away
+    #[error("{}", i18n::lookup("error.dead"))]
+    Dead,
+    /// The last pat was too recent
+    #[error("{}", i18n::lookup("error.pat_cooldown"))]
+    PatCooldown,
+    /// Medicine is disabled on Hardcore
+    #[error("{}", i18n::lookup("error.no_medicine"))]
+    NoMedicine,
+    /// Only adults can have eggs
+    #[error("{}", tr!("error.not_adult", name = name))]
+    NotAdult { name: String },
+    /// Not happy enough for a match
+    #[error("{}", tr!("error.unhappy", name = name, min = MATCH_HAPPINESS))]
+    Unhappy { name: String },
+    /// Had an egg too recently
+    #[error("{}", tr!("error.match_cooldown", name = name))]
+    MatchCooldown { name: String },
+    /// No other pet could be matched
+    #[error("{}", i18n::lookup("error.no_partner"))]
+    NoPartner,
+}
+
+impl ActionError {
+    /// Action that would get past the refusal, if there is one
+    pub fn suggestion(&self) -> Option<Action> {
+        match self {
+            ActionError::NotHatched | ActionError::IsEgg => Some(Action::Warm),
+            ActionError::TooTired { .. } | ActionError::Asleep => Some(Action::Sleep),
+            _ => None,
+        }
+    }
+}
+
+/// Kind of animal that hatches from the egg
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Species {
+    #[default]
+    Cat,
+    Bunny,
+    Bear,
+}
+
+impl Species {
+    /// All species, in the order the new-game wizard offers them
+    pub const ALL: [Species; 3] = [Species::Cat, Species::Bunny, Species::Bear];
+
+    /// Ears in the built-in art, which is drawn as a cat
+    pub const CAT_EARS: &'static str = "/\\_/\\";
+
+    /// Display name
+    pub fn label(self) -> &'static str {
+        i18n::lookup(match self {
+            Species::Cat => "species.cat",
+            Species::Bunny => "species.bunny",
+            Species::Bear => "species.bear",
+        })
+    }
+
+    /// Ears drawn in place of [`Species::CAT_EARS`]; always the same width
+    pub fn ears(self) -> &'static str {
+        match self {
+            Species::Cat => Self::CAT_EARS,
+            Species::Bunny => "(\\_/)",
+            Species::Bear => "()_()",
+        }
+    }
+}
+
 /// Life stages of a pet
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum LifeStage {
     /// Egg stage (first 30 seconds)
     Egg,
@@ -31,17 +155,34 @@ impl LifeStage {
         }
     }
 
-    /// Get display name for the stage
-    pub fn display_name(self) -> &'static str {
+    /// Whether pets at this stage can have eggs
+    pub fn is_fertile(self) -> bool {
+        self == LifeStage::Adult
+    }
+
+    /// `egg`, `baby`, `child`, `teen` or `adult`, as scripts and data files
+    /// call it
+    pub fn id(self) -> &'static str {
         match self {
-            LifeStage::Egg => "Egg",
-            LifeStage::Baby => "Baby",
-            LifeStage::Child => "Child",
-            LifeStage::Teen => "Teen",
-            LifeStage::Adult => "Adult",
+            LifeStage::Egg => "egg",
+            LifeStage::Baby => "baby",
+            LifeStage::Child => "child",
+            LifeStage::Teen => "teen",
+            LifeStage::Adult => "adult",
         }
     }
 
+    /// Get display name for the stage
+    pub fn display_name(self) -> &'static str {
+        i18n::lookup(match self {
+            LifeStage::Egg => "stage.egg",
+            LifeStage::Baby => "stage.baby",
+            LifeStage::Child => "stage.child",
+            LifeStage::Teen => "stage.teen",
+            LifeStage::Adult => "stage.adult",
+        })
+    }
+
     /// Get ASCII art for the stage
     pub fn ascii_art(self) -> &'static str {
         match self {
@@ -95,14 +236,21 @@ impl LifeStage {
 }
 
 /// Current state of the pet
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
 pub enum PetState {
     /// Normal active state
     Normal,
     /// Pet is sleeping
-    Sleeping { since: Instant },
+    Sleeping {
+        #[serde(with = "wall_clock")]
+        since: Instant,
+    },
     /// Pet is sick
-    Sick { since: Instant },
+    Sick {
+        #[serde(with = "wall_clock")]
+        since: Instant,
+    },
     /// Pet has died :(
     Dead,
 }
@@ -127,10 +275,21 @@ impl PetState {
     pub fn is_alive(self) -> bool {
         !matches!(self, PetState::Dead)
     }
+
+    /// `normal`, `sleeping`, `sick` or `dead`, as scripts and data files
+    /// call it
+    pub fn id(self) -> &'static str {
+        match self {
+            PetState::Normal => "normal",
+            PetState::Sleeping { .. } => "sleeping",
+            PetState::Sick { .. } => "sick",
+            PetState::Dead => "dead",
+        }
+    }
 }
 
 /// Stats specific to Egg stage
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EggStats {
     /// Incubation progress (0-100), time-based
     pub incubation_progress: StatValue,
@@ -143,11 +302,12 @@ pub struct EggStats {
 }
 
 impl EggStats {
-    /// Create new egg stats
-    pub fn new() -> Self {
+    /// Create new egg stats; eggs from healthier lines start warmer
+    pub fn new(genome: &Genome) -> Self {
         Self {
             incubation_progress: StatValue::new(0),
-            warmth_level: StatValue::new(20), // Start a bit cold
+            // Start a bit cold
+            warmth_level: StatValue::new(20u8.saturating_add_signed(genome.modifiers.health)),
             health: StatValue::new(100),
             is_dead: false,
         }
@@ -156,15 +316,19 @@ impl EggStats {
 
 impl Default for EggStats {
     fn default() -> Self {
-        Self::new()
+        Self::new(&Genome::default())
     }
 }
 
 /// The main Pet struct
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Pet {
     /// Pet's name
     pub name: String,
+    /// Species, coat and inherited stat modifiers
+    pub genome: Genome,
+    /// Difficulty the game was started with
+    pub difficulty: Difficulty,
     /// Current life stage
     pub stage: LifeStage,
     /// Current state
@@ -172,31 +336,55 @@ pub struct Pet {
     /// All stats (used after hatching)
     pub stats: Stats,
     /// When the pet was born
+    #[allow(dead_code)]
+    #[serde(with = "wall_clock")]
     pub birth_time: Instant,
     /// Total age in seconds
     pub age_seconds: u64,
     /// Last time stats were decayed
+    #[serde(with = "wall_clock")]
     last_decay: Instant,
     /// Egg-specific stats (only used during Egg stage)
     pub egg_stats: Option<EggStats>,
+    /// Last time the pet was patted
+    #[serde(default, with = "wall_clock::option")]
+    last_pat: Option<Instant>,
+    /// Last time the pet had an egg
+    #[serde(default, with = "wall_clock::option")]
+    last_match: Option<Instant>,
 }
 
 impl Pet {
-    /// Create a new pet with the given name
-    pub fn new(name: impl Into<String>) -> Self {
+    /// Create a new egg with the given name and genes
+    pub fn new(name: impl Into<String>, genome: Genome) -> Self {
         let now = Instant::now();
         Self {
             name: name.into(),
+            genome,
+            difficulty: Difficulty::default(),
             stage: LifeStage::Egg,
             state: PetState::Normal,
             stats: Stats::new(),
             birth_time: now,
             age_seconds: 0,
             last_decay: now,
-            egg_stats: Some(EggStats::new()),
+            egg_stats: Some(EggStats::new(&genome)),
+            last_pat: None,
+            last_match: None,
         }
     }
 
+    /// Kind of animal
+    pub fn species(&self) -> Species {
+        self.genome.species
+    }
+
+    /// Set the difficulty
+    pub fn with_difficulty(mut self, difficulty: Difficulty) -> Self {
+        self.difficulty = difficulty;
+        self
+    }
+
     /// Check if egg is dead (failed to hatch)
     pub fn is_egg_dead(&self) -> bool {
         match &self.egg_stats {
@@ -244,14 +432,41 @@ impl Pet {
         }
     }
 
-    /// Update the pet (call every frame/tick)
-    pub fn update(&mut self, delta_time: Duration) {
+    /// Update the pet (call every frame/tick); returns whether a decay
+    /// interval passed
+    pub fn update(&mut self, delta_time: Duration) -> bool {
         // Update age
         self.age_seconds += delta_time.as_secs();
 
+        let due = self.last_decay.elapsed() >= DECAY_INTERVAL;
+        if due {
+            self.last_decay = Instant::now();
+        }
+        self.step(due);
+        due
+    }
+
+    /// Let a whole decay interval pass at once, for time spent away
+    pub fn advance(&mut self) {
+        self.age_seconds += DECAY_INTERVAL.as_secs();
+        // Naps, sickness and cooldowns count the interval as passed too
+        let earlier = |at: Instant| at.checked_sub(DECAY_INTERVAL).unwrap_or(at);
+        self.last_pat = self.last_pat.map(earlier);
+        self.last_match = self.last_match.map(earlier);
+        match &mut self.state {
+            PetState::Sleeping { since } | PetState::Sick { since } => *since = earlier(*since),
+            PetState::Normal | PetState::Dead => {}
+        }
+        self.step(true);
+    }
+
+    /// Grow up, and decay or incubate when an interval is over
+    fn step(&mut self, decay: bool) {
         // Handle Egg stage separately
         if self.stage == LifeStage::Egg {
-            self.update_egg();
+            if decay {
+                self.incubate();
+            }
             return;
         }
 
@@ -263,10 +478,8 @@ impl Pet {
         // Update life stage based on age
         self.update_life_stage();
 
-        // Apply stat decay every 5 seconds
-        if self.last_decay.elapsed() >= Duration::from_secs(5) {
-            self.stats.decay();
-            self.last_decay = Instant::now();
+        if decay {
+            self.stats.decay(self.difficulty.decay_percent());
 
             // Check for death
             if self.stats.health.value() == 0 {
@@ -282,38 +495,33 @@ impl Pet {
         }
     }
 
-    /// Update egg mechanics
-    fn update_egg(&mut self) {
+    /// Update egg mechanics, once per decay interval
+    fn incubate(&mut self) {
         if let Some(ref mut egg) = self.egg_stats {
-            // Check every 5 seconds
-            if self.last_decay.elapsed() >= Duration::from_secs(5) {
-                self.last_decay = Instant::now();
-
-                // Incubation progress increases over time (30 seconds total = 100%)
-                // Every 5 seconds = ~16.67% progress
-                egg.incubation_progress.add(17);
-
-                // Warmth decays slowly (-3 every 5 seconds)
-                egg.warmth_level.sub(3);
-
-                // Health mechanics based on warmth
-                if egg.warmth_level.value() < 30 {
-                    // Egg is too cold - health drops
-                    egg.health.sub(10);
-                } else {
-                    // Egg is warm enough - health recovers slowly
-                    egg.health.add(5);
-                }
+            // Incubation progress increases over time (30 seconds total = 100%)
+            // Every 5 seconds = ~16.67% progress
+            egg.incubation_progress.add(17);
+
+            // Warmth decays slowly (-3 every 5 seconds on Normal)
+            egg.warmth_level.sub(self.difficulty.scale(3));
+
+            // Health mechanics based on warmth
+            if egg.warmth_level.value() < 30 {
+                // Egg is too cold - health drops
+                egg.health.sub(10);
+            } else {
+                // Egg is warm enough - health recovers slowly
+                egg.health.add(5);
+            }
 
-                // Check if egg died
-                if egg.health.value() == 0 {
-                    egg.is_dead = true;
-                }
+            // Check if egg died
+            if egg.health.value() == 0 {
+                egg.is_dead = true;
+            }
 
-                // Check if ready to hatch
-                if egg.incubation_progress.is_max() && !egg.is_dead {
-                    self.hatch_egg();
-                }
+            // Check if ready to hatch
+            if egg.incubation_progress.is_max() && !egg.is_dead {
+                self.hatch_egg();
             }
         }
     }
@@ -339,21 +547,42 @@ impl Pet {
             }
             // Medium warmth = normal stats (no change)
         }
+        self.genome.modifiers.apply(&mut self.stats);
 
         // Hatch!
         self.stage = LifeStage::Baby;
         self.egg_stats = None; // No longer needed
     }
 
+    /// Refuse actions that need a hatched pet
+    fn check_hatched(&self) -> Result<(), ActionError> {
+        if self.stage == LifeStage::Egg {
+            Err(ActionError::NotHatched)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Refuse actions that need the pet awake and alive
+    fn check_can_act(&self) -> Result<(), ActionError> {
+        if self.state.can_act() {
+            Ok(())
+        } else if self.state.is_alive() {
+            Err(ActionError::Asleep)
+        } else {
+            Err(ActionError::Dead)
+        }
+    }
+
     /// Warm the egg (only available in Egg stage)
-    pub fn warm(&mut self) -> Result<(), &'static str> {
+    pub fn warm(&mut self) -> Result<(), ActionError> {
         if self.stage != LifeStage::Egg {
-            return Err("The pet has already hatched!");
+            return Err(ActionError::AlreadyHatched);
         }
 
         if let Some(ref mut egg) = self.egg_stats {
             if egg.warmth_level.value() >= 100 {
-                return Err("The egg is warm enough!");
+                return Err(ActionError::WarmEnough);
             }
 
             egg.warmth_level.add(10);
@@ -365,20 +594,27 @@ impl Pet {
         Ok(())
     }
 
+    /// Push every timer forward so a pause doesn't count as time passed
+    pub fn shift_clock(&mut self, paused: Duration) {
+        self.last_decay += paused;
+        self.last_pat = self.last_pat.map(|at| at + paused);
+        self.last_match = self.last_match.map(|at| at + paused);
+        match &mut self.state {
+            PetState::Sleeping { since } | PetState::Sick { since } => *since += paused,
+            PetState::Normal | PetState::Dead => {}
+        }
+    }
+
     /// Restart with a new egg (game over)
+    #[allow(dead_code)]
     pub fn restart(&mut self) {
-        *self = Self::new(&self.name);
+        *self = Self::new(&self.name, self.genome).with_difficulty(self.difficulty);
     }
 
     /// Feed the pet
-    pub fn feed(&mut self) -> Result<(), &'static str> {
-        if self.stage == LifeStage::Egg {
-            return Err("Can't feed an egg! Try warming it instead.");
-        }
-
-        if !self.state.can_act() {
-            return Err("Pet cannot eat right now");
-        }
+    pub fn feed(&mut self) -> Result<(), ActionError> {
+        self.check_hatched()?;
+        self.check_can_act()?;
 
         self.stats.hunger.add(25);
         self.stats.energy.sub(5); // Eating takes some energy
@@ -386,19 +622,15 @@ impl Pet {
     }
 
     /// Play with the pet
-    pub fn play(&mut self) -> Result<(), &'static str> {
-        if self.stage == LifeStage::Egg {
-            return Err("Can't play with an egg!");
-        }
-
-        if !self.state.can_act() {
-            return Err("Pet cannot play right now");
-        }
+    pub fn play(&mut self) -> Result<(), ActionError> {
+        self.check_hatched()?;
+        self.check_can_act()?;
 
         // Baby stage restrictions
         if self.stage == LifeStage::Baby {
-            if self.stats.energy.value() < 30 {
-                return Err("Baby is too tired. Let it sleep first!");
+            let energy = self.stats.energy.value();
+            if energy < 30 {
+                return Err(ActionError::TooTired { energy });
             }
             // Baby can't play for long
             self.stats.happiness.add(15); // Less happiness gain
@@ -407,8 +639,9 @@ impl Pet {
             return Ok(());
         }
 
-        if self.stats.energy.value() < 20 {
-            return Err("Pet is too tired to play");
+        let energy = self.stats.energy.value();
+        if energy < 20 {
+            return Err(ActionError::TooTired { energy });
         }
 
         self.stats.happiness.add(20);
@@ -418,14 +651,9 @@ impl Pet {
     }
 
     /// Clean the pet
-    pub fn clean(&mut self) -> Result<(), &'static str> {
-        if self.stage == LifeStage::Egg {
-            return Err("Can't clean an egg!");
-        }
-
-        if !self.state.can_act() {
-            return Err("Pet cannot be cleaned right now");
-        }
+    pub fn clean(&mut self) -> Result<(), ActionError> {
+        self.check_hatched()?;
+        self.check_can_act()?;
 
         self.stats.hygiene = StatValue::new(100);
 
@@ -438,17 +666,17 @@ impl Pet {
     }
 
     /// Put pet to sleep
-    pub fn sleep(&mut self) -> Result<(), &'static str> {
+    pub fn sleep(&mut self) -> Result<(), ActionError> {
         if self.stage == LifeStage::Egg {
-            return Err("Eggs don't sleep! Try warming it.");
+            return Err(ActionError::IsEgg);
         }
 
         if !self.state.is_alive() {
-            return Err("Pet is dead");
+            return Err(ActionError::Dead);
         }
 
         if matches!(self.state, PetState::Sleeping { .. }) {
-            return Err("Pet is already sleeping");
+            return Err(ActionError::AlreadyAsleep);
         }
 
         self.state = PetState::Sleeping {
@@ -458,7 +686,11 @@ impl Pet {
     }
 
     /// Wake up the pet
-    pub fn wake(&mut self) -> Result<(), &'static str> {
+    pub fn wake(&mut self) -> Result<(), ActionError> {
+        if self.stage == LifeStage::Egg {
+            return Err(ActionError::IsEgg);
+        }
+
         match self.state {
             PetState::Sleeping { since } => {
                 let sleep_duration = since.elapsed().as_secs();
@@ -476,19 +708,80 @@ impl Pet {
                 self.state = PetState::Normal;
                 Ok(())
             }
-            _ => Err("Pet is not sleeping"),
+            _ => Err(ActionError::NotAsleep),
         }
     }
 
     /// Give medicine to the pet
-    pub fn give_medicine(&mut self) -> Result<(), &'static str> {
+    pub fn give_medicine(&mut self) -> Result<(), ActionError> {
+        if !self.difficulty.allows_medicine() {
+            return Err(ActionError::NoMedicine);
+        }
         if matches!(self.state, PetState::Sick { .. }) {
             self.state = PetState::Normal;
             self.stats.health.add(20);
             Ok(())
         } else {
-            Err("Pet is not sick")
+            Err(ActionError::NotSick)
+        }
+    }
+
+    /// Pat the pet for a small happiness boost
+    pub fn pat(&mut self) -> Result<(), ActionError> {
+        self.check_hatched()?;
+        self.check_can_act()?;
+
+        if self.last_pat.is_some_and(|at| at.elapsed() < PAT_COOLDOWN) {
+            return Err(ActionError::PatCooldown);
+        }
+
+        self.stats.happiness.add(3);
+        self.last_pat = Some(Instant::now());
+        Ok(())
+    }
+
+    /// Refuse a match unless the pet is a happy, rested adult
+    pub fn check_can_match(&self) -> Result<(), ActionError> {
+        let name = self.name.clone();
+        if !self.stage.is_fertile() {
+            return Err(ActionError::NotAdult { name });
         }
+        self.check_can_act()?;
+        if self.stats.happiness.value() < MATCH_HAPPINESS {
+            return Err(ActionError::Unhappy { name });
+        }
+        if self
+            .last_match
+            .is_some_and(|at| at.elapsed() < MATCH_COOLDOWN)
+        {
+            return Err(ActionError::MatchCooldown { name });
+        }
+        Ok(())
+    }
+
+    /// Have an egg with `partner`, mixing both genomes; the egg keeps this
+    /// pet's difficulty
+    pub fn match_with(&mut self, partner: &mut Pet, name: &str) -> Result<Pet, ActionError> {
+        self.check_can_match()?;
+        partner.check_can_match()?;
+
+        let now = Instant::now();
+        self.last_match = Some(now);
+        partner.last_match = Some(now);
+        Ok(Pet::new(name, Genome::cross(&self.genome, &partner.genome))
+            .with_difficulty(self.difficulty))
+    }
+
+    /// Age in seconds at which the pet grows into its next stage; `None`
+    /// for eggs, which hatch once incubated, and for adults
+    pub fn next_stage_at(&self) -> Option<u64> {
+        let age = match self.stage {
+            LifeStage::Egg | LifeStage::Adult => return None,
+            LifeStage::Baby => CHILD_AGE,
+            LifeStage::Child => TEEN_AGE,
+            LifeStage::Teen => ADULT_AGE,
+        };
+        Some(age.as_secs())
     }
 
     /// Update life stage based on age
@@ -497,13 +790,8 @@ impl Pet {
             return;
         }
 
-        let age_minutes = self.age_seconds / 60;
-
-        let new_stage = match self.stage {
-            LifeStage::Egg => LifeStage::Egg,
-            LifeStage::Baby if age_minutes >= 5 => LifeStage::Child,
-            LifeStage::Child if age_minutes >= 15 => LifeStage::Teen,
-            LifeStage::Teen if age_minutes >= 30 => LifeStage::Adult,
+        let new_stage = match (self.stage.next(), self.next_stage_at()) {
+            (Some(next), Some(at)) if self.age_seconds >= at => next,
             _ => return,
         };
 
@@ -514,62 +802,64 @@ impl Pet {
 
     /// Get a status message
     pub fn status_message(&self) -> String {
+        let name = &self.name;
         if !self.state.is_alive() {
-            return format!("{} has passed away...", self.name);
+            return tr!("status.dead", name = name);
         }
 
         // Egg stage messages based on warmth level
-        if self.stage == LifeStage::Egg {
-            if let Some(ref egg) = self.egg_stats {
-                if egg.is_dead {
-                    return format!("The egg failed to hatch... It was too cold.");
-                }
-
-                let warmth = egg.warmth_level.value();
-                let health = egg.health.value();
+        if self.stage == LifeStage::Egg
+            && let Some(ref egg) = self.egg_stats
+        {
+            if egg.is_dead {
+                return tr!("status.egg_failed");
+            }
 
-                // Critical health warning
-                if health < 30 {
-                    return format!("⚠ CRITICAL: The egg is dying! Warm it NOW!");
-                }
+            let warmth = egg.warmth_level.value();
+            let health = egg.health.value();
 
-                // Messages based on warmth level
-                return match warmth {
-                    0..=20 => format!("⚠ The egg is FREEZING! Warm it quickly or it will die!"),
-                    21..=40 => format!("The egg feels cold... Try pressing [W] to warm it!"),
-                    41..=60 => format!("The egg is getting warmer..."),
-                    61..=80 => format!("The egg is cozy and warm!"),
-                    81..=95 => format!("The egg is very warm! The baby will be healthy!"),
-                    _ => format!("🎉 The egg is ready to hatch any moment!"),
-                };
+            // Critical health warning
+            if health < 30 {
+                return tr!("status.egg_dying");
             }
+
+            // Messages based on warmth level
+            return tr!(match warmth {
+                0..=20 => "status.egg_freezing",
+                21..=40 => "status.egg_cold",
+                41..=60 => "status.egg_warming",
+                61..=80 => "status.egg_cozy",
+                81..=95 => "status.egg_very_warm",
+                _ => "status.egg_ready",
+            });
         }
 
-        match self.state {
-            PetState::Sleeping { .. } => format!("{} is sleeping peacefully", self.name),
-            PetState::Sick { .. } => format!("{} is not feeling well", self.name),
+        let key = match self.state {
+            PetState::Sleeping { .. } => "status.sleeping",
+            PetState::Sick { .. } => "status.sick",
             _ => {
                 if self.stats.is_starving() {
-                    format!("{} is very hungry!", self.name)
+                    "status.starving"
                 } else if self.stats.is_depressed() {
-                    format!("{} seems sad...", self.name)
+                    "status.depressed"
                 } else if self.stats.is_exhausted() {
-                    format!("{} is exhausted", self.name)
+                    "status.exhausted"
                 } else if self.stats.is_filthy() {
-                    format!("{} needs a bath", self.name)
+                    "status.filthy"
                 } else if self.stage == LifeStage::Baby {
-                    format!("{} is a cute baby!", self.name)
+                    "status.baby"
                 } else {
-                    format!("{} is doing well!", self.name)
+                    "status.well"
                 }
             }
-        }
+        };
+        tr!(key, name = name)
     }
 }
 
 impl Default for Pet {
     fn default() -> Self {
-        Self::new("Fluffy")
+        Self::new("Fluffy", Genome::default())
     }
 }
 
@@ -579,13 +869,13 @@ mod tests {
 
     #[test]
     fn pet_starts_as_egg() {
-        let pet = Pet::new("Test");
+        let pet = Pet::new("Test", Genome::default());
         assert_eq!(pet.stage, LifeStage::Egg);
     }
 
     #[test]
     fn pet_ages_correctly() {
-        let mut pet = Pet::new("Test");
+        let mut pet = Pet::new("Test", Genome::default());
         // Simulate hatching by setting age to after hatching and clearing egg_stats
         pet.age_seconds = 31; // Just over 30s
         pet.stage = LifeStage::Baby; // Skip egg stage
@@ -600,19 +890,88 @@ mod tests {
 
     #[test]
     fn feeding_increases_hunger() {
-        let mut pet = Pet::new("Test");
+        let mut pet = Pet::new("Test", Genome::default());
         pet.stage = LifeStage::Baby; // Skip egg stage
         pet.stats.hunger = StatValue::new(30);
         pet.feed().unwrap();
         assert!(pet.stats.hunger.value() > 30);
     }
 
+    #[test]
+    fn eggs_are_refused_with_a_hint() {
+        let mut pet = Pet::new("Test", Genome::default());
+        assert_eq!(pet.feed(), Err(ActionError::NotHatched));
+        assert_eq!(pet.sleep(), Err(ActionError::IsEgg));
+        assert_eq!(pet.wake(), Err(ActionError::IsEgg));
+        assert_eq!(ActionError::IsEgg.suggestion(), Some(Action::Warm));
+    }
+
+    #[test]
+    fn patting_has_a_cooldown() {
+        let mut pet = Pet::new("Test", Genome::default());
+        pet.stage = LifeStage::Baby;
+        pet.stats.happiness = StatValue::new(50);
+
+        pet.pat().unwrap();
+        assert_eq!(pet.stats.happiness.value(), 53);
+        assert!(pet.pat().is_err());
+    }
+
     #[test]
     fn sleeping_pet_cannot_eat() {
-        let mut pet = Pet::new("Test");
+        let mut pet = Pet::new("Test", Genome::default());
+        pet.stage = LifeStage::Baby;
         pet.state = PetState::Sleeping {
             since: Instant::now(),
         };
-        assert!(pet.feed().is_err());
+        assert_eq!(pet.feed(), Err(ActionError::Asleep));
+    }
+
+    #[test]
+    fn tired_pet_refuses_to_play() {
+        let mut pet = Pet::new("Test", Genome::default());
+        pet.stage = LifeStage::Adult;
+        pet.stats.energy = StatValue::new(10);
+
+        let err = pet.play().unwrap_err();
+        assert_eq!(err, ActionError::TooTired { energy: 10 });
+        assert_eq!(err.suggestion(), Some(Action::Sleep));
+    }
+
+    #[test]
+    fn only_happy_rested_adults_match() {
+        let adult = |name: &str| {
+            let mut pet = Pet::new(name, Genome::new(Species::Bunny));
+            pet.stage = LifeStage::Adult;
+            pet.stats.happiness = StatValue::new(MATCH_HAPPINESS);
+            pet
+        };
+        let mut first = adult("Pip");
+        let mut second = adult("Mochi");
+
+        second.stage = LifeStage::Teen;
+        let err = first.match_with(&mut second, "Bean").unwrap_err();
+        assert_eq!(
+            err,
+            ActionError::NotAdult {
+                name: "Mochi".into()
+            }
+        );
+
+        second.stage = LifeStage::Adult;
+        second.stats.happiness = StatValue::new(MATCH_HAPPINESS - 1);
+        assert!(matches!(
+            first.match_with(&mut second, "Bean"),
+            Err(ActionError::Unhappy { .. })
+        ));
+
+        second.stats.happiness = StatValue::new(90);
+        let egg = first.match_with(&mut second, "Bean").unwrap();
+        assert_eq!(egg.stage, LifeStage::Egg);
+        assert_eq!(egg.name, "Bean");
+        assert!(matches!(
+            first.check_can_match(),
+            Err(ActionError::MatchCooldown { .. })
+        ));
     }
 }
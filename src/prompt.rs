@@ -0,0 +1,203 @@
+//! Tiny status line for shell prompts and tmux
+//!
+//! The running game rewrites `status.json` in the data directory after every
+//! decay step. `mypet-tui prompt` only reads that file and fills in a
+//! template, so it is cheap enough to run on every prompt:
+//!
+//! ```sh
+//! PS1='$(mypet-tui prompt) \$ '
+//! set -g status-right '#(mypet-tui prompt --format "{face} {hunger}")'
+//! ```
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::glyphs::GlyphMode;
+use crate::paths;
+use crate::pet::{LifeStage, Pet, PetState};
+
+/// Template used without `--format`
+pub const DEFAULT_FORMAT: &str = "{alert}{face} {warmth} {hunger} {happiness}";
+/// Stats below this are flagged by `{alert}`
+pub const CRITICAL: u8 = 20;
+/// Egg warmth or health below this is flagged by `{alert}`
+const EGG_CRITICAL: u8 = 30;
+/// File name of the cache inside the data directory
+const CACHE_FILE: &str = "status.json";
+
+/// Where the running game leaves the status
+pub fn cache_path() -> PathBuf {
+    paths::data_dir().join(CACHE_FILE)
+}
+
+/// The few values a prompt shows, as cached on disk
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PromptStatus {
+    pub name: String,
+    pub face: String,
+    /// Whether anything needs attention right away
+    pub critical: bool,
+    /// Stats of a hatched pet: hunger, happiness, energy, health, hygiene
+    pub stats: Option<[u8; 5]>,
+    /// Warmth of an egg
+    pub warmth: Option<u8>,
+}
+
+impl PromptStatus {
+    /// Status of a pet as it is now
+    pub fn new(pet: &Pet) -> Self {
+        let stats = &pet.stats;
+        let values = [
+            stats.hunger.value(),
+            stats.happiness.value(),
+            stats.energy.value(),
+            stats.health.value(),
+            stats.hygiene.value(),
+        ];
+        let hatched = pet.stage != LifeStage::Egg;
+        let critical = match pet.state {
+            PetState::Sick { .. } | PetState::Dead => true,
+            _ if hatched => values.iter().any(|value| *value < CRITICAL),
+            _ => pet.get_warmth() < EGG_CRITICAL || pet.get_egg_health() < EGG_CRITICAL,
+        };
+
+        Self {
+            name: pet.name.clone(),
+            face: face(pet).to_string(),
+            critical,
+            stats: hatched.then_some(values),
+            warmth: (!hatched).then(|| pet.get_warmth()),
+        }
+    }
+
+    /// Fill in `{name}`, `{face}`, `{alert}`, `{hunger}`, `{happiness}`,
+    /// `{energy}`, `{health}`, `{hygiene}` and `{warmth}`
+    ///
+    /// Values that don't apply (stats of an egg, warmth of a hatched pet)
+    /// are left out along with the space around them.
+    pub fn render(&self, format: &str, glyphs: GlyphMode) -> String {
+        let icons = match glyphs {
+            GlyphMode::Unicode => ["⚠", "🍖", "♥", "⚡", "✚", "🛁", "🔥"],
+            GlyphMode::Ascii => ["!", "H", "<3", "E", "+", "~", "W"],
+        };
+        let stat = |index: usize| {
+            self.stats
+                .map(|stats| format!("{}{}", icons[index + 1], stats[index]))
+                .unwrap_or_default()
+        };
+
+        let replacements = [
+            ("{name}", self.name.clone()),
+            ("{face}", self.face.clone()),
+            (
+                "{alert}",
+                if self.critical {
+                    icons[0].to_string()
+                } else {
+                    String::new()
+                },
+            ),
+            ("{hunger}", stat(0)),
+            ("{happiness}", stat(1)),
+            ("{energy}", stat(2)),
+            ("{health}", stat(3)),
+            ("{hygiene}", stat(4)),
+            (
+                "{warmth}",
+                self.warmth
+                    .map(|warmth| format!("{}{}", icons[6], warmth))
+                    .unwrap_or_default(),
+            ),
+        ];
+
+        let mut line = format.to_string();
+        for (placeholder, value) in replacements {
+            line = line.replace(placeholder, &value);
+        }
+        line.split(' ')
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Write the status so prompts can pick it up
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        // Replace the file in one go so a prompt never reads half of it
+        let temp = path.with_extension("json.tmp");
+        fs::write(&temp, serde_json::to_vec(self)?)?;
+        fs::rename(&temp, path)
+    }
+
+    /// Read the cached status
+    pub fn read(path: &Path) -> io::Result<Self> {
+        Ok(serde_json::from_slice(&fs::read(path)?)?)
+    }
+}
+
+/// Face matching the pet's state and mood
+fn face(pet: &Pet) -> &'static str {
+    let happiness = pet.stats.happiness.value();
+    match pet.state {
+        _ if pet.stage == LifeStage::Egg => "(...)",
+        PetState::Dead => "(x_x)",
+        PetState::Sick { .. } => "(@.@)",
+        PetState::Sleeping { .. } => "(-.-)",
+        PetState::Normal if happiness > 70 => "(^.^)",
+        PetState::Normal if happiness < 30 => "(;.;)",
+        PetState::Normal => "(o.o)",
+    }
+}
+
+/// Print the cached status, returning the exit code
+///
+/// Prints nothing when there is no cache yet, so prompts stay clean.
+pub fn run(format: Option<&str>, glyphs: GlyphMode) -> i32 {
+    match PromptStatus::read(&cache_path()) {
+        Ok(status) => {
+            println!(
+                "{}",
+                status.render(format.unwrap_or(DEFAULT_FORMAT), glyphs)
+            );
+            0
+        }
+        Err(_) => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::Genome;
+    use crate::stats::StatValue;
+
+    #[test]
+    fn renders_templates() {
+        let mut pet = Pet::new("Pip", Genome::default());
+        let egg = PromptStatus::new(&pet);
+        assert_eq!(egg.render(DEFAULT_FORMAT, GlyphMode::Ascii), "!(...) W20");
+
+        pet.stage = LifeStage::Adult;
+        pet.stats.hunger = StatValue::new(42);
+        pet.stats.happiness = StatValue::new(80);
+        let status = PromptStatus::new(&pet);
+        assert!(!status.critical);
+        assert_eq!(
+            status.render(DEFAULT_FORMAT, GlyphMode::Unicode),
+            "(^.^) 🍖42 ♥80"
+        );
+        assert_eq!(
+            status.render("{name}: {energy} {warmth}", GlyphMode::Ascii),
+            "Pip: E50"
+        );
+
+        pet.stats.hygiene = StatValue::new(5);
+        let status = PromptStatus::new(&pet);
+        assert_eq!(status.render("{alert}{face}", GlyphMode::Unicode), "⚠(^.^)");
+    }
+}
@@ -0,0 +1,222 @@
+//! Messages exchanged with the daemon
+//!
+//! Clients talk to `mypet-tui daemon` over a Unix socket, one JSON-RPC 2.0
+//! message per line:
+//!
+//! ```text
+//! -> {"jsonrpc":"2.0","id":1,"method":"get_state"}
+//! <- {"jsonrpc":"2.0","id":1,"result":{"game_state":"playing","pets":[...],...}}
+//! -> {"jsonrpc":"2.0","id":2,"method":"perform_action","params":{"action":"feed","pet":0}}
+//! <- {"jsonrpc":"2.0","id":2,"result":{"message":"You fed Pip"}}
+//! -> {"jsonrpc":"2.0","id":3,"method":"subscribe_events"}
+//! <- {"jsonrpc":"2.0","method":"event","params":{"timestamp":...,"message":...}}
+//! ```
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::app::GameState;
+use crate::difficulty::Difficulty;
+use crate::paths;
+use crate::pet::{Pet, Species};
+use crate::vacation::Vacation;
+
+/// File name of the socket inside the data directory
+const SOCKET_FILE: &str = "mypet.sock";
+
+/// Error code for an action the pet refused
+pub const REFUSED: i64 = 1;
+/// JSON-RPC error code for a line that isn't a request
+pub const PARSE_ERROR: i64 = -32700;
+/// JSON-RPC error code for an unknown method
+pub const METHOD_NOT_FOUND: i64 = -32601;
+/// JSON-RPC error code for missing or malformed parameters
+pub const INVALID_PARAMS: i64 = -32602;
+
+/// Where the daemon listens
+pub fn socket_path() -> PathBuf {
+    paths::data_dir().join(SOCKET_FILE)
+}
+
+/// A call from a client
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Request {
+    pub jsonrpc: String,
+    pub id: u64,
+    pub method: String,
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    pub params: Value,
+}
+
+impl Request {
+    pub fn new(id: u64, method: &str, params: Value) -> Self {
+        Self {
+            jsonrpc: "2.0".to_string(),
+            id,
+            method: method.to_string(),
+            params,
+        }
+    }
+}
+
+/// Anything the daemon sends: an answer to a request, or an event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Message {
+    pub jsonrpc: String,
+    /// The request answered; absent for notifications
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<RpcError>,
+    /// Notification name, `event` for journal entries
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub params: Option<Value>,
+}
+
+impl Message {
+    /// Successful answer to request `id`
+    pub fn result(id: u64, result: Value) -> Self {
+        Self {
+            id: Some(id),
+            result: Some(result),
+            ..Self::empty()
+        }
+    }
+
+    /// Failed answer to request `id`, or to a line that couldn't be read
+    pub fn error(id: Option<u64>, code: i64, message: impl Into<String>) -> Self {
+        Self {
+            id,
+            error: Some(RpcError {
+                code,
+                message: message.into(),
+            }),
+            ..Self::empty()
+        }
+    }
+
+    /// Message nobody asked for
+    pub fn notification(method: &str, params: Value) -> Self {
+        Self {
+            method: Some(method.to_string()),
+            params: Some(params),
+            ..Self::empty()
+        }
+    }
+
+    fn empty() -> Self {
+        Self {
+            jsonrpc: "2.0".to_string(),
+            id: None,
+            result: None,
+            error: None,
+            method: None,
+            params: None,
+        }
+    }
+}
+
+/// Why a request failed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+/// What clients can ask the daemon to do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RemoteAction {
+    Warm,
+    Feed,
+    Play,
+    Clean,
+    /// Put to bed, or wake up
+    Sleep,
+    Medicine,
+    Pat,
+    Pause,
+    Vacation,
+    Match,
+    /// Add a pet; needs `setup`
+    Adopt,
+    /// Start over after game over; needs `setup`
+    Restart,
+}
+
+/// Parameters of `perform_action`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionParams {
+    pub action: RemoteAction,
+    /// Pet to act on; the daemon's selection if absent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pet: Option<usize>,
+    /// The new pet, for `adopt` and `restart`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub setup: Option<SetupChoices>,
+}
+
+/// Choices made in a client's new-game wizard
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetupChoices {
+    pub name: String,
+    #[serde(default)]
+    pub species: Species,
+    #[serde(default)]
+    pub difficulty: Difficulty,
+}
+
+/// Result of `perform_action`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionResult {
+    pub message: String,
+}
+
+/// Result of `get_state`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteState {
+    pub game_state: GameState,
+    pub selected: usize,
+    pub pets: Vec<Pet>,
+    #[serde(default)]
+    pub vacation: Option<Vacation>,
+    /// Status line as the daemon shows it
+    pub status: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn messages_follow_json_rpc() {
+        let request: Request = serde_json::from_value(json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "perform_action",
+            "params": {"action": "feed", "pet": 1}
+        }))
+        .unwrap();
+        let params: ActionParams = serde_json::from_value(request.params).unwrap();
+        assert_eq!(params.action, RemoteAction::Feed);
+        assert_eq!(params.pet, Some(1));
+
+        let error = serde_json::to_value(Message::error(Some(2), REFUSED, "no")).unwrap();
+        assert_eq!(
+            error,
+            json!({"jsonrpc": "2.0", "id": 2, "error": {"code": 1, "message": "no"}})
+        );
+        let event = serde_json::to_value(Message::notification("event", json!({}))).unwrap();
+        assert_eq!(
+            event,
+            json!({"jsonrpc": "2.0", "method": "event", "params": {}})
+        );
+    }
+}
@@ -0,0 +1,319 @@
+//! The household on disk
+//!
+//! The game writes `state.json` in the data directory every minute and
+//! when it exits, and the one-shot commands read and rewrite it. Time spent away is replayed
+//! on load one decay interval at a time, with the pet-sitter stepping in
+//! for as long as a vacation was booked. A game saved while paused stays
+//! paused, and no time passes for it.
+//!
+//! Whatever runs the game holds a lock on `state.lock` next to the save, so
+//! commands and other copies of the game can't work from a stale copy.
+
+use std::fs::{self, File, TryLockError};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::household::Household;
+use crate::paths;
+use crate::pet::{DECAY_INTERVAL, Pet};
+use crate::vacation::Vacation;
+
+/// File name of the saved household inside the data directory
+const STATE_FILE: &str = "state.json";
+
+/// File locked while a game is running
+const LOCK_FILE: &str = "state.lock";
+
+/// How often a running game writes the household to disk
+pub const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Where the household is saved
+pub fn state_path() -> PathBuf {
+    paths::data_dir().join(STATE_FILE)
+}
+
+/// A claim on the saved game, let go when dropped or when the process ends
+#[derive(Debug)]
+pub struct SaveLock {
+    _file: File,
+}
+
+impl SaveLock {
+    /// Claim the saved game; `Ok(None)` while something else runs it
+    pub fn acquire() -> io::Result<Option<Self>> {
+        Self::acquire_at(&paths::data_dir().join(LOCK_FILE))
+    }
+
+    fn acquire_at(path: &Path) -> io::Result<Option<Self>> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let file = File::options()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(path)?;
+        match file.try_lock() {
+            Ok(()) => Ok(Some(Self { _file: file })),
+            Err(TryLockError::WouldBlock) => Ok(None),
+            Err(TryLockError::Error(err)) => Err(err),
+        }
+    }
+}
+
+/// Why a saved household couldn't be read or written
+#[derive(Debug, Error)]
+pub enum SaveError {
+    #[error("{0}")]
+    Io(#[from] io::Error),
+    #[error("{0}")]
+    Format(#[from] serde_json::Error),
+    /// The file parsed but holds no pets
+    #[error("no pets in the saved game")]
+    Empty,
+}
+
+/// Everything needed to pick a game back up
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveState {
+    /// When the state was written; time since then is replayed on load
+    pub saved_at: DateTime<Utc>,
+    /// Index of the pet actions apply to
+    #[serde(default)]
+    pub selected: usize,
+    /// Every pet in the household
+    pub pets: Vec<Pet>,
+    /// Pet-sitting booked when the game was saved
+    #[serde(default)]
+    pub vacation: Option<Vacation>,
+    /// Whether the game was paused, so no time passed while it was away
+    #[serde(default)]
+    pub paused: bool,
+}
+
+impl SaveState {
+    /// Capture a household as of now
+    pub fn new(household: &Household, vacation: Option<&Vacation>) -> Self {
+        Self {
+            saved_at: Utc::now(),
+            selected: household.selected_index(),
+            pets: household
+                .pets
+                .iter()
+                .map(|companion| companion.pet.clone())
+                .collect(),
+            vacation: vacation.cloned(),
+            paused: false,
+        }
+    }
+
+    /// Read a saved game; `Ok(None)` when there isn't one
+    pub fn load(path: &Path) -> Result<Option<Self>, SaveError> {
+        let json = match fs::read_to_string(path) {
+            Ok(json) => json,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        let state: Self = serde_json::from_str(&json)?;
+        if state.pets.is_empty() {
+            return Err(SaveError::Empty);
+        }
+        Ok(Some(state))
+    }
+
+    /// Write the game, replacing the file only once it is complete
+    pub fn write(&self, path: &Path) -> Result<(), SaveError> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let temp = path.with_extension("json.tmp");
+        fs::write(&temp, serde_json::to_string_pretty(self)?)?;
+        fs::rename(&temp, path)?;
+        Ok(())
+    }
+
+    /// Replay the time since saving, with the sitter covering any vacation;
+    /// a paused game just picks up where it stopped
+    pub fn catch_up(&mut self) {
+        let away = (Utc::now() - self.saved_at).to_std().unwrap_or_default();
+        if self.paused {
+            for pet in &mut self.pets {
+                pet.shift_clock(away);
+            }
+            self.saved_at = Utc::now();
+            return;
+        }
+        // How long ago the sitter left; zero while still booked
+        let sitter_gone = self
+            .vacation
+            .as_ref()
+            .map(|vacation| vacation.until.elapsed());
+
+        let steps = away.as_secs() / DECAY_INTERVAL.as_secs();
+        for step in 1..=steps {
+            let left = away.saturating_sub(DECAY_INTERVAL * step as u32);
+            for pet in &mut self.pets {
+                pet.advance();
+                if let (Some(vacation), Some(gone)) = (&self.vacation, sitter_gone)
+                    && left > gone
+                {
+                    vacation.care(pet);
+                }
+            }
+        }
+
+        if self.vacation.as_ref().is_some_and(Vacation::is_over) {
+            self.vacation = None;
+        }
+        self.saved_at = Utc::now();
+    }
+
+    /// The pets as a household, with the saved selection
+    pub fn household(&self) -> Household {
+        Household::from_pets(self.pets.iter().cloned(), self.selected)
+            .unwrap_or_else(|| Household::new(Pet::default()))
+    }
+}
+
+/// Serde adapter storing an [`Instant`] as the wall-clock time it stands for
+///
+/// Instants can't outlive the process; loading maps the time back onto the
+/// current clock, clamping to now if the clock can't reach that far back.
+pub mod wall_clock {
+    use super::*;
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(at: &Instant, serializer: S) -> Result<S::Ok, S::Error> {
+        to_utc(*at).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Instant, D::Error> {
+        DateTime::<Utc>::deserialize(deserializer).map(from_utc)
+    }
+
+    /// The same for optional instants
+    pub mod option {
+        use super::*;
+
+        pub fn serialize<S: Serializer>(
+            at: &Option<Instant>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            at.map(to_utc).serialize(serializer)
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<Instant>, D::Error> {
+            Option::<DateTime<Utc>>::deserialize(deserializer).map(|at| at.map(from_utc))
+        }
+    }
+
+    fn to_utc(at: Instant) -> DateTime<Utc> {
+        let now = Instant::now();
+        let offset = |duration| chrono::Duration::from_std(duration).unwrap_or_default();
+        if at >= now {
+            Utc::now() + offset(at - now)
+        } else {
+            Utc::now() - offset(now - at)
+        }
+    }
+
+    fn from_utc(at: DateTime<Utc>) -> Instant {
+        let now = Instant::now();
+        let offset = at - Utc::now();
+        match offset.to_std() {
+            Ok(ahead) => now + ahead,
+            Err(_) => (-offset)
+                .to_std()
+                .ok()
+                .and_then(|behind| now.checked_sub(behind))
+                .unwrap_or(now),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::Genome;
+    use crate::pet::{LifeStage, PetState};
+    use crate::stats::StatValue;
+    use std::time::Duration;
+
+    #[test]
+    fn round_trips_through_json() {
+        let mut pet = Pet::new("Pip", Genome::default());
+        pet.stage = LifeStage::Teen;
+        pet.stats.hunger = StatValue::new(42);
+        pet.state = PetState::Sleeping {
+            since: Instant::now() - Duration::from_secs(60),
+        };
+        let mut household = Household::new(pet);
+        household.add(Pet::new("Mochi", Genome::default()));
+        household.select(0);
+
+        let state = SaveState::new(&household, None);
+        let json = serde_json::to_string(&state).unwrap();
+        let loaded: SaveState = serde_json::from_str(&json).unwrap();
+        let household = loaded.household();
+
+        assert_eq!(household.len(), 2);
+        let pet = &household.selected().pet;
+        assert_eq!(pet.name, "Pip");
+        assert_eq!(pet.stage, LifeStage::Teen);
+        assert_eq!(pet.stats.hunger.value(), 42);
+        match pet.state {
+            PetState::Sleeping { since } => {
+                assert!((59..=61).contains(&since.elapsed().as_secs()));
+            }
+            other => panic!("expected sleeping, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn catching_up_replays_time_away() {
+        let mut pet = Pet::new("Pip", Genome::default());
+        pet.stage = LifeStage::Adult;
+        pet.stats.hunger = StatValue::new(100);
+        let household = Household::new(pet);
+
+        let mut alone = SaveState::new(&household, None);
+        alone.saved_at -= chrono::Duration::hours(1);
+        alone.catch_up();
+        assert!(alone.pets[0].stats.hunger.value() < 50);
+
+        let vacation = Vacation::new(Duration::from_secs(60 * 60));
+        let mut sat = SaveState::new(&household, Some(&vacation));
+        sat.saved_at -= chrono::Duration::hours(1);
+        sat.catch_up();
+        assert!(sat.pets[0].stats.hunger.value() >= 20);
+        assert!(sat.vacation.is_some());
+
+        let mut paused = SaveState::new(&household, None);
+        paused.paused = true;
+        paused.saved_at -= chrono::Duration::hours(1);
+        paused.catch_up();
+        assert_eq!(paused.pets[0].stats.hunger.value(), 100);
+        assert_eq!(
+            paused.pets[0].age_seconds,
+            household.selected().pet.age_seconds
+        );
+    }
+
+    #[test]
+    fn one_game_at_a_time() {
+        let path = std::env::temp_dir().join(format!("mypet-lock-{}", std::process::id()));
+        let lock = SaveLock::acquire_at(&path).unwrap();
+        assert!(lock.is_some());
+        assert!(SaveLock::acquire_at(&path).unwrap().is_none());
+        drop(lock);
+        assert!(SaveLock::acquire_at(&path).unwrap().is_some());
+        let _ = fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,537 @@
+//! Scripts that add random events and look after pets
+//!
+//! Every `*.rhai` file in the `scripts` folder of the config directory runs
+//! once at startup, in name order. Scripts see a small API:
+//!
+//! ```rhai
+//! // Offered alongside the built-in random events
+//! register_event(#{
+//!     id: "pumpkin",
+//!     weight: 0.2,
+//!     condition: |pet| pet.stats.happiness > 40,
+//!     message: "{name} found a pumpkin!",
+//!     effects: #{ happiness: 10, hunger: 5 },
+//!     effect: |pet| emit(pet.name + " carves a face in it"),
+//! });
+//!
+//! // Called for each pet after every stat decay step
+//! fn on_update(pet) {
+//!     if pet.stage != "egg" && pet.stats.hunger < 20 {
+//!         feed();
+//!     }
+//! }
+//! ```
+//!
+//! `pet` is a read-only map shaped like `mypet-tui status --json`.
+//! `feed()`, `play()`, `clean()`, `sleep()`, `medicine()`, `warm()` and
+//! `pat()` act on that pet, and `emit(text)` writes to the journal. Only
+//! `id` and `message` are required when registering an event.
+//!
+//! Scripts can't reach files, the network or other programs, and each call
+//! is cut short after a fixed number of operations.
+
+use std::fs;
+use std::mem;
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use rhai::module_resolvers::DummyModuleResolver;
+use rhai::{AST, CallFnOptions, Dynamic, Engine, FnPtr, Map, Scope};
+
+use crate::commands::PetSummary;
+use crate::events::{self, EventType};
+use crate::paths;
+use crate::pet::Pet;
+use crate::rpc::RemoteAction;
+use crate::stats::Stats;
+
+/// Folder of scripts inside the config directory
+const SCRIPTS_DIR: &str = "scripts";
+/// Operations one call may take before it is stopped
+const MAX_OPERATIONS: u64 = 100_000;
+/// Function called after every stat decay step
+const ON_UPDATE: &str = "on_update";
+
+/// Something a script asked for while it ran
+#[derive(Debug, Clone)]
+enum Request {
+    Register(Map),
+    Emit(String),
+    Act(RemoteAction),
+}
+
+/// What scripts ask the game to do
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Order {
+    /// Write a line to the journal
+    Say(String),
+    /// Act on the pet at an index in the household
+    Act(usize, RemoteAction),
+}
+
+/// A random event registered by a script
+#[derive(Debug, Clone)]
+struct ScriptEvent {
+    event_type: EventType,
+    /// Index of the script that registered it
+    script: usize,
+    weight: f32,
+    condition: Option<FnPtr>,
+    /// What happened, with `{name}` standing for the pet's name
+    message: String,
+    /// Stat changes, by stat name
+    effects: Vec<(String, i64)>,
+    effect: Option<FnPtr>,
+}
+
+/// A loaded script
+#[derive(Debug)]
+struct Script {
+    name: String,
+    ast: AST,
+}
+
+/// Every loaded script and what they registered
+#[derive(Debug)]
+pub struct Scripts {
+    engine: Engine,
+    scripts: Vec<Script>,
+    events: Vec<ScriptEvent>,
+    /// Filled by the functions scripts call
+    requests: Arc<Mutex<Vec<Request>>>,
+    /// What scripts asked for, waiting for the game
+    pub orders: Vec<Order>,
+    /// Problems loading or running scripts, for the player to fix
+    pub warnings: Vec<String>,
+}
+
+impl Default for Scripts {
+    fn default() -> Self {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        Self {
+            engine: sandbox(&requests),
+            scripts: Vec::new(),
+            events: Vec::new(),
+            requests,
+            orders: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+}
+
+/// An engine with only the pet API and tight limits
+fn sandbox(requests: &Arc<Mutex<Vec<Request>>>) -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.set_max_call_levels(32);
+    engine.set_max_expr_depths(64, 32);
+    engine.set_max_string_size(4096);
+    engine.set_max_array_size(1024);
+    engine.set_max_map_size(256);
+    engine.disable_symbol("eval");
+    engine.set_module_resolver(DummyModuleResolver::new());
+    engine.on_print(|text| tracing::info!("script: {}", text));
+    engine.on_debug(|text, _, _| tracing::debug!("script: {}", text));
+
+    let push = |requests: &Arc<Mutex<Vec<Request>>>, request| lock(requests).push(request);
+    let queue = requests.clone();
+    engine.register_fn("register_event", move |event: Map| {
+        push(&queue, Request::Register(event))
+    });
+    let queue = requests.clone();
+    engine.register_fn("emit", move |text: &str| {
+        push(&queue, Request::Emit(text.to_string()))
+    });
+    let actions = [
+        ("feed", RemoteAction::Feed),
+        ("play", RemoteAction::Play),
+        ("clean", RemoteAction::Clean),
+        ("sleep", RemoteAction::Sleep),
+        ("medicine", RemoteAction::Medicine),
+        ("warm", RemoteAction::Warm),
+        ("pat", RemoteAction::Pat),
+    ];
+    for (name, action) in actions {
+        let queue = requests.clone();
+        engine.register_fn(name, move || push(&queue, Request::Act(action)));
+    }
+    engine
+}
+
+/// The requests, even if a script panicked while holding them
+fn lock(requests: &Mutex<Vec<Request>>) -> MutexGuard<'_, Vec<Request>> {
+    requests
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+impl Scripts {
+    /// Run the scripts in the config directory
+    pub fn load() -> Self {
+        let mut scripts = Self::default();
+        scripts.load_dir(&paths::config_dir().join(SCRIPTS_DIR));
+        scripts
+    }
+
+    fn load_dir(&mut self, dir: &Path) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+
+        let mut files: Vec<_> = entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "rhai"))
+            .collect();
+        files.sort();
+
+        for path in files {
+            let name = path
+                .file_name()
+                .and_then(|s| s.to_str())
+                .unwrap_or("script")
+                .to_string();
+            match fs::read_to_string(&path) {
+                Ok(source) => self.add(&name, &source),
+                Err(err) => {
+                    self.warnings
+                        .push(format!("could not read {}: {}", path.display(), err))
+                }
+            }
+        }
+    }
+
+    /// Compile a script and run it once
+    pub fn add(&mut self, name: &str, source: &str) {
+        let ast = match self.engine.compile(source) {
+            Ok(ast) => ast,
+            Err(err) => {
+                self.warnings.push(format!("{}: {}", name, err));
+                return;
+            }
+        };
+        let script = self.scripts.len();
+        if let Err(err) = self.engine.run_ast(&ast) {
+            self.warnings.push(format!("{}: {}", name, err));
+        }
+        self.scripts.push(Script {
+            name: name.to_string(),
+            ast,
+        });
+        self.take_requests(script, None);
+    }
+
+    /// Give every script with an `on_update` function a look at the pet
+    pub fn update(&mut self, index: usize, pet: &Pet) {
+        let wanted: Vec<usize> = (0..self.scripts.len())
+            .filter(|&script| {
+                self.scripts[script]
+                    .ast
+                    .iter_functions()
+                    .any(|function| function.name == ON_UPDATE && function.params.len() == 1)
+            })
+            .collect();
+        if wanted.is_empty() {
+            return;
+        }
+
+        let summary = summarize(pet);
+        for script in wanted {
+            let options = CallFnOptions::new().eval_ast(false);
+            let result = self.engine.call_fn_with_options::<Dynamic>(
+                options,
+                &mut Scope::new(),
+                &self.scripts[script].ast,
+                ON_UPDATE,
+                (summary.clone(),),
+            );
+            if let Err(err) = result {
+                self.report(script, &err.to_string());
+            }
+            self.take_requests(script, Some(index));
+        }
+    }
+
+    /// Registered events that could happen to the pet now, with their
+    /// weights
+    pub fn candidates(&mut self, index: usize, pet: &Pet) -> Vec<(EventType, f32)> {
+        if self.events.is_empty() {
+            return Vec::new();
+        }
+
+        let summary = summarize(pet);
+        let mut candidates = Vec::new();
+        for event in self.events.clone() {
+            let possible = match &event.condition {
+                None => true,
+                Some(condition) => {
+                    let ast = &self.scripts[event.script].ast;
+                    match condition.call::<bool>(&self.engine, ast, (summary.clone(),)) {
+                        Ok(possible) => possible,
+                        Err(err) => {
+                            self.report(event.script, &err.to_string());
+                            false
+                        }
+                    }
+                }
+            };
+            self.take_requests(event.script, Some(index));
+            if possible {
+                candidates.push((event.event_type, event.weight));
+            }
+        }
+        candidates
+    }
+
+    /// Apply a registered event to the pet, returning what happened;
+    /// `None` for events scripts didn't register
+    pub fn happen(
+        &mut self,
+        index: usize,
+        event_type: &EventType,
+        pet: &mut Pet,
+    ) -> Option<String> {
+        let event = self
+            .events
+            .iter()
+            .find(|event| event.event_type == *event_type)
+            .cloned()?;
+
+        for (stat, amount) in &event.effects {
+            if let Some(value) = pet.stats.get_mut(stat) {
+                value.change(*amount);
+            }
+        }
+        if let Some(effect) = &event.effect {
+            let ast = &self.scripts[event.script].ast;
+            if let Err(err) = effect.call::<Dynamic>(&self.engine, ast, (summarize(pet),)) {
+                self.report(event.script, &err.to_string());
+            }
+            self.take_requests(event.script, Some(index));
+        }
+        Some(event.message.replace("{name}", &pet.name))
+    }
+
+    /// Note a runtime error; these are logged rather than shown, since
+    /// they can repeat on every tick
+    fn report(&self, script: usize, error: &str) {
+        tracing::warn!("{}: {}", self.scripts[script].name, error);
+    }
+
+    /// Turn what a script asked for into registered events and orders;
+    /// actions need a pet to act on
+    fn take_requests(&mut self, script: usize, pet: Option<usize>) {
+        let requests = mem::take(&mut *lock(&self.requests));
+        for request in requests {
+            match (request, pet) {
+                (Request::Register(event), _) => self.register(script, event),
+                (Request::Emit(text), _) => self.orders.push(Order::Say(text)),
+                (Request::Act(action), Some(pet)) => self.orders.push(Order::Act(pet, action)),
+                (Request::Act(_), None) => self.warnings.push(format!(
+                    "{}: pets can only be looked after from on_update or an event",
+                    self.scripts[script].name
+                )),
+            }
+        }
+    }
+
+    /// Check and keep an event a script registered
+    fn register(&mut self, script: usize, mut event: Map) {
+        let name = &self.scripts[script].name;
+        let mut take = |key: &str| event.remove(key).filter(|value| !value.is_unit());
+
+        let Some(id) = take("id").and_then(|id| id.into_string().ok()) else {
+            self.warnings
+                .push(format!("{}: an event needs an 'id' string", name));
+            return;
+        };
+        let event_type = EventType::from(id.clone());
+        if !matches!(event_type, EventType::Custom(_))
+            || events::is_defined(&event_type)
+            || self
+                .events
+                .iter()
+                .any(|event| event.event_type == event_type)
+        {
+            self.warnings
+                .push(format!("{}: event '{}' already exists", name, id));
+            return;
+        }
+        let Some(message) = take("message").and_then(|message| message.into_string().ok()) else {
+            self.warnings
+                .push(format!("{}: event '{}' needs a 'message' string", name, id));
+            return;
+        };
+
+        let weight = match take("weight") {
+            None => 1.0,
+            Some(weight) => match weight
+                .as_float()
+                .or_else(|_| weight.as_int().map(|w| w as f64))
+            {
+                Ok(weight) if weight.is_finite() && weight > 0.0 => weight as f32,
+                _ => {
+                    self.warnings.push(format!(
+                        "{}: event '{}' needs a positive 'weight'",
+                        name, id
+                    ));
+                    return;
+                }
+            },
+        };
+
+        let mut function = |key: &str| match take(key) {
+            None => Ok(None),
+            Some(value) => value
+                .try_cast::<FnPtr>()
+                .map(Some)
+                .ok_or_else(|| format!("{}: '{}' of event '{}' must be a function", name, key, id)),
+        };
+        let (condition, effect) = match (function("condition"), function("effect")) {
+            (Ok(condition), Ok(effect)) => (condition, effect),
+            (Err(problem), _) | (_, Err(problem)) => {
+                self.warnings.push(problem);
+                return;
+            }
+        };
+
+        let mut effects = Vec::new();
+        if let Some(changes) = take("effects") {
+            let Some(changes) = changes.try_cast::<Map>() else {
+                self.warnings.push(format!(
+                    "{}: 'effects' of event '{}' must be a map of stat changes",
+                    name, id
+                ));
+                return;
+            };
+            for (stat, amount) in changes {
+                let known = Stats::NAMES.contains(&stat.as_str());
+                match amount.as_int() {
+                    Ok(amount) if known => effects.push((stat.to_string(), amount)),
+                    _ => {
+                        self.warnings.push(format!(
+                            "{}: event '{}' can't change '{}' (stats are {})",
+                            name,
+                            id,
+                            stat,
+                            Stats::NAMES.join(", ")
+                        ));
+                        return;
+                    }
+                }
+            }
+        }
+
+        self.events.push(ScriptEvent {
+            event_type,
+            script,
+            weight,
+            condition,
+            message,
+            effects,
+            effect,
+        });
+    }
+}
+
+/// The pet as scripts see it
+fn summarize(pet: &Pet) -> Dynamic {
+    rhai::serde::to_dynamic(PetSummary::new(pet)).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pet::LifeStage;
+
+    fn adult() -> Pet {
+        let mut pet = Pet::default();
+        pet.stage = LifeStage::Adult;
+        pet
+    }
+
+    #[test]
+    fn registers_events_and_reports_problems() {
+        let mut scripts = Scripts::default();
+        scripts.add(
+            "seasons.rhai",
+            r#"
+                let bonus = 10;
+                register_event(#{
+                    id: "pumpkin",
+                    weight: 0.5,
+                    condition: |pet| pet.stats.happiness > 40,
+                    message: "{name} found a pumpkin!",
+                    effects: #{ happiness: bonus, hunger: -5 },
+                    effect: |pet| { emit(pet.name + " carves it"); feed(); },
+                });
+                register_event(#{ id: "MadeMess", message: "again" });
+                register_event(#{ id: "odd", message: "odd", effects: #{ luck: 1 } });
+                feed();
+            "#,
+        );
+        scripts.add("broken.rhai", "register_event(");
+        assert_eq!(scripts.events.len(), 1);
+        assert_eq!(scripts.warnings.len(), 4, "{:?}", scripts.warnings);
+
+        // Modules would let scripts read files, so there are none
+        std::fs::write(std::env::temp_dir().join("mypet-module.rhai"), "").unwrap();
+        let path = std::env::temp_dir().join("mypet-module");
+        scripts.add(
+            "sneaky.rhai",
+            &format!(
+                r#"import "{}" as m; register_event(#{{ id: "sneaky", message: "hi" }});"#,
+                path.display()
+            ),
+        );
+        assert_eq!(scripts.events.len(), 1);
+        assert_eq!(scripts.warnings.len(), 5, "{:?}", scripts.warnings);
+
+        let mut pet = adult();
+        let pumpkin = EventType::Custom("pumpkin".to_string());
+        pet.stats.happiness.set(30);
+        assert!(scripts.candidates(0, &pet).is_empty());
+        pet.stats.happiness.set(50);
+        assert_eq!(scripts.candidates(0, &pet), [(pumpkin.clone(), 0.5)]);
+
+        let hunger = pet.stats.hunger.value();
+        let message = scripts.happen(1, &pumpkin, &mut pet);
+        assert_eq!(message, Some(format!("{} found a pumpkin!", pet.name)));
+        assert_eq!(pet.stats.happiness.value(), 60);
+        assert_eq!(pet.stats.hunger.value(), hunger - 5);
+        assert_eq!(
+            scripts.orders,
+            [
+                Order::Say(format!("{} carves it", pet.name)),
+                Order::Act(1, RemoteAction::Feed)
+            ]
+        );
+    }
+
+    #[test]
+    fn looks_after_pets_within_limits() {
+        let mut scripts = Scripts::default();
+        scripts.add(
+            "bot.rhai",
+            r#"
+                fn on_update(pet) {
+                    if pet.stats.hunger < 20 { feed(); }
+                    if pet.stats.hygiene < 20 { loop {} }
+                }
+            "#,
+        );
+        let mut pet = adult();
+        scripts.update(0, &pet);
+        assert!(scripts.orders.is_empty());
+
+        pet.stats.hunger.set(10);
+        scripts.update(2, &pet);
+        assert_eq!(scripts.orders, [Order::Act(2, RemoteAction::Feed)]);
+
+        // Runaway scripts are stopped, keeping what they asked for so far
+        scripts.orders.clear();
+        pet.stats.hygiene.set(10);
+        scripts.update(0, &pet);
+        assert_eq!(scripts.orders, [Order::Act(0, RemoteAction::Feed)]);
+    }
+}
@@ -0,0 +1,251 @@
+//! User-adjustable settings
+//!
+//! Whatever is changed in the settings view is written to `settings.json`
+//! in the data directory and applied again on the next start. The character
+//! set follows the terminal instead, and command-line flags and `NO_COLOR`
+//! only last for the run they were given to.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::glyphs::GlyphMode;
+use crate::i18n::{self, Locale, tr};
+use crate::paths;
+use crate::theme::{Theme, ThemeSet};
+
+/// Lengths offered for pet-sitter mode, in hours
+const VACATION_HOURS: [u64; 5] = [1, 2, 4, 8, 24];
+
+/// File name of the saved settings inside the data directory
+const SETTINGS_FILE: &str = "settings.json";
+
+/// Where changed settings are kept
+fn settings_path() -> PathBuf {
+    paths::data_dir().join(SETTINGS_FILE)
+}
+
+/// Settings that can be changed from the settings view
+#[derive(Debug, Clone)]
+pub struct Settings {
+    /// Whether the pet is animated
+    pub animations: bool,
+    /// Whether the main view shows the event log panel
+    pub show_event_log: bool,
+    /// Available color themes and the active one
+    pub themes: ThemeSet,
+    /// Whether the terminal gets Unicode or ASCII only
+    pub glyphs: GlyphMode,
+    /// How long pet-sitter mode lasts, in hours
+    pub vacation_hours: u64,
+}
+
+/// Settings as written to disk; only the ones the player changed are set
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct SavedSettings {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    language: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    theme: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    high_contrast: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    animations: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    show_event_log: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    vacation_hours: Option<u64>,
+}
+
+impl SavedSettings {
+    /// Read the settings file; empty when there isn't one yet
+    fn read(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(json) => {
+                serde_json::from_str(&json).map_err(|err| format!("{}: {}", path.display(), err))
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(format!("could not read {}: {}", path.display(), err)),
+        }
+    }
+}
+
+/// A single entry in the settings view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingItem {
+    Language,
+    Theme,
+    HighContrast,
+    Glyphs,
+    Animations,
+    EventLogPanel,
+    VacationLength,
+}
+
+impl SettingItem {
+    /// All items in display order
+    pub const ALL: [SettingItem; 7] = [
+        SettingItem::Language,
+        SettingItem::Theme,
+        SettingItem::HighContrast,
+        SettingItem::Glyphs,
+        SettingItem::Animations,
+        SettingItem::EventLogPanel,
+        SettingItem::VacationLength,
+    ];
+
+    /// Label shown in the settings view
+    pub fn label(self) -> &'static str {
+        i18n::lookup(match self {
+            SettingItem::Language => "setting.language",
+            SettingItem::Theme => "setting.theme",
+            SettingItem::HighContrast => "setting.high_contrast",
+            SettingItem::Glyphs => "setting.glyphs",
+            SettingItem::Animations => "setting.animations",
+            SettingItem::EventLogPanel => "setting.event_log_panel",
+            SettingItem::VacationLength => "setting.vacation_length",
+        })
+    }
+}
+
+impl Settings {
+    /// Create settings with default values
+    pub fn new() -> Self {
+        Self {
+            animations: true,
+            show_event_log: true,
+            themes: ThemeSet::new(),
+            glyphs: GlyphMode::Unicode,
+            vacation_hours: VACATION_HOURS[2],
+        }
+    }
+
+    /// Apply the settings changed in earlier games
+    pub fn load(&mut self) -> Result<(), String> {
+        let saved = SavedSettings::read(&settings_path())?;
+        self.apply(&saved);
+        Ok(())
+    }
+
+    fn apply(&mut self, saved: &SavedSettings) {
+        if let Some(locale) = saved.language.as_deref().and_then(Locale::from_lang) {
+            i18n::set_locale(locale);
+        }
+        if let Some(theme) = &saved.theme {
+            self.themes.select(theme);
+        }
+        if let Some(on) = saved.high_contrast {
+            self.themes.set_high_contrast(on);
+        }
+        if let Some(on) = saved.animations {
+            self.animations = on;
+        }
+        if let Some(on) = saved.show_event_log {
+            self.show_event_log = on;
+        }
+        if let Some(hours) = saved.vacation_hours.filter(|h| VACATION_HOURS.contains(h)) {
+            self.vacation_hours = hours;
+        }
+    }
+
+    /// Keep an item's current value for the next game
+    pub fn remember(&self, item: SettingItem) -> io::Result<()> {
+        let path = settings_path();
+        // A broken file is replaced rather than kept forever
+        let mut saved = SavedSettings::read(&path).unwrap_or_default();
+        self.store(item, &mut saved);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(&saved)?)
+    }
+
+    fn store(&self, item: SettingItem, saved: &mut SavedSettings) {
+        match item {
+            SettingItem::Language => saved.language = Some(i18n::locale().code().to_string()),
+            SettingItem::Theme => saved.theme = Some(self.theme().name.clone()),
+            SettingItem::HighContrast => saved.high_contrast = Some(self.themes.high_contrast()),
+            SettingItem::Glyphs => {}
+            SettingItem::Animations => saved.animations = Some(self.animations),
+            SettingItem::EventLogPanel => saved.show_event_log = Some(self.show_event_log),
+            SettingItem::VacationLength => saved.vacation_hours = Some(self.vacation_hours),
+        }
+    }
+
+    /// The active color theme
+    pub fn theme(&self) -> &Theme {
+        self.themes.current()
+    }
+
+    /// Current value of an item, formatted for display
+    pub fn value_label(&self, item: SettingItem) -> String {
+        let on = match item {
+            SettingItem::Language => return i18n::locale().label().to_string(),
+            SettingItem::Theme => return self.theme().name.clone(),
+            SettingItem::HighContrast => self.themes.high_contrast(),
+            SettingItem::Glyphs => return self.glyphs.label().to_string(),
+            SettingItem::Animations => self.animations,
+            SettingItem::EventLogPanel => self.show_event_log,
+            SettingItem::VacationLength => {
+                return tr!("setting.hours", hours = self.vacation_hours);
+            }
+        };
+        i18n::lookup(if on { "setting.on" } else { "setting.off" }).to_string()
+    }
+
+    /// Change an item to its next value
+    pub fn toggle(&mut self, item: SettingItem) {
+        match item {
+            SettingItem::Language => i18n::set_locale(i18n::locale().next()),
+            SettingItem::Theme => self.themes.cycle(),
+            SettingItem::HighContrast => {
+                let on = self.themes.high_contrast();
+                self.themes.set_high_contrast(!on);
+            }
+            SettingItem::Glyphs => self.glyphs = self.glyphs.toggled(),
+            SettingItem::Animations => self.animations = !self.animations,
+            SettingItem::EventLogPanel => self.show_event_log = !self.show_event_log,
+            SettingItem::VacationLength => {
+                let index = VACATION_HOURS
+                    .iter()
+                    .position(|hours| *hours == self.vacation_hours)
+                    .map_or(0, |index| index + 1);
+                self.vacation_hours = VACATION_HOURS[index % VACATION_HOURS.len()];
+            }
+        }
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_changed_settings_are_kept() {
+        let mut settings = Settings::new();
+        settings.toggle(SettingItem::Theme);
+        settings.toggle(SettingItem::Animations);
+        settings.toggle(SettingItem::EventLogPanel);
+
+        let mut saved = SavedSettings::default();
+        settings.store(SettingItem::Theme, &mut saved);
+        settings.store(SettingItem::Animations, &mut saved);
+        let json = serde_json::to_string(&saved).unwrap();
+        assert_eq!(json, r#"{"theme":"dark","animations":false}"#);
+
+        let mut restored = Settings::new();
+        restored.apply(&serde_json::from_str(&json).unwrap());
+        assert_eq!(restored.theme().name, "dark");
+        assert!(!restored.animations);
+        assert!(restored.show_event_log);
+    }
+}
@@ -0,0 +1,255 @@
+//! New-game wizard: the pet's name, species and difficulty
+//!
+//! Shown on first launch and after game over. The choices are kept here
+//! until the last step is confirmed, then [`App::start_game`] builds the pet.
+//!
+//! [`App::start_game`]: crate::app::App::start_game
+
+use thiserror::Error;
+
+use crate::difficulty::Difficulty;
+use crate::events::random_float;
+use crate::genome::{Coat, Genome};
+use crate::i18n::{self, tr};
+use crate::pet::{Pet, Species};
+
+/// Longest name accepted, in characters
+pub const MAX_NAME_LEN: usize = 16;
+
+/// Names offered by the random name button
+const RANDOM_NAMES: [&str; 16] = [
+    "Biscuit", "Mochi", "Pip", "Noodle", "Pebble", "Tofu", "Waffles", "Ziggy", "Clover", "Pickle",
+    "Sprout", "Bean", "Maple", "Nugget", "Juniper", "Sushi",
+];
+
+/// Why a name was rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum NameError {
+    /// Nothing but whitespace
+    #[error("{}", i18n::lookup("setup.name_empty"))]
+    Empty,
+    /// Longer than [`MAX_NAME_LEN`]
+    #[error("{}", tr!("setup.name_too_long", max = MAX_NAME_LEN))]
+    TooLong,
+    /// Contains something other than letters, digits, spaces, `-` or `'`
+    #[error("{}", tr!("setup.name_invalid", char = .0))]
+    InvalidChar(char),
+}
+
+/// Whether a character may appear in a name
+pub fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, ' ' | '-' | '\'')
+}
+
+/// Check a name, returning it without surrounding whitespace
+pub fn validate_name(name: &str) -> Result<&str, NameError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(NameError::Empty);
+    }
+    if name.chars().count() > MAX_NAME_LEN {
+        return Err(NameError::TooLong);
+    }
+    if let Some(c) = name.chars().find(|c| !is_name_char(*c)) {
+        return Err(NameError::InvalidChar(c));
+    }
+    Ok(name)
+}
+
+/// A name picked at random
+pub fn random_name() -> &'static str {
+    let index = (random_float() * RANDOM_NAMES.len() as f32) as usize;
+    RANDOM_NAMES[index.min(RANDOM_NAMES.len() - 1)]
+}
+
+/// Page of the wizard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetupStep {
+    Name,
+    Species,
+    Difficulty,
+}
+
+impl SetupStep {
+    /// All steps, in order
+    pub const ALL: [SetupStep; 3] = [SetupStep::Name, SetupStep::Species, SetupStep::Difficulty];
+
+    /// Title of the step
+    pub fn title(self) -> &'static str {
+        i18n::lookup(match self {
+            SetupStep::Name => "setup.step_name",
+            SetupStep::Species => "setup.step_species",
+            SetupStep::Difficulty => "setup.step_difficulty",
+        })
+    }
+
+    /// 1-based position of the step
+    pub fn number(self) -> usize {
+        Self::ALL.iter().position(|step| *step == self).unwrap_or(0) + 1
+    }
+}
+
+/// Choices made so far in the wizard
+#[derive(Debug, Clone)]
+pub struct Setup {
+    /// Current page
+    pub step: SetupStep,
+    /// Name as typed
+    pub name: String,
+    /// Selected species
+    pub species: Species,
+    /// Selected difficulty
+    pub difficulty: Difficulty,
+    /// Problem with the typed name, shown until it is edited
+    pub error: Option<NameError>,
+    /// Whether the pet joins the household instead of starting a new game
+    pub adopting: bool,
+}
+
+impl Setup {
+    /// A blank wizard
+    pub fn new() -> Self {
+        Self {
+            step: SetupStep::Name,
+            name: String::new(),
+            species: Species::default(),
+            difficulty: Difficulty::default(),
+            error: None,
+            adopting: false,
+        }
+    }
+
+    /// A blank wizard for an extra pet, which can be cancelled
+    pub fn adopting() -> Self {
+        Self {
+            adopting: true,
+            ..Self::new()
+        }
+    }
+
+    /// A wizard pre-filled with the choices behind a previous pet
+    pub fn after(pet: &Pet) -> Self {
+        Self {
+            name: pet.name.clone(),
+            species: pet.species(),
+            difficulty: pet.difficulty,
+            ..Self::new()
+        }
+    }
+
+    /// Type a character into the name, ignoring ones names can't contain
+    pub fn push_char(&mut self, c: char) {
+        if is_name_char(c) && self.name.chars().count() < MAX_NAME_LEN {
+            self.name.push(c);
+            self.error = None;
+        }
+    }
+
+    /// Delete the last character of the name
+    pub fn pop_char(&mut self) {
+        self.name.pop();
+        self.error = None;
+    }
+
+    /// Replace the name with a random one
+    pub fn randomize_name(&mut self) {
+        self.name = random_name().to_string();
+        self.error = None;
+    }
+
+    /// Move the selection on the current step
+    pub fn cycle(&mut self, forward: bool) {
+        match self.step {
+            SetupStep::Name => {}
+            SetupStep::Species => self.species = cycle(&Species::ALL, self.species, forward),
+            SetupStep::Difficulty => {
+                self.difficulty = cycle(&Difficulty::ALL, self.difficulty, forward)
+            }
+        }
+    }
+
+    /// Accept the current step; `Ok(true)` once every step is done
+    pub fn confirm(&mut self) -> Result<bool, NameError> {
+        match self.step {
+            SetupStep::Name => {
+                let name = validate_name(&self.name).inspect_err(|err| self.error = Some(*err))?;
+                self.name = name.to_string();
+                self.step = SetupStep::Species;
+            }
+            SetupStep::Species => self.step = SetupStep::Difficulty,
+            SetupStep::Difficulty => return Ok(true),
+        }
+        Ok(false)
+    }
+
+    /// Go back a step; the first step stays put
+    pub fn back(&mut self) {
+        self.step = match self.step {
+            SetupStep::Name | SetupStep::Species => SetupStep::Name,
+            SetupStep::Difficulty => SetupStep::Species,
+        };
+    }
+
+    /// The egg the choices describe
+    pub fn pet(&self) -> Pet {
+        let genome = Genome::new(self.species).with_coat(Coat::random());
+        Pet::new(self.name.trim(), genome).with_difficulty(self.difficulty)
+    }
+}
+
+impl Default for Setup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The item before or after `current` in `all`, wrapping around
+fn cycle<T: Copy + PartialEq>(all: &[T], current: T, forward: bool) -> T {
+    let index = all.iter().position(|item| *item == current).unwrap_or(0);
+    let next = if forward {
+        index + 1
+    } else {
+        index + all.len() - 1
+    };
+    all[next % all.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_names() {
+        assert_eq!(validate_name("  Pip "), Ok("Pip"));
+        assert_eq!(validate_name("Mr. Pip"), Err(NameError::InvalidChar('.')));
+        assert_eq!(validate_name("   "), Err(NameError::Empty));
+        assert_eq!(validate_name(&"a".repeat(17)), Err(NameError::TooLong));
+        assert!(validate_name(random_name()).is_ok());
+    }
+
+    #[test]
+    fn wizard_walks_through_every_step() {
+        let mut setup = Setup::new();
+        assert_eq!(setup.confirm(), Err(NameError::Empty));
+        assert_eq!(setup.step, SetupStep::Name);
+
+        "Pip!".chars().for_each(|c| setup.push_char(c));
+        assert_eq!(setup.name, "Pip");
+        assert_eq!(setup.confirm(), Ok(false));
+
+        setup.cycle(false);
+        assert_eq!(setup.species, Species::Bear);
+        assert_eq!(setup.confirm(), Ok(false));
+        setup.back();
+        assert_eq!(setup.step, SetupStep::Species);
+        setup.confirm().unwrap();
+
+        setup.cycle(true);
+        assert_eq!(setup.confirm(), Ok(true));
+
+        let pet = setup.pet();
+        assert_eq!(pet.name, "Pip");
+        assert_eq!(pet.species(), Species::Bear);
+        assert_eq!(pet.difficulty, Difficulty::Hard);
+    }
+}
@@ -1,7 +1,12 @@
 //! Stats system with bounded values
 
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
 /// A bounded value that clamps between MIN and MAX
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(from = "u8", into = "u8")]
 pub struct StatValue {
     value: u8,
 }
@@ -35,10 +40,21 @@ impl StatValue {
     }
 
     /// Set the value directly, clamped to range
+    #[allow(dead_code)]
     pub fn set(&mut self, value: u8) {
         self.value = value.clamp(Self::MIN, Self::MAX);
     }
 
+    /// Raise or lower the value by `amount`, clamping at the bounds
+    pub fn change(&mut self, amount: i64) {
+        let amount = amount.clamp(-(Self::MAX as i64), Self::MAX as i64);
+        if amount < 0 {
+            self.sub(amount.unsigned_abs() as u8);
+        } else {
+            self.add(amount as u8);
+        }
+    }
+
     /// Check if value is at maximum
     pub fn is_max(&self) -> bool {
         self.value == Self::MAX
@@ -50,11 +66,24 @@ impl StatValue {
     }
 
     /// Get value as percentage (0-100)
+    #[allow(dead_code)]
     pub fn percentage(&self) -> f32 {
         (self.value as f32 / Self::MAX as f32) * 100.0
     }
 }
 
+impl From<u8> for StatValue {
+    fn from(value: u8) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<StatValue> for u8 {
+    fn from(stat: StatValue) -> Self {
+        stat.value
+    }
+}
+
 impl Default for StatValue {
     fn default() -> Self {
         Self::new(50)
@@ -62,7 +91,7 @@ impl Default for StatValue {
 }
 
 /// All pet stats
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Stats {
     /// Hunger (0-100), 0 = starving, 100 = full
     pub hunger: StatValue,
@@ -74,6 +103,9 @@ pub struct Stats {
     pub health: StatValue,
     /// Hygiene (0-100), 0 = filthy, 100 = spotless
     pub hygiene: StatValue,
+    /// Decay owed but not applied yet, in hundredths of a point
+    #[serde(default)]
+    decay_progress: u16,
 }
 
 impl Stats {
@@ -85,6 +117,34 @@ impl Stats {
             energy: StatValue::new(50),
             health: StatValue::new(100),
             hygiene: StatValue::new(50),
+            decay_progress: 0,
+        }
+    }
+
+    /// Names of the stats, as scripts and data files call them
+    pub const NAMES: [&'static str; 5] = ["hunger", "happiness", "energy", "health", "hygiene"];
+
+    /// The value of a stat by its name
+    pub fn get(&self, name: &str) -> Option<u8> {
+        match name {
+            "hunger" => Some(self.hunger.value()),
+            "happiness" => Some(self.happiness.value()),
+            "energy" => Some(self.energy.value()),
+            "health" => Some(self.health.value()),
+            "hygiene" => Some(self.hygiene.value()),
+            _ => None,
+        }
+    }
+
+    /// A stat by its name
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut StatValue> {
+        match name {
+            "hunger" => Some(&mut self.hunger),
+            "happiness" => Some(&mut self.happiness),
+            "energy" => Some(&mut self.energy),
+            "health" => Some(&mut self.health),
+            "hygiene" => Some(&mut self.hygiene),
+            _ => None,
         }
     }
 
@@ -114,19 +174,26 @@ impl Stats {
     }
 
     /// Apply natural decay over time
-    pub fn decay(&mut self) {
+    ///
+    /// `percent` scales the loss; fractions carry over to later calls, so
+    /// 50 loses a point every other call and 150 alternates one and two.
+    pub fn decay(&mut self, percent: u16) {
+        self.decay_progress += percent;
+        let points = (self.decay_progress / 100) as u8;
+        self.decay_progress %= 100;
+
         // Hunger increases over time (pet gets hungrier)
-        self.hunger.sub(1);
+        self.hunger.sub(points);
         // Happiness slowly decreases without interaction
-        self.happiness.sub(1);
+        self.happiness.sub(points);
         // Energy slowly decreases
-        self.energy.sub(1);
+        self.energy.sub(points);
         // Hygiene decreases over time
-        self.hygiene.sub(1);
+        self.hygiene.sub(points);
 
         // Health is affected by other stats
         if self.is_starving() || self.is_depressed() || self.is_filthy() {
-            self.health.sub(1);
+            self.health.sub(points);
         }
     }
 }
@@ -137,6 +204,50 @@ impl Default for Stats {
     }
 }
 
+/// Rolling history of stat snapshots, oldest first
+#[derive(Debug, Clone)]
+pub struct StatHistory {
+    samples: VecDeque<Stats>,
+    capacity: usize,
+}
+
+impl StatHistory {
+    /// Create an empty history keeping at most `capacity` samples
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            samples: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Record a snapshot, dropping the oldest one when full
+    pub fn record(&mut self, stats: &Stats) {
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(stats.clone());
+    }
+
+    /// Check if nothing has been recorded yet
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Values of one stat over time, oldest first
+    pub fn series(&self, pick: impl Fn(&Stats) -> StatValue) -> Vec<u64> {
+        self.samples
+            .iter()
+            .map(|s| pick(s).value() as u64)
+            .collect()
+    }
+}
+
+impl Default for StatHistory {
+    fn default() -> Self {
+        Self::new(120)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -169,8 +280,34 @@ mod tests {
         let mut stats = Stats::new();
         let initial_hunger = stats.hunger.value();
 
-        stats.decay();
+        stats.decay(100);
 
         assert!(stats.hunger.value() < initial_hunger);
     }
+
+    #[test]
+    fn stats_decay_carries_fractions() {
+        let mut stats = Stats::new();
+
+        stats.decay(50);
+        assert_eq!(stats.hunger.value(), 50);
+        stats.decay(50);
+        assert_eq!(stats.hunger.value(), 49);
+        stats.decay(150);
+        stats.decay(150);
+        assert_eq!(stats.hunger.value(), 46);
+    }
+
+    #[test]
+    fn stat_history_drops_oldest_when_full() {
+        let mut history = StatHistory::new(2);
+        let mut stats = Stats::new();
+
+        for hunger in [10, 20, 30] {
+            stats.hunger = StatValue::new(hunger);
+            history.record(&stats);
+        }
+
+        assert_eq!(history.series(|s| s.hunger), vec![20, 30]);
+    }
 }
@@ -0,0 +1,653 @@
+//! Color themes
+//!
+//! Every styled element of the UI takes its color from the active [`Theme`].
+//! Besides the built-in themes, each `*.json` file in the `themes` directory
+//! of the config directory adds a theme named after the file. A theme file
+//! starts from a built-in theme and overrides single colors:
+//!
+//! ```json
+//! { "base": "dark", "header": "#ff8800", "pet_art": "light-green" }
+//! ```
+//!
+//! Colors are ANSI names (`red`, `light-blue`, `dark-gray`, `reset`),
+//! 256-color indexes (`"208"`) or hex values (`"#ff8800"`).
+//!
+//! Independent of the palette, high-contrast mode adds text indicators and
+//! bold/reverse attributes so that no state is shown by color alone. It is
+//! switched on by `--high-contrast` or the `NO_COLOR` environment variable,
+//! which also selects the colorless `no-color` theme.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use ratatui::style::{Color, Modifier};
+
+use crate::animation::frame::ArtColor;
+use crate::genome::Coat;
+use crate::i18n::tr;
+use crate::journal::JournalCategory;
+use crate::paths;
+
+/// Directory of user themes inside the config directory
+const THEMES_DIR: &str = "themes";
+
+/// Values at or below this are shown as low
+pub const LOW_LEVEL: u8 = 30;
+
+/// Colors for every styled element of the UI
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    pub name: String,
+    /// Show states with text and attributes, not just color
+    pub high_contrast: bool,
+    /// Menu bar text and border
+    pub header: Color,
+    /// Text of the selected menu tab
+    pub tab_selected_fg: Color,
+    /// Background of the selected menu tab
+    pub tab_selected_bg: Color,
+    /// Border of the pet panel
+    pub pet_border: Color,
+    /// Border of the stats panel
+    pub stats_border: Color,
+    /// Border of the event log panel on the main view
+    pub log_border: Color,
+    /// Border and title of the actions bar
+    pub actions_border: Color,
+    /// Border of the help overlay
+    pub help_border: Color,
+    /// Border of the pet info view
+    pub info_border: Color,
+    /// Border of the settings view
+    pub settings_border: Color,
+    /// Border of the stats history view
+    pub history_border: Color,
+    /// Regular text
+    pub text: Color,
+    /// Secondary text (event lists, hints)
+    pub text_muted: Color,
+    /// Least important text (timestamps)
+    pub text_dim: Color,
+    /// Focused input fields
+    pub highlight: Color,
+    /// Warnings, critical stats and game over
+    pub warning: Color,
+    pub hunger: Color,
+    pub happiness: Color,
+    pub energy: Color,
+    pub health: Color,
+    pub hygiene: Color,
+    pub incubation: Color,
+    /// Level-dependent gauges (egg warmth) when low, medium and high
+    pub level_low: Color,
+    pub level_mid: Color,
+    pub level_high: Color,
+    /// Unfilled part of every gauge
+    pub gauge_background: Color,
+    /// Journal categories in the event log view
+    pub log_event: Color,
+    pub log_action: Color,
+    pub log_state: Color,
+    pub log_system: Color,
+    /// The pet and the egg
+    pub pet_art: Color,
+    /// The pet while asleep
+    pub pet_sleeping: Color,
+    /// Effect particles
+    pub hearts: Color,
+    pub food: Color,
+    pub sparkles: Color,
+    pub zzz: Color,
+    pub sweat: Color,
+    /// Pets with an inherited coat; plain coats use `pet_art`
+    pub coat_ginger: Color,
+    pub coat_smoke: Color,
+    pub coat_midnight: Color,
+    pub coat_snow: Color,
+}
+
+impl Theme {
+    /// The original look of the game
+    pub fn default_theme() -> Self {
+        Self {
+            name: "default".to_string(),
+            high_contrast: false,
+            header: Color::Cyan,
+            tab_selected_fg: Color::Black,
+            tab_selected_bg: Color::Cyan,
+            pet_border: Color::Green,
+            stats_border: Color::Yellow,
+            log_border: Color::White,
+            actions_border: Color::Magenta,
+            help_border: Color::Yellow,
+            info_border: Color::Green,
+            settings_border: Color::Cyan,
+            history_border: Color::Yellow,
+            text: Color::White,
+            text_muted: Color::Gray,
+            text_dim: Color::DarkGray,
+            highlight: Color::Yellow,
+            warning: Color::Red,
+            hunger: Color::Red,
+            happiness: Color::Green,
+            energy: Color::Blue,
+            health: Color::Magenta,
+            hygiene: Color::Cyan,
+            incubation: Color::Green,
+            level_low: Color::Red,
+            level_mid: Color::Yellow,
+            level_high: Color::Green,
+            gauge_background: Color::Black,
+            log_event: Color::Yellow,
+            log_action: Color::Green,
+            log_state: Color::Cyan,
+            log_system: Color::Magenta,
+            pet_art: Color::White,
+            pet_sleeping: Color::DarkGray,
+            hearts: Color::LightRed,
+            food: Color::Yellow,
+            sparkles: Color::LightYellow,
+            zzz: Color::Gray,
+            sweat: Color::LightBlue,
+            coat_ginger: Color::LightRed,
+            coat_smoke: Color::Gray,
+            coat_midnight: Color::LightBlue,
+            coat_snow: Color::White,
+        }
+    }
+
+    /// Brighter colors for dark terminals
+    pub fn dark() -> Self {
+        Self {
+            name: "dark".to_string(),
+            header: Color::LightCyan,
+            tab_selected_bg: Color::LightCyan,
+            pet_border: Color::LightGreen,
+            stats_border: Color::LightYellow,
+            actions_border: Color::LightMagenta,
+            help_border: Color::LightYellow,
+            info_border: Color::LightGreen,
+            settings_border: Color::LightCyan,
+            history_border: Color::LightYellow,
+            highlight: Color::LightYellow,
+            warning: Color::LightRed,
+            hunger: Color::LightRed,
+            happiness: Color::LightGreen,
+            energy: Color::LightBlue,
+            health: Color::LightMagenta,
+            hygiene: Color::LightCyan,
+            incubation: Color::LightGreen,
+            level_low: Color::LightRed,
+            level_mid: Color::LightYellow,
+            level_high: Color::LightGreen,
+            gauge_background: Color::DarkGray,
+            log_event: Color::LightYellow,
+            log_action: Color::LightGreen,
+            log_state: Color::LightCyan,
+            log_system: Color::LightMagenta,
+            ..Self::default_theme()
+        }
+    }
+
+    /// Dark text and saturated colors for light terminal backgrounds
+    pub fn light_terminal() -> Self {
+        Self {
+            name: "light-terminal".to_string(),
+            header: Color::Blue,
+            tab_selected_fg: Color::White,
+            tab_selected_bg: Color::Blue,
+            pet_border: Color::Green,
+            stats_border: Color::Blue,
+            log_border: Color::Black,
+            actions_border: Color::Magenta,
+            help_border: Color::Blue,
+            info_border: Color::Green,
+            settings_border: Color::Blue,
+            history_border: Color::Blue,
+            text: Color::Black,
+            text_muted: Color::DarkGray,
+            text_dim: Color::Gray,
+            highlight: Color::Magenta,
+            warning: Color::Red,
+            level_mid: Color::Magenta,
+            gauge_background: Color::Gray,
+            log_event: Color::Blue,
+            log_state: Color::Cyan,
+            log_system: Color::Magenta,
+            pet_art: Color::Black,
+            pet_sleeping: Color::DarkGray,
+            hearts: Color::Red,
+            food: Color::Magenta,
+            sparkles: Color::Blue,
+            zzz: Color::DarkGray,
+            sweat: Color::Blue,
+            coat_ginger: Color::Red,
+            coat_smoke: Color::DarkGray,
+            coat_midnight: Color::Blue,
+            coat_snow: Color::Gray,
+            ..Self::default_theme()
+        }
+    }
+
+    /// Ethan Schoonover's Solarized (dark) palette
+    pub fn solarized() -> Self {
+        let base02 = Color::Rgb(0x07, 0x36, 0x42);
+        let base01 = Color::Rgb(0x58, 0x6e, 0x75);
+        let base0 = Color::Rgb(0x83, 0x94, 0x96);
+        let base1 = Color::Rgb(0x93, 0xa1, 0xa1);
+        let base2 = Color::Rgb(0xee, 0xe8, 0xd5);
+        let base03 = Color::Rgb(0x00, 0x2b, 0x36);
+        let yellow = Color::Rgb(0xb5, 0x89, 0x00);
+        let orange = Color::Rgb(0xcb, 0x4b, 0x16);
+        let red = Color::Rgb(0xdc, 0x32, 0x2f);
+        let magenta = Color::Rgb(0xd3, 0x36, 0x82);
+        let violet = Color::Rgb(0x6c, 0x71, 0xc4);
+        let blue = Color::Rgb(0x26, 0x8b, 0xd2);
+        let cyan = Color::Rgb(0x2a, 0xa1, 0x98);
+        let green = Color::Rgb(0x85, 0x99, 0x00);
+
+        Self {
+            name: "solarized".to_string(),
+            high_contrast: false,
+            header: blue,
+            tab_selected_fg: base03,
+            tab_selected_bg: blue,
+            pet_border: green,
+            stats_border: yellow,
+            log_border: base0,
+            actions_border: violet,
+            help_border: yellow,
+            info_border: green,
+            settings_border: cyan,
+            history_border: yellow,
+            text: base1,
+            text_muted: base0,
+            text_dim: base01,
+            highlight: orange,
+            warning: red,
+            hunger: orange,
+            happiness: green,
+            energy: blue,
+            health: magenta,
+            hygiene: cyan,
+            incubation: green,
+            level_low: red,
+            level_mid: yellow,
+            level_high: green,
+            gauge_background: base02,
+            log_event: yellow,
+            log_action: green,
+            log_state: cyan,
+            log_system: violet,
+            pet_art: base1,
+            pet_sleeping: base01,
+            hearts: magenta,
+            food: yellow,
+            sparkles: orange,
+            zzz: base01,
+            sweat: blue,
+            coat_ginger: orange,
+            coat_smoke: base0,
+            coat_midnight: violet,
+            coat_snow: base2,
+        }
+    }
+
+    /// Shades of gray only
+    pub fn monochrome() -> Self {
+        let mut theme = Self::default_theme();
+        theme.name = "monochrome".to_string();
+        for (_, color) in theme.colors_mut() {
+            *color = match *color {
+                Color::Black => Color::Black,
+                Color::DarkGray => Color::DarkGray,
+                Color::Gray => Color::Gray,
+                _ => Color::White,
+            };
+        }
+        theme
+    }
+
+    /// The terminal's own colors everywhere, for `NO_COLOR`
+    pub fn no_color() -> Self {
+        let mut theme = Self::default_theme();
+        theme.name = "no-color".to_string();
+        for (_, color) in theme.colors_mut() {
+            *color = Color::Reset;
+        }
+        theme
+    }
+
+    /// All built-in themes, in the order the settings view cycles through
+    pub fn builtin() -> Vec<Theme> {
+        vec![
+            Self::default_theme(),
+            Self::dark(),
+            Self::light_terminal(),
+            Self::solarized(),
+            Self::monochrome(),
+            Self::no_color(),
+        ]
+    }
+
+    /// Color of a semantic color used by the pet animations
+    pub fn art_color(&self, color: ArtColor) -> Color {
+        match color {
+            ArtColor::Sleeping => self.pet_sleeping,
+            ArtColor::Hearts => self.hearts,
+            ArtColor::Food => self.food,
+            ArtColor::Sparkles => self.sparkles,
+            ArtColor::Zzz => self.zzz,
+            ArtColor::Sweat => self.sweat,
+        }
+    }
+
+    /// Color of a pet with this coat
+    pub fn coat_color(&self, coat: Coat) -> Color {
+        match coat {
+            Coat::Plain => self.pet_art,
+            Coat::Ginger => self.coat_ginger,
+            Coat::Smoke => self.coat_smoke,
+            Coat::Midnight => self.coat_midnight,
+            Coat::Snow => self.coat_snow,
+        }
+    }
+
+    /// Color of a journal category
+    pub fn log_color(&self, category: JournalCategory) -> Color {
+        match category {
+            JournalCategory::Event => self.log_event,
+            JournalCategory::Action => self.log_action,
+            JournalCategory::State => self.log_state,
+            JournalCategory::System => self.log_system,
+        }
+    }
+
+    /// Gauge color for a value where low is bad
+    pub fn level_color(&self, value: u8) -> Color {
+        if value <= LOW_LEVEL {
+            self.level_low
+        } else if value <= 60 {
+            self.level_mid
+        } else {
+            self.level_high
+        }
+    }
+
+    /// Extra attributes for text that would otherwise stand out by color only
+    pub fn emphasis(&self) -> Modifier {
+        if self.high_contrast {
+            Modifier::BOLD
+        } else {
+            Modifier::empty()
+        }
+    }
+
+    /// Gauge title for a value where low is bad, marked in high-contrast mode
+    pub fn level_label(&self, label: &str, value: u8) -> String {
+        if self.high_contrast && value <= LOW_LEVEL {
+            tr!("stat.low", stat = label)
+        } else {
+            label.to_string()
+        }
+    }
+
+    /// Every color with the key used for it in theme files
+    fn colors_mut(&mut self) -> [(&'static str, &mut Color); 41] {
+        [
+            ("header", &mut self.header),
+            ("tab_selected_fg", &mut self.tab_selected_fg),
+            ("tab_selected_bg", &mut self.tab_selected_bg),
+            ("pet_border", &mut self.pet_border),
+            ("stats_border", &mut self.stats_border),
+            ("log_border", &mut self.log_border),
+            ("actions_border", &mut self.actions_border),
+            ("help_border", &mut self.help_border),
+            ("info_border", &mut self.info_border),
+            ("settings_border", &mut self.settings_border),
+            ("history_border", &mut self.history_border),
+            ("text", &mut self.text),
+            ("text_muted", &mut self.text_muted),
+            ("text_dim", &mut self.text_dim),
+            ("highlight", &mut self.highlight),
+            ("warning", &mut self.warning),
+            ("hunger", &mut self.hunger),
+            ("happiness", &mut self.happiness),
+            ("energy", &mut self.energy),
+            ("health", &mut self.health),
+            ("hygiene", &mut self.hygiene),
+            ("incubation", &mut self.incubation),
+            ("level_low", &mut self.level_low),
+            ("level_mid", &mut self.level_mid),
+            ("level_high", &mut self.level_high),
+            ("gauge_background", &mut self.gauge_background),
+            ("log_event", &mut self.log_event),
+            ("log_action", &mut self.log_action),
+            ("log_state", &mut self.log_state),
+            ("log_system", &mut self.log_system),
+            ("pet_art", &mut self.pet_art),
+            ("pet_sleeping", &mut self.pet_sleeping),
+            ("hearts", &mut self.hearts),
+            ("food", &mut self.food),
+            ("sparkles", &mut self.sparkles),
+            ("zzz", &mut self.zzz),
+            ("sweat", &mut self.sweat),
+            ("coat_ginger", &mut self.coat_ginger),
+            ("coat_smoke", &mut self.coat_smoke),
+            ("coat_midnight", &mut self.coat_midnight),
+            ("coat_snow", &mut self.coat_snow),
+        ]
+    }
+
+    /// Build a theme from the contents of a theme file
+    ///
+    /// Problems are pushed to `warnings`; the affected keys keep the color
+    /// of the base theme.
+    pub fn from_json(name: &str, json: &str, warnings: &mut Vec<String>) -> Self {
+        let entries = match serde_json::from_str::<BTreeMap<String, String>>(json) {
+            Ok(entries) => entries,
+            Err(err) => {
+                warnings.push(format!("theme '{}': invalid JSON: {}", name, err));
+                BTreeMap::new()
+            }
+        };
+
+        let mut theme = match entries.get("base") {
+            Some(base) => Self::builtin()
+                .into_iter()
+                .find(|theme| theme.name == *base)
+                .unwrap_or_else(|| {
+                    warnings.push(format!("theme '{}': unknown base theme '{}'", name, base));
+                    Self::default_theme()
+                }),
+            None => Self::default_theme(),
+        };
+        theme.name = name.to_string();
+
+        for (key, value) in entries.iter().filter(|(key, _)| *key != "base") {
+            let Some((_, color)) = theme.colors_mut().into_iter().find(|(k, _)| k == key) else {
+                warnings.push(format!("theme '{}': unknown key '{}'", name, key));
+                continue;
+            };
+            match Color::from_str(value) {
+                Ok(parsed) => *color = parsed,
+                Err(_) => warnings.push(format!("theme '{}': invalid color '{}'", name, value)),
+            }
+        }
+
+        theme
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::default_theme()
+    }
+}
+
+/// The themes available to the player and which one is active
+#[derive(Debug, Clone)]
+pub struct ThemeSet {
+    themes: Vec<Theme>,
+    selected: usize,
+    /// Problems found while loading user themes
+    pub warnings: Vec<String>,
+}
+
+impl ThemeSet {
+    /// Only the built-in themes, starting with the default one
+    pub fn new() -> Self {
+        Self {
+            themes: Theme::builtin(),
+            selected: 0,
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Built-in themes plus the user themes in the config directory
+    pub fn load() -> Self {
+        let mut set = Self::new();
+        set.load_dir(&paths::config_dir().join(THEMES_DIR));
+        set
+    }
+
+    fn load_dir(&mut self, dir: &Path) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+
+        let mut files: Vec<_> = entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect();
+        files.sort();
+
+        for path in files {
+            let name = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("custom")
+                .to_string();
+            match fs::read_to_string(&path) {
+                Ok(json) => {
+                    let theme = Theme::from_json(&name, &json, &mut self.warnings);
+                    // A user theme with a built-in name replaces it
+                    match self.themes.iter_mut().find(|t| t.name == name) {
+                        Some(existing) => *existing = theme,
+                        None => self.themes.push(theme),
+                    }
+                }
+                Err(err) => {
+                    self.warnings
+                        .push(format!("could not read {}: {}", path.display(), err))
+                }
+            }
+        }
+    }
+
+    /// The active theme
+    pub fn current(&self) -> &Theme {
+        &self.themes[self.selected]
+    }
+
+    /// Switch to the next theme, wrapping around
+    pub fn cycle(&mut self) {
+        self.selected = (self.selected + 1) % self.themes.len();
+    }
+
+    /// Switch to the theme with the given name, if there is one
+    pub fn select(&mut self, name: &str) -> bool {
+        match self.themes.iter().position(|theme| theme.name == name) {
+            Some(index) => {
+                self.selected = index;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Whether high-contrast mode is on
+    pub fn high_contrast(&self) -> bool {
+        self.current().high_contrast
+    }
+
+    /// Turn high-contrast mode on or off for every theme
+    pub fn set_high_contrast(&mut self, on: bool) {
+        for theme in &mut self.themes {
+            theme.high_contrast = on;
+        }
+    }
+}
+
+/// Whether the user asked for no colors (<https://no-color.org>)
+pub fn no_color_requested() -> bool {
+    env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
+impl Default for ThemeSet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn theme_file_overrides_base_theme() {
+        let mut warnings = Vec::new();
+        let theme = Theme::from_json(
+            "mine",
+            r##"{ "base": "solarized", "header": "#ff8800", "pet_art": "light-green" }"##,
+            &mut warnings,
+        );
+
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        assert_eq!(theme.name, "mine");
+        assert_eq!(theme.header, Color::Rgb(0xff, 0x88, 0x00));
+        assert_eq!(theme.pet_art, Color::LightGreen);
+        assert_eq!(theme.hygiene, Theme::solarized().hygiene);
+    }
+
+    #[test]
+    fn theme_file_problems_are_reported() {
+        let mut warnings = Vec::new();
+        let theme = Theme::from_json(
+            "broken",
+            r#"{ "base": "neon", "headr": "red", "text": "not-a-color" }"#,
+            &mut warnings,
+        );
+
+        assert_eq!(warnings.len(), 3);
+        assert_eq!(theme.text, Theme::default_theme().text);
+    }
+
+    #[test]
+    fn high_contrast_survives_theme_switching() {
+        let mut themes = ThemeSet::new();
+        themes.set_high_contrast(true);
+        assert!(themes.select("no-color"));
+        assert!(themes.high_contrast());
+        assert_eq!(themes.current().header, Color::Reset);
+
+        let theme = themes.current();
+        assert_eq!(theme.level_label("Warmth", 20), "! Warmth - LOW");
+        assert_eq!(theme.level_label("Warmth", 80), "Warmth");
+    }
+
+    #[test]
+    fn cycling_wraps_around() {
+        let mut themes = ThemeSet::new();
+        for _ in 0..Theme::builtin().len() - 1 {
+            themes.cycle();
+        }
+        assert_eq!(themes.current().name, "no-color");
+        themes.cycle();
+        assert_eq!(themes.current().name, "default");
+    }
+}
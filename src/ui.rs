@@ -1,315 +1,309 @@
 //! UI rendering module
 
 use ratatui::{
-    layout::{Alignment, Constraint, Direction, Layout},
-    style::{Color, Style},
-    widgets::{Block, Borders, Gauge, Paragraph, Wrap},
     Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Gauge, Paragraph, Tabs, Wrap},
 };
+use unicode_width::UnicodeWidthStr;
 
 use crate::app::{App, GameState};
-use crate::pet::LifeStage;
+use crate::glyphs::{self, GlyphMode};
+use crate::i18n::tr;
+use crate::keymap::Action;
+use crate::mouse::HitTarget;
+use crate::theme::Theme;
+use crate::views::{self, View};
+
+/// Smallest terminal anything but the "too small" message is drawn in
+pub const MIN_WIDTH: u16 = 40;
+pub const MIN_HEIGHT: u16 = 16;
+/// Smallest terminal the regular two-column layout fits in
+const NORMAL_WIDTH: u16 = 80;
+const NORMAL_HEIGHT: u16 = 30;
+/// Width from which a side panel is added
+const WIDE_WIDTH: u16 = 140;
+
+/// Layout breakpoint chosen from the terminal size
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayoutMode {
+    /// Below the minimum size: only a message is shown
+    TooSmall,
+    /// Single column with one-line gauges
+    Compact,
+    /// Pet and stats side by side
+    Normal,
+    /// Normal layout plus a stats history side panel
+    Wide,
+}
+
+impl LayoutMode {
+    /// Pick the layout for a terminal area
+    pub fn for_area(area: Rect) -> Self {
+        if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+            LayoutMode::TooSmall
+        } else if area.width < NORMAL_WIDTH || area.height < NORMAL_HEIGHT {
+            LayoutMode::Compact
+        } else if area.width >= WIDE_WIDTH {
+            LayoutMode::Wide
+        } else {
+            LayoutMode::Normal
+        }
+    }
+}
 
 /// Render the UI
 pub fn render(frame: &mut Frame, app: &App) {
-    let main_layout = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3),
-            Constraint::Min(10),
-            Constraint::Length(4),
-        ])
-        .split(frame.area());
-
-    // Header
-    let header = Block::default()
-        .title(" MyPet TUI - v0.1.0 ")
-        .title_alignment(Alignment::Center)
-        .borders(Borders::ALL)
-        .style(Style::default().fg(Color::Cyan));
-    frame.render_widget(header, main_layout[0]);
+    app.hit_map.borrow_mut().clear();
 
-    // Main content area
-    let content_layout = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-        .split(main_layout[1]);
+    let mode = LayoutMode::for_area(frame.area());
+    if mode == LayoutMode::TooSmall {
+        render_too_small(frame, app);
+        return;
+    }
 
-    // Left side: Pet and Event Log
-    let left_layout = Layout::default()
+    // Compact mode drops the header's border to save two lines
+    let header_height = if mode == LayoutMode::Compact { 1 } else { 3 };
+    let main_layout = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
-        .split(content_layout[0]);
-
-    // Pet display (top left)
-    render_pet(frame, app, left_layout[0]);
-
-    // Event log (bottom left)
-    render_event_log(frame, app, left_layout[1]);
+        .constraints([Constraint::Length(header_height), Constraint::Min(0)])
+        .split(frame.area());
 
-    // Stats panel (right side)
-    render_stats(frame, app, content_layout[1]);
+    render_menu_bar(frame, app, main_layout[0], mode != LayoutMode::Compact);
+    views::render(frame, app, main_layout[1]);
 
-    // Actions bar at bottom
-    render_actions(frame, app, main_layout[2]);
-}
-
-fn render_pet(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
-    let pet_block = Block::default()
-        .title(format!(
-            " {} - {} ",
-            app.pet.name,
-            app.pet.stage.display_name()
-        ))
-        .borders(Borders::ALL)
-        .style(Style::default().fg(Color::Green));
-
-    frame.render_widget(pet_block, area);
-
-    let inner = Layout::default()
-        .constraints([Constraint::Percentage(100)])
-        .margin(1)
-        .split(area)[0];
-
-    // Use animated pet for hatched stages, static art for egg
-    if app.pet.stage == LifeStage::Egg {
-        let art_color = Color::White;
-        let pet_art = Paragraph::new(app.pet.stage.ascii_art())
-            .alignment(Alignment::Center)
-            .style(Style::default().fg(art_color));
-        frame.render_widget(pet_art, inner);
-    } else {
-        frame.render_widget(&app.animated_pet, inner);
+    if app.game_state == GameState::Paused {
+        render_paused(frame, app);
     }
-}
-
-fn render_stats(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
-    let stats_block = Block::default()
-        .title(" Stats ")
-        .borders(Borders::ALL)
-        .style(Style::default().fg(Color::Yellow));
-
-    frame.render_widget(stats_block, area);
 
-    // Check if we're in Egg stage
-    if app.pet.stage == LifeStage::Egg {
-        render_egg_stats(frame, app, area);
-        return;
+    if app.settings.glyphs == GlyphMode::Ascii {
+        glyphs::asciify(frame.buffer_mut());
     }
+}
 
-    let inner = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(1), // Spacer
-            Constraint::Length(3), // Hunger
-            Constraint::Length(3), // Happiness
-            Constraint::Length(3), // Energy
-            Constraint::Length(3), // Health
-            Constraint::Length(3), // Hygiene
-            Constraint::Length(1), // Spacer
-            Constraint::Length(2), // Age
-            Constraint::Length(2), // Status
-            Constraint::Min(0),    // Remaining space
-        ])
-        .margin(1)
-        .split(area);
-
-    // Render stat bars
-    render_stat_bar(
-        frame,
-        "Hunger",
-        app.pet.stats.hunger.value(),
-        inner[1],
-        Color::Red,
+/// Dim everything and say how to resume
+fn render_paused(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+    frame
+        .buffer_mut()
+        .set_style(area, Style::default().add_modifier(Modifier::DIM));
+
+    let text = tr!("status.paused", keys = app.keymap.keys_label(Action::Pause));
+    let width = (text.width() as u16 + 4).min(area.width);
+    let popup = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + area.height.saturating_sub(3) / 2,
+        width,
+        3.min(area.height),
     );
-    render_stat_bar(
-        frame,
-        "Happiness",
-        app.pet.stats.happiness.value(),
-        inner[2],
-        Color::Green,
+    let message = Paragraph::new(text).alignment(Alignment::Center).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .style(Style::default().fg(app.settings.theme().warning)),
     );
-    render_stat_bar(
-        frame,
-        "Energy",
-        app.pet.stats.energy.value(),
-        inner[3],
-        Color::Blue,
-    );
-    render_stat_bar(
-        frame,
-        "Health",
-        app.pet.stats.health.value(),
-        inner[4],
-        Color::Magenta,
-    );
-    render_stat_bar(
-        frame,
-        "Hygiene",
-        app.pet.stats.hygiene.value(),
-        inner[5],
-        Color::Cyan,
-    );
-
-    // Age
-    let age_text = format!("Age: {}", app.pet.age_formatted());
-    let age = Paragraph::new(age_text).style(Style::default().fg(Color::White));
-    frame.render_widget(age, inner[7]);
-
-    // Status message
-    let status = Paragraph::new(app.status_message.as_str())
-        .style(Style::default().fg(Color::White))
-        .wrap(Wrap { trim: true });
-    frame.render_widget(status, inner[8]);
+    frame.render_widget(Clear, popup);
+    frame.render_widget(message, popup);
 }
 
-fn render_egg_stats(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
-    let warmth = app.pet.get_warmth();
-    let incubation = app.pet.get_incubation();
-    let health = app.pet.get_egg_health();
-
-    // Only show health if warmth is low
-    let show_health = warmth < 30;
-
-    let constraints = if show_health {
-        vec![
-            Constraint::Length(1), // Spacer
-            Constraint::Length(3), // Incubation
-            Constraint::Length(3), // Warmth
-            Constraint::Length(3), // Health (critical)
-            Constraint::Length(1), // Spacer
-            Constraint::Length(2), // Age
-            Constraint::Length(2), // Status
-            Constraint::Min(0),    // Remaining space
-        ]
-    } else {
-        vec![
-            Constraint::Length(1), // Spacer
-            Constraint::Length(3), // Incubation
-            Constraint::Length(3), // Warmth
-            Constraint::Length(1), // Spacer
-            Constraint::Length(2), // Age
-            Constraint::Length(2), // Status
-            Constraint::Min(0),    // Remaining space
-        ]
+/// Explain that the terminal needs to be bigger
+fn render_too_small(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+    let text = tr!(
+        "panel.too_small",
+        width = area.width,
+        height = area.height,
+        min_width = MIN_WIDTH,
+        min_height = MIN_HEIGHT,
+        keys = app.keymap.keys_label(Action::Quit)
+    );
+    let message = Paragraph::new(text)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .style(Style::default().fg(app.settings.theme().warning));
+
+    let top = area.height.saturating_sub(3) / 2;
+    let area = Rect {
+        y: area.y + top,
+        height: area.height - top,
+        ..area
     };
+    frame.render_widget(message, area);
+}
 
-    let inner = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints(constraints)
-        .margin(1)
-        .split(area);
+/// Header with a tab for each view in the menu
+fn render_menu_bar(frame: &mut Frame, app: &App, area: Rect, bordered: bool) {
+    let theme = app.settings.theme();
+    let header = if bordered {
+        let block = Block::default()
+            .title(" MyPet TUI - v0.1.0 ")
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL);
+        if app.game_state == GameState::Setup {
+            block
+        } else {
+            let mut status = app.pet().difficulty.label().to_string();
+            if let Some(vacation) = &app.vacation {
+                status = tr!(
+                    "header.vacation",
+                    difficulty = status,
+                    time = vacation.remaining_label()
+                );
+            }
+            block.title(Line::from(format!(" {} ", status)).right_aligned())
+        }
+    } else {
+        Block::default()
+    }
+    .style(Style::default().fg(theme.header));
+
+    let titles: Vec<String> = View::MENU
+        .iter()
+        .map(|view| {
+            format!(
+                "[{}] {}",
+                app.keymap.keys_label(view.open_action()),
+                view.title()
+            )
+        })
+        .collect();
+
+    // Mirror the Tabs layout (" title " separated by a divider) to record
+    // a clickable rectangle per tab
+    let inner = header.inner(area);
+    let mut x = inner.x + 1;
+    let mut tab_lines = Vec::new();
+    let mut hits = app.hit_map.borrow_mut();
+    for (view, title) in View::MENU.iter().zip(titles) {
+        let tab = Rect::new(x, inner.y, title.width() as u16, 1).intersection(inner);
+        hits.add(tab, HitTarget::Action(view.open_action()));
+        x += tab.width + 3;
+
+        let style = if app.is_hovered(tab) {
+            Style::default().add_modifier(Modifier::UNDERLINED)
+        } else {
+            Style::default()
+        };
+        tab_lines.push(Line::styled(title, style));
+    }
 
-    // Incubation progress bar
-    render_stat_bar(frame, "Incubation", incubation, inner[1], Color::Green);
+    let selected = View::MENU
+        .iter()
+        .position(|view| *view == app.navigation.base());
 
-    // Warmth bar (color changes based on level)
-    let warmth_color = match warmth {
-        0..=30 => Color::Red,
-        31..=60 => Color::Yellow,
-        _ => Color::Green,
+    // Reverse video marks the selected tab even without colors
+    let highlight = if theme.high_contrast {
+        Style::default().add_modifier(Modifier::REVERSED | Modifier::BOLD)
+    } else {
+        Style::default()
+            .fg(theme.tab_selected_fg)
+            .bg(theme.tab_selected_bg)
+            .add_modifier(Modifier::BOLD)
     };
-    render_stat_bar(frame, "Warmth", warmth, inner[2], warmth_color);
 
-    // Health (only if warmth is low)
-    if show_health {
-        render_stat_bar(frame, "⚠ Health", health, inner[3], Color::Red);
-    }
+    let tabs = Tabs::new(tab_lines)
+        .block(header)
+        .select(selected)
+        .style(Style::default().fg(theme.header))
+        .highlight_style(highlight);
 
-    // Age
-    let age_idx = if show_health { 5 } else { 4 };
-    let age_text = format!("Age: {}", app.pet.age_formatted());
-    let age = Paragraph::new(age_text).style(Style::default().fg(Color::White));
-    frame.render_widget(age, inner[age_idx]);
-
-    // Status message
-    let status_idx = if show_health { 6 } else { 5 };
-    let status = Paragraph::new(app.status_message.as_str())
-        .style(Style::default().fg(Color::White))
-        .wrap(Wrap { trim: true });
-    frame.render_widget(status, inner[status_idx]);
+    frame.render_widget(tabs, area);
 }
 
-fn render_stat_bar(
+/// Gauge for a need where low values are bad, flagged when low in
+/// high-contrast mode
+pub fn render_level_bar(
     frame: &mut Frame,
+    theme: &Theme,
     label: &str,
     value: u8,
-    area: ratatui::layout::Rect,
+    area: Rect,
+    color: Color,
+) {
+    let label = theme.level_label(label, value);
+    render_stat_bar(frame, theme, &label, value, area, color);
+}
+
+/// Labelled percentage gauge for a single stat
+pub fn render_stat_bar(
+    frame: &mut Frame,
+    theme: &Theme,
+    label: &str,
+    value: u8,
+    area: Rect,
     color: Color,
 ) {
     let gauge = Gauge::default()
         .block(Block::default().title(label).borders(Borders::NONE))
-        .gauge_style(Style::default().fg(color).bg(Color::Black))
+        .gauge_style(
+            Style::default()
+                .fg(color)
+                .bg(theme.gauge_background)
+                .add_modifier(theme.emphasis()),
+        )
         .percent(value as u16)
         .label(format!("{}%", value));
 
     frame.render_widget(gauge, area);
 }
 
-fn render_event_log(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
-    let event_block = Block::default()
-        .title(" Event Log ")
-        .borders(Borders::ALL)
-        .style(Style::default().fg(Color::White));
-
-    frame.render_widget(event_block, area);
-
-    let inner = Layout::default()
-        .constraints([Constraint::Percentage(100)])
-        .margin(1)
-        .split(area)[0];
-
-    // Get recent events
-    let recent_events = app.event_system.recent_events(5);
+/// One-line gauge with the label and value drawn on the bar
+pub fn render_compact_bar(
+    frame: &mut Frame,
+    theme: &Theme,
+    label: &str,
+    value: u8,
+    area: Rect,
+    color: Color,
+) {
+    let gauge = Gauge::default()
+        .gauge_style(
+            Style::default()
+                .fg(color)
+                .bg(theme.gauge_background)
+                .add_modifier(theme.emphasis()),
+        )
+        .percent(value as u16)
+        .label(format!("{} {}%", label, value));
 
-    let event_text = if recent_events.is_empty() {
-        "No events yet...".to_string()
-    } else {
-        recent_events
-            .iter()
-            .map(|e| format!("> {}", e.message))
-            .collect::<Vec<_>>()
-            .join("\n")
-    };
+    frame.render_widget(gauge, area);
+}
 
-    let events = Paragraph::new(event_text)
-        .style(Style::default().fg(Color::Gray))
-        .wrap(Wrap { trim: true });
+/// A rectangle of the given percentage size centered in `area`
+pub fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
 
-    frame.render_widget(events, inner);
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(vertical[1])[1]
 }
 
-fn render_actions(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
-    let actions_block = Block::default()
-        .title(" Actions ")
-        .borders(Borders::ALL)
-        .style(Style::default().fg(Color::Magenta));
-
-    frame.render_widget(actions_block, area);
-
-    let inner = Layout::default()
-        .constraints([Constraint::Percentage(100)])
-        .margin(1)
-        .split(area)[0];
-
-    // Check for game over state first
-    let actions_text = if app.game_state == GameState::GameOver {
-        Paragraph::new("[R]estart  [Q]uit")
-            .alignment(Alignment::Center)
-            .style(Style::default().fg(Color::Red))
-    } else if app.pet.stage == LifeStage::Egg {
-        Paragraph::new("[W]arm Egg  [Q]uit")
-            .alignment(Alignment::Center)
-            .style(Style::default().fg(Color::White))
-    } else if app.pet.stage == LifeStage::Baby {
-        Paragraph::new("[F]eed  [P]lay (Gentle)  [C]lean  [S]leep  [M]edicine  [Q]uit")
-            .alignment(Alignment::Center)
-            .style(Style::default().fg(Color::White))
-    } else {
-        Paragraph::new("[F]eed  [P]lay  [C]lean  [S]leep  [M]edicine  [Q]uit")
-            .alignment(Alignment::Center)
-            .style(Style::default().fg(Color::White))
-    };
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    frame.render_widget(actions_text, inner);
+    #[test]
+    fn layout_mode_breakpoints() {
+        let mode = |w, h| LayoutMode::for_area(Rect::new(0, 0, w, h));
+
+        assert_eq!(mode(30, 24), LayoutMode::TooSmall);
+        assert_eq!(mode(80, 10), LayoutMode::TooSmall);
+        assert_eq!(mode(80, 24), LayoutMode::Compact);
+        assert_eq!(mode(60, 40), LayoutMode::Compact);
+        assert_eq!(mode(100, 30), LayoutMode::Normal);
+        assert_eq!(mode(160, 40), LayoutMode::Wide);
+    }
 }
@@ -0,0 +1,155 @@
+//! Pet-sitter mode for when the player steps away
+//!
+//! Unlike pausing, time keeps running: for the declared period a sitter
+//! keeps the pet's needs from bottoming out. The sitter only tops needs up
+//! once they get low and never plays, and every visit costs a little
+//! happiness because the pet misses its owner.
+
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::tr;
+use crate::pet::{LifeStage, Pet, PetState};
+use crate::save::wall_clock;
+
+/// Needs below this get looked after
+const NEED_FLOOR: u8 = 30;
+/// Happiness below this earns a pat
+const HAPPINESS_FLOOR: u8 = 20;
+/// Egg warmth below this gets warmed
+const WARMTH_FLOOR: u8 = 40;
+/// How long the sitter lets the pet sleep
+const NAP_LENGTH: Duration = Duration::from_secs(30);
+/// Happiness lost each time the sitter steps in
+const MISSES_OWNER: u8 = 2;
+
+/// Something the sitter did
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SitterTask {
+    Warmed,
+    Fed,
+    Cleaned,
+    Medicine,
+    PutToBed,
+    Woke,
+    Patted,
+}
+
+impl SitterTask {
+    /// Journal message for the task
+    pub fn message(self, name: &str) -> String {
+        let key = match self {
+            SitterTask::Warmed => "sitter.warmed",
+            SitterTask::Fed => "sitter.fed",
+            SitterTask::Cleaned => "sitter.cleaned",
+            SitterTask::Medicine => "sitter.medicine",
+            SitterTask::PutToBed => "sitter.put_to_bed",
+            SitterTask::Woke => "sitter.woke",
+            SitterTask::Patted => "sitter.patted",
+        };
+        tr!(key, name = name)
+    }
+}
+
+/// A declared period of pet-sitting
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Vacation {
+    /// When the sitter leaves
+    #[serde(with = "wall_clock")]
+    pub until: Instant,
+}
+
+impl Vacation {
+    /// Start a vacation lasting `length`
+    pub fn new(length: Duration) -> Self {
+        Self {
+            until: Instant::now() + length,
+        }
+    }
+
+    /// Whether the declared period has passed
+    pub fn is_over(&self) -> bool {
+        Instant::now() >= self.until
+    }
+
+    /// Time left, formatted like `3h 05m`
+    pub fn remaining_label(&self) -> String {
+        let minutes = self
+            .until
+            .saturating_duration_since(Instant::now())
+            .as_secs()
+            / 60;
+        format!("{}h {:02}m", minutes / 60, minutes % 60)
+    }
+
+    /// Look after the pet once, returning what was done, if anything
+    pub fn care(&self, pet: &mut Pet) -> Option<SitterTask> {
+        let task = sitter_task(pet)?;
+        if task != SitterTask::Patted {
+            pet.stats.happiness.sub(MISSES_OWNER);
+        }
+        Some(task)
+    }
+}
+
+/// Do the most pressing job, if any
+fn sitter_task(pet: &mut Pet) -> Option<SitterTask> {
+    if pet.stage == LifeStage::Egg {
+        let cold = pet.get_warmth() < WARMTH_FLOOR;
+        return (cold && pet.warm().is_ok()).then_some(SitterTask::Warmed);
+    }
+
+    let stats = &pet.stats;
+    let (task, done) = match pet.state {
+        PetState::Dead => return None,
+        PetState::Sleeping { since } if since.elapsed() >= NAP_LENGTH => {
+            (SitterTask::Woke, pet.wake())
+        }
+        PetState::Sleeping { .. } => return None,
+        PetState::Sick { .. } if pet.difficulty.allows_medicine() => {
+            (SitterTask::Medicine, pet.give_medicine())
+        }
+        // Cleaning cures sickness too
+        PetState::Sick { .. } => (SitterTask::Cleaned, pet.clean()),
+        PetState::Normal if stats.hunger.value() < NEED_FLOOR => (SitterTask::Fed, pet.feed()),
+        PetState::Normal if stats.hygiene.value() < NEED_FLOOR => {
+            (SitterTask::Cleaned, pet.clean())
+        }
+        PetState::Normal if stats.energy.value() < NEED_FLOOR => {
+            (SitterTask::PutToBed, pet.sleep())
+        }
+        PetState::Normal if stats.happiness.value() < HAPPINESS_FLOOR => {
+            (SitterTask::Patted, pet.pat())
+        }
+        PetState::Normal => return None,
+    };
+    done.ok().map(|()| task)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::Genome;
+    use crate::stats::StatValue;
+
+    #[test]
+    fn sitter_tops_up_low_needs_at_a_cost() {
+        let vacation = Vacation::new(Duration::from_secs(3600));
+        let mut pet = Pet::new("Test", Genome::default());
+        pet.stage = LifeStage::Adult;
+        pet.stats.happiness = StatValue::new(50);
+
+        assert_eq!(vacation.care(&mut pet), None);
+
+        pet.stats.hunger = StatValue::new(10);
+        assert_eq!(vacation.care(&mut pet), Some(SitterTask::Fed));
+        assert!(pet.stats.hunger.value() >= NEED_FLOOR);
+        assert_eq!(pet.stats.happiness.value(), 48);
+
+        pet.stats.energy = StatValue::new(10);
+        assert_eq!(vacation.care(&mut pet), Some(SitterTask::PutToBed));
+        assert!(pet.state.is_sleeping());
+        assert_eq!(vacation.care(&mut pet), None);
+    }
+}
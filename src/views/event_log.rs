@@ -0,0 +1,149 @@
+//! Event log view: the whole journal, newest first, with filter and search
+
+use ratatui::{
+    Frame,
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
+};
+
+use crate::app::App;
+use crate::i18n;
+use crate::journal::JournalFilter;
+use crate::keymap::Action;
+use crate::mouse::HitTarget;
+
+/// Render the event log
+pub fn render(frame: &mut Frame, app: &App, area: Rect) {
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(3)])
+        .split(area);
+
+    let theme = app.settings.theme();
+    let filter = current_filter(app);
+    let nav = &app.navigation;
+    let entries: Vec<_> = app.journal.query(&filter, &nav.event_log_search).collect();
+
+    let items: Vec<ListItem> = if entries.is_empty() {
+        vec![ListItem::new(i18n::lookup("panel.no_matching_events"))]
+    } else {
+        entries
+            .iter()
+            .skip(nav.event_log_scroll)
+            .map(|entry| {
+                ListItem::new(Line::from(vec![
+                    Span::styled(entry.local_time(), Style::default().fg(theme.text_dim)),
+                    Span::raw(" "),
+                    Span::styled(
+                        format!("{:<6}", entry.category.label()),
+                        Style::default().fg(theme.log_color(entry.category)),
+                    ),
+                    Span::raw(" "),
+                    Span::raw(entry.message.clone()),
+                ]))
+            })
+            .collect()
+    };
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(format!(
+                    " Event Log - {} of {} - filter: {} - [{}] [{}] change filter ",
+                    entries.len(),
+                    app.journal.len(),
+                    filter.label(),
+                    app.keymap.keys_label(Action::Left),
+                    app.keymap.keys_label(Action::Right),
+                ))
+                .borders(Borders::ALL),
+        )
+        .style(Style::default().fg(theme.text_muted));
+
+    frame.render_widget(list, layout[0]);
+    app.hit_map.borrow_mut().add(layout[0], HitTarget::EventLog);
+
+    // Search box
+    let search_style = if nav.editing_search {
+        Style::default()
+            .fg(theme.highlight)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(theme.text_muted)
+    };
+    let cursor = if nav.editing_search { "_" } else { "" };
+    let search = Paragraph::new(format!("{}{}", nav.event_log_search, cursor)).block(
+        Block::default()
+            .title(format!(
+                " Search [{}] - [Enter] done, [Esc] clear ",
+                app.keymap.keys_label(Action::Search)
+            ))
+            .borders(Borders::ALL)
+            .style(search_style),
+    );
+    frame.render_widget(search, layout[1]);
+}
+
+/// Scroll, change filter and start searching
+pub fn handle_action(app: &mut App, action: Action) {
+    let filters = JournalFilter::all().len();
+    let nav = &mut app.navigation;
+
+    match action {
+        Action::Up => nav.scroll_event_log_up(),
+        Action::Down => {
+            let len = visible_count(app);
+            app.navigation.scroll_event_log_down(len);
+        }
+        Action::Left => {
+            nav.event_log_filter = (nav.event_log_filter + filters - 1) % filters;
+            nav.event_log_scroll = 0;
+        }
+        Action::Right => {
+            nav.event_log_filter = (nav.event_log_filter + 1) % filters;
+            nav.event_log_scroll = 0;
+        }
+        Action::Search => nav.editing_search = true,
+        _ => {}
+    }
+}
+
+/// Edit the search text while the search box is focused, returning whether
+/// the key was used
+pub fn handle_text_input(app: &mut App, key: KeyEvent) -> bool {
+    let nav = &mut app.navigation;
+
+    match key.code {
+        KeyCode::Enter => nav.editing_search = false,
+        KeyCode::Esc => {
+            nav.event_log_search.clear();
+            nav.editing_search = false;
+        }
+        KeyCode::Backspace => {
+            nav.event_log_search.pop();
+        }
+        KeyCode::Char(c) if super::types_char(&key) => nav.event_log_search.push(c),
+        _ => return false,
+    }
+
+    nav.event_log_scroll = 0;
+    true
+}
+
+/// Number of entries passing the current filter and search
+pub fn visible_count(app: &App) -> usize {
+    let filter = current_filter(app);
+    app.journal
+        .query(&filter, &app.navigation.event_log_search)
+        .count()
+}
+
+fn current_filter(app: &App) -> JournalFilter {
+    JournalFilter::all()
+        .into_iter()
+        .nth(app.navigation.event_log_filter)
+        .unwrap_or(JournalFilter::All)
+}
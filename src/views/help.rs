@@ -0,0 +1,58 @@
+//! Help overlay listing every key binding
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Clear, Row, Table},
+};
+
+use crate::app::App;
+use crate::keymap::Action;
+use crate::ui;
+
+/// Render the help overlay, generated from the active keymap
+pub fn render(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = app.settings.theme();
+    let popup = ui::centered_rect(60, 80, area);
+    frame.render_widget(Clear, popup);
+
+    let mut rows: Vec<Row> = Action::ALL
+        .iter()
+        .map(|action| {
+            Row::new([
+                app.keymap.keys_label(*action),
+                action.description().to_string(),
+            ])
+        })
+        .collect();
+
+    // Surface keymap problems where the player looks for key help
+    if !app.keymap.warnings.is_empty() {
+        rows.push(Row::new(["".to_string(), "".to_string()]));
+        let warning_style = Style::default().fg(theme.warning);
+        rows.extend(
+            app.keymap.warnings.iter().map(|warning| {
+                Row::new(["Keymap".to_string(), warning.clone()]).style(warning_style)
+            }),
+        );
+    }
+
+    let table = Table::new(rows, [Constraint::Length(14), Constraint::Min(10)])
+        .header(
+            Row::new(["Key", "Action"])
+                .style(Style::default().add_modifier(Modifier::BOLD))
+                .bottom_margin(1),
+        )
+        .block(
+            Block::default()
+                .title(format!(
+                    " Help - [{}] to close ",
+                    app.keymap.keys_label(Action::Help)
+                ))
+                .borders(Borders::ALL)
+                .style(Style::default().fg(theme.help_border)),
+        );
+
+    frame.render_widget(table, popup);
+}
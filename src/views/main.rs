@@ -0,0 +1,581 @@
+//! Main view: the pet, its stats, recent events and available actions
+
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::app::{App, GameState};
+use crate::i18n::{self, tr};
+use crate::keymap::Action;
+use crate::mouse::HitTarget;
+use crate::pet::{LifeStage, PetState};
+use crate::theme::LOW_LEVEL;
+use crate::ui::{self, LayoutMode};
+use crate::views::stats_history;
+
+/// Render the main view
+pub fn render(frame: &mut Frame, app: &App, area: Rect) {
+    let mode = LayoutMode::for_area(frame.area());
+
+    // A row listing every pet once there is more than one
+    let area = if app.household.len() > 1 {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0)])
+            .split(area);
+        render_household(frame, app, rows[0]);
+        rows[1]
+    } else {
+        area
+    };
+
+    if mode == LayoutMode::Compact {
+        render_compact(frame, app, area);
+        return;
+    }
+
+    let main_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(10), Constraint::Length(4)])
+        .split(area);
+
+    // Main content area; wide terminals get a history panel on the right
+    let columns: &[Constraint] = if mode == LayoutMode::Wide {
+        &[
+            Constraint::Percentage(40),
+            Constraint::Percentage(30),
+            Constraint::Percentage(30),
+        ]
+    } else {
+        &[Constraint::Percentage(50), Constraint::Percentage(50)]
+    };
+    let content_layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(columns)
+        .split(main_layout[0]);
+
+    if mode == LayoutMode::Wide {
+        stats_history::render(frame, app, content_layout[2]);
+    }
+
+    if app.settings.show_event_log {
+        // Left side: Pet and Event Log
+        let left_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+            .split(content_layout[0]);
+
+        // Pet display (top left)
+        render_pet(frame, app, left_layout[0]);
+
+        // Event log (bottom left)
+        render_event_log(frame, app, left_layout[1]);
+    } else {
+        render_pet(frame, app, content_layout[0]);
+    }
+
+    // Stats panel (right side)
+    render_stats(frame, app, content_layout[1]);
+
+    // Actions bar at bottom
+    render_actions(frame, app, main_layout[1]);
+}
+
+/// Single column for small terminals: pet, one-line gauges, actions
+fn render_compact(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = app.settings.theme();
+
+    // (label, value, color, whether low values are bad)
+    let bars: Vec<(String, u8, Color, bool)> = if app.pet().stage == LifeStage::Egg {
+        let warmth = app.pet().get_warmth();
+        let mut bars = vec![
+            (
+                tr!("stat.incubation"),
+                app.pet().get_incubation(),
+                theme.incubation,
+                false,
+            ),
+            (tr!("stat.warmth"), warmth, theme.level_color(warmth), true),
+        ];
+        if warmth < 30 {
+            bars.push((
+                format!("⚠ {}", tr!("stat.health")),
+                app.pet().get_egg_health(),
+                theme.warning,
+                true,
+            ));
+        }
+        bars
+    } else {
+        let stats = &app.pet().stats;
+        vec![
+            (tr!("stat.hunger"), stats.hunger.value(), theme.hunger, true),
+            (
+                tr!("stat.happiness"),
+                stats.happiness.value(),
+                theme.happiness,
+                true,
+            ),
+            (tr!("stat.energy"), stats.energy.value(), theme.energy, true),
+            (tr!("stat.health"), stats.health.value(), theme.health, true),
+            (
+                tr!("stat.hygiene"),
+                stats.hygiene.value(),
+                theme.hygiene,
+                true,
+            ),
+        ]
+    };
+
+    // One row per bar plus the status line, inside a border
+    let stats_height = bars.len() as u16 + 3;
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(3),
+            Constraint::Length(stats_height),
+            Constraint::Length(3),
+        ])
+        .split(area);
+
+    render_pet(frame, app, layout[0]);
+
+    let stats_block = Block::default()
+        .title(format!(
+            "{}- {} ",
+            i18n::lookup("panel.stats"),
+            tr!("stat.age", age = app.pet().age_formatted())
+        ))
+        .borders(Borders::ALL)
+        .style(Style::default().fg(theme.stats_border));
+    let inner = stats_block.inner(layout[1]);
+    frame.render_widget(stats_block, layout[1]);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![Constraint::Length(1); bars.len() + 1])
+        .split(inner);
+
+    for ((label, value, color, is_need), row) in bars.iter().zip(rows.iter()) {
+        let (value, color) = (*value, *color);
+        let label = if *is_need {
+            theme.level_label(label, value)
+        } else {
+            label.clone()
+        };
+        ui::render_compact_bar(frame, theme, &label, value, *row, color);
+    }
+
+    let status = Paragraph::new(app.status_line()).style(Style::default().fg(theme.text));
+    frame.render_widget(status, rows[bars.len()]);
+
+    render_actions(frame, app, layout[2]);
+}
+
+/// Handle pet care actions
+pub fn handle_action(app: &mut App, action: Action) {
+    match action {
+        Action::Restart => app.restart(),
+        Action::Warm => app.warm_egg(),
+        Action::Feed => app.feed_pet(),
+        Action::Play => app.play_with_pet(),
+        Action::Clean => app.clean_pet(),
+        Action::Sleep => app.toggle_sleep(),
+        Action::Medicine => app.give_medicine(),
+        _ => {}
+    }
+}
+
+/// One entry per pet with its most urgent need; click one to select it
+fn render_household(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = app.settings.theme();
+    let selected = app.household.selected_index();
+    let mut hits = app.hit_map.borrow_mut();
+    let mut spans = Vec::new();
+    let mut x = area.x;
+
+    for (index, companion) in app.household.pets.iter().enumerate() {
+        if index > 0 {
+            spans.push(Span::styled(" │ ", Style::default().fg(theme.text_dim)));
+            x += 3;
+        }
+
+        let (stat, value) = companion.most_urgent_need();
+        // Marked in words too, not only by color
+        let label = theme.level_label(i18n::lookup(stat), value);
+        let entry = format!("{} {} {}%", companion.pet.name, label, value);
+        let mut style = if value <= LOW_LEVEL {
+            Style::default()
+                .fg(theme.warning)
+                .add_modifier(theme.emphasis())
+        } else {
+            Style::default().fg(theme.text)
+        };
+        if index == selected {
+            style = style.add_modifier(Modifier::REVERSED | Modifier::BOLD);
+        }
+
+        let width = entry.width() as u16;
+        hits.add(
+            Rect::new(x, area.y, width, 1).intersection(area),
+            HitTarget::SelectPet(index),
+        );
+        x += width;
+        spans.push(Span::styled(entry, style));
+    }
+
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+fn render_pet(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = app.settings.theme();
+    // In high-contrast mode the state is spelled out instead of only
+    // being visible from the pet's colors
+    let state_tag = match app.pet().state {
+        PetState::Sleeping { .. } if theme.high_contrast => " [ASLEEP]",
+        PetState::Sick { .. } if theme.high_contrast => " [! SICK]",
+        PetState::Dead if theme.high_contrast => " [DEAD]",
+        _ => "",
+    };
+    let pet_block = Block::default()
+        .title(format!(
+            " {} - {}{} ",
+            app.pet().name,
+            app.pet().stage.display_name(),
+            state_tag
+        ))
+        .borders(Borders::ALL)
+        .style(Style::default().fg(theme.pet_border));
+
+    frame.render_widget(pet_block, area);
+
+    let inner = Layout::default()
+        .constraints([Constraint::Percentage(100)])
+        .margin(1)
+        .split(area)[0];
+
+    // Use animated pet for hatched stages, static art for egg
+    if app.pet().stage == LifeStage::Egg {
+        let pet_art = Paragraph::new(app.pet().stage.ascii_art())
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(theme.pet_art));
+        frame.render_widget(pet_art, inner);
+    } else {
+        frame.render_widget(app.companion().animated_pet.themed(theme), inner);
+    }
+
+    app.hit_map.borrow_mut().add(inner, HitTarget::Pet);
+}
+
+fn render_stats(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = app.settings.theme();
+    let stats_block = Block::default()
+        .title(i18n::lookup("panel.stats"))
+        .borders(Borders::ALL)
+        .style(Style::default().fg(theme.stats_border));
+
+    frame.render_widget(stats_block, area);
+
+    // Check if we're in Egg stage
+    if app.pet().stage == LifeStage::Egg {
+        render_egg_stats(frame, app, area);
+        return;
+    }
+
+    let inner = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Spacer
+            Constraint::Length(3), // Hunger
+            Constraint::Length(3), // Happiness
+            Constraint::Length(3), // Energy
+            Constraint::Length(3), // Health
+            Constraint::Length(3), // Hygiene
+            Constraint::Length(1), // Spacer
+            Constraint::Length(2), // Age
+            Constraint::Length(2), // Status
+            Constraint::Min(0),    // Remaining space
+        ])
+        .margin(1)
+        .split(area);
+
+    // Render stat bars
+    ui::render_level_bar(
+        frame,
+        theme,
+        i18n::lookup("stat.hunger"),
+        app.pet().stats.hunger.value(),
+        inner[1],
+        theme.hunger,
+    );
+    ui::render_level_bar(
+        frame,
+        theme,
+        i18n::lookup("stat.happiness"),
+        app.pet().stats.happiness.value(),
+        inner[2],
+        theme.happiness,
+    );
+    ui::render_level_bar(
+        frame,
+        theme,
+        i18n::lookup("stat.energy"),
+        app.pet().stats.energy.value(),
+        inner[3],
+        theme.energy,
+    );
+    ui::render_level_bar(
+        frame,
+        theme,
+        i18n::lookup("stat.health"),
+        app.pet().stats.health.value(),
+        inner[4],
+        theme.health,
+    );
+    ui::render_level_bar(
+        frame,
+        theme,
+        i18n::lookup("stat.hygiene"),
+        app.pet().stats.hygiene.value(),
+        inner[5],
+        theme.hygiene,
+    );
+
+    // Age
+    let age_text = tr!("stat.age", age = app.pet().age_formatted());
+    let age = Paragraph::new(age_text).style(Style::default().fg(theme.text));
+    frame.render_widget(age, inner[7]);
+
+    // Status message
+    let status = Paragraph::new(app.status_line())
+        .style(Style::default().fg(theme.text))
+        .wrap(Wrap { trim: true });
+    frame.render_widget(status, inner[8]);
+}
+
+fn render_egg_stats(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = app.settings.theme();
+    let warmth = app.pet().get_warmth();
+    let incubation = app.pet().get_incubation();
+    let health = app.pet().get_egg_health();
+
+    // Only show health if warmth is low
+    let show_health = warmth < 30;
+
+    let constraints = if show_health {
+        vec![
+            Constraint::Length(1), // Spacer
+            Constraint::Length(3), // Incubation
+            Constraint::Length(3), // Warmth
+            Constraint::Length(3), // Health (critical)
+            Constraint::Length(1), // Spacer
+            Constraint::Length(2), // Age
+            Constraint::Length(2), // Status
+            Constraint::Min(0),    // Remaining space
+        ]
+    } else {
+        vec![
+            Constraint::Length(1), // Spacer
+            Constraint::Length(3), // Incubation
+            Constraint::Length(3), // Warmth
+            Constraint::Length(1), // Spacer
+            Constraint::Length(2), // Age
+            Constraint::Length(2), // Status
+            Constraint::Min(0),    // Remaining space
+        ]
+    };
+
+    let inner = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(constraints)
+        .margin(1)
+        .split(area);
+
+    // Incubation progress bar
+    ui::render_stat_bar(
+        frame,
+        theme,
+        i18n::lookup("stat.incubation"),
+        incubation,
+        inner[1],
+        theme.incubation,
+    );
+
+    // Warmth bar (color changes based on level)
+    let warmth_color = theme.level_color(warmth);
+    ui::render_level_bar(
+        frame,
+        theme,
+        i18n::lookup("stat.warmth"),
+        warmth,
+        inner[2],
+        warmth_color,
+    );
+
+    // Health (only if warmth is low)
+    if show_health {
+        ui::render_level_bar(
+            frame,
+            theme,
+            &format!("⚠ {}", tr!("stat.health")),
+            health,
+            inner[3],
+            theme.warning,
+        );
+    }
+
+    // Age
+    let age_idx = if show_health { 5 } else { 4 };
+    let age_text = tr!("stat.age", age = app.pet().age_formatted());
+    let age = Paragraph::new(age_text).style(Style::default().fg(theme.text));
+    frame.render_widget(age, inner[age_idx]);
+
+    // Status message
+    let status_idx = if show_health { 6 } else { 5 };
+    let status = Paragraph::new(app.status_line())
+        .style(Style::default().fg(theme.text))
+        .wrap(Wrap { trim: true });
+    frame.render_widget(status, inner[status_idx]);
+}
+
+fn render_event_log(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = app.settings.theme();
+    let event_block = Block::default()
+        .title(i18n::lookup("panel.event_log"))
+        .borders(Borders::ALL)
+        .style(Style::default().fg(theme.log_border));
+
+    frame.render_widget(event_block, area);
+
+    let inner = Layout::default()
+        .constraints([Constraint::Percentage(100)])
+        .margin(1)
+        .split(area)[0];
+
+    // Get recent journal entries
+    let event_text = if app.journal.is_empty() {
+        tr!("panel.no_events")
+    } else {
+        app.journal
+            .recent(usize::MAX)
+            .skip(app.navigation.event_log_scroll)
+            .take(5)
+            .map(|e| format!("> {}", e.message))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let events = Paragraph::new(event_text)
+        .style(Style::default().fg(theme.text_muted))
+        .wrap(Wrap { trim: true });
+
+    frame.render_widget(events, inner);
+    app.hit_map.borrow_mut().add(area, HitTarget::EventLog);
+}
+
+fn render_actions(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = app.settings.theme();
+    let actions_block = Block::default()
+        .title(i18n::lookup("panel.actions"))
+        .borders(Borders::ALL)
+        .style(Style::default().fg(theme.actions_border));
+
+    frame.render_widget(actions_block, area);
+
+    let inner = Layout::default()
+        .constraints([Constraint::Percentage(100)])
+        .margin(1)
+        .split(area)[0];
+
+    // Check for game over state first
+    let (actions, color): (&[(Action, &str)], Color) = if app.game_state == GameState::GameOver {
+        (
+            &[
+                (Action::Restart, "button.restart"),
+                (Action::Quit, "button.quit"),
+            ],
+            theme.warning,
+        )
+    } else if app.pet().stage == LifeStage::Egg {
+        (
+            &[
+                (Action::Warm, "button.warm_egg"),
+                (Action::Quit, "button.quit"),
+            ],
+            theme.text,
+        )
+    } else if app.pet().stage == LifeStage::Baby {
+        (
+            &[
+                (Action::Feed, "button.feed"),
+                (Action::Play, "button.play_gentle"),
+                (Action::Clean, "button.clean"),
+                (Action::Sleep, "button.sleep"),
+                (Action::Medicine, "button.medicine"),
+                (Action::Quit, "button.quit"),
+            ],
+            theme.text,
+        )
+    } else {
+        (
+            &[
+                (Action::Feed, "button.feed"),
+                (Action::Play, "button.play"),
+                (Action::Clean, "button.clean"),
+                (Action::Sleep, "button.sleep"),
+                (Action::Medicine, "button.medicine"),
+                (Action::Quit, "button.quit"),
+            ],
+            theme.text,
+        )
+    };
+
+    // Labels come from the active keymap so rebinding keeps them correct
+    let hints: Vec<(Action, String)> = actions
+        .iter()
+        .filter(|(action, _)| *action != Action::Medicine || app.pet().difficulty.allows_medicine())
+        .map(|(action, label)| (*action, app.keymap.hint(*action, i18n::lookup(label))))
+        .filter(|(_, hint)| !hint.is_empty())
+        .collect();
+
+    // Lay the buttons out the same way a centered line would be, so each
+    // one gets its own clickable rectangle
+    let gap = 2;
+    let total_width = hints
+        .iter()
+        .map(|(_, hint)| hint.width() as u16)
+        .sum::<u16>()
+        + gap * hints.len().saturating_sub(1) as u16;
+    let mut x = inner.x + inner.width.saturating_sub(total_width) / 2;
+
+    let mut spans = Vec::new();
+    let mut hits = app.hit_map.borrow_mut();
+    for (i, (action, hint)) in hints.into_iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::raw(" ".repeat(gap as usize)));
+            x += gap;
+        }
+
+        let button = Rect::new(x, inner.y, hint.width() as u16, 1).intersection(inner);
+        hits.add(button, HitTarget::Action(action));
+        x += button.width;
+
+        let style = if app.is_hovered(button) {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default()
+        };
+        spans.push(Span::styled(hint, style));
+    }
+
+    let actions_text = Paragraph::new(Line::from(spans))
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(color).add_modifier(theme.emphasis()));
+
+    frame.render_widget(actions_text, inner);
+}
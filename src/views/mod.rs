@@ -0,0 +1,307 @@
+//! Screens of the game and navigation between them
+//!
+//! Each view module owns a `render` function and, where it reacts to input,
+//! a `handle_action` function. Global actions (quit, help, back, switching
+//! views) are resolved here before the current view sees them.
+
+pub mod event_log;
+pub mod help;
+pub mod main;
+pub mod pet_info;
+pub mod settings;
+pub mod setup;
+pub mod stats_history;
+
+use ratatui::{
+    Frame,
+    crossterm::event::{KeyEvent, KeyModifiers},
+    layout::Rect,
+};
+
+use crate::app::App;
+use crate::i18n;
+use crate::keymap::Action;
+use crate::mouse::HitTarget;
+use crate::setup::SetupStep;
+
+/// A screen that can be shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum View {
+    /// Pet, stats and actions
+    Main,
+    /// Key binding overlay
+    Help,
+    /// Stat graphs over time
+    StatsHistory,
+    /// Settings list
+    Settings,
+    /// Details about the pet
+    PetInfo,
+    /// Full event history
+    EventLog,
+    /// New-game wizard
+    Setup,
+}
+
+impl View {
+    /// Views reachable from the menu bar, in display order
+    pub const MENU: [View; 5] = [
+        View::Main,
+        View::StatsHistory,
+        View::PetInfo,
+        View::EventLog,
+        View::Settings,
+    ];
+
+    /// Action that opens the view from the menu bar
+    pub fn open_action(self) -> Action {
+        match self {
+            View::Main | View::Setup => Action::ShowMain,
+            View::Help => Action::Help,
+            View::StatsHistory => Action::ShowStats,
+            View::Settings => Action::ShowSettings,
+            View::PetInfo => Action::ShowPetInfo,
+            View::EventLog => Action::ShowEventLog,
+        }
+    }
+
+    /// Title shown in the menu bar
+    pub fn title(self) -> &'static str {
+        i18n::lookup(match self {
+            View::Main => "view.main",
+            View::Help => "view.help",
+            View::StatsHistory => "view.stats_history",
+            View::Settings => "view.settings",
+            View::PetInfo => "view.pet_info",
+            View::EventLog => "view.event_log",
+            View::Setup => "view.setup",
+        })
+    }
+
+    /// Whether the view is drawn on top of the view below it
+    pub fn is_overlay(self) -> bool {
+        matches!(self, View::Help)
+    }
+}
+
+/// Navigation stack plus per-view cursor state
+#[derive(Debug, Clone)]
+pub struct Navigation {
+    stack: Vec<View>,
+    /// Selected row in the settings view
+    pub settings_selected: usize,
+    /// Scroll offset of the event log view
+    pub event_log_scroll: usize,
+    /// Index into [`JournalFilter::all`] used by the event log view
+    pub event_log_filter: usize,
+    /// Search text of the event log view
+    pub event_log_search: String,
+    /// Whether keys are currently typed into the search box
+    pub editing_search: bool,
+}
+
+impl Navigation {
+    /// Start on the main view
+    pub fn new() -> Self {
+        Self {
+            stack: vec![View::Main],
+            settings_selected: 0,
+            event_log_scroll: 0,
+            event_log_filter: 0,
+            event_log_search: String::new(),
+            editing_search: false,
+        }
+    }
+
+    /// The view on top of the stack
+    pub fn current(&self) -> View {
+        *self.stack.last().unwrap_or(&View::Main)
+    }
+
+    /// The topmost full-screen view (below any overlay)
+    pub fn base(&self) -> View {
+        self.stack
+            .iter()
+            .rev()
+            .copied()
+            .find(|view| !view.is_overlay())
+            .unwrap_or(View::Main)
+    }
+
+    /// Open a view, returning to it if it is already on the stack
+    pub fn open(&mut self, view: View) {
+        if let Some(pos) = self.stack.iter().position(|v| *v == view) {
+            self.stack.truncate(pos + 1);
+        } else {
+            self.stack.push(view);
+        }
+    }
+
+    /// Go back to the previous view; the main view is never popped
+    pub fn back(&mut self) {
+        if self.stack.len() > 1 {
+            self.stack.pop();
+        }
+    }
+
+    /// Scroll the event log towards newer entries
+    pub fn scroll_event_log_up(&mut self) {
+        self.event_log_scroll = self.event_log_scroll.saturating_sub(1);
+    }
+
+    /// Scroll the event log towards older entries, stopping at the last one
+    pub fn scroll_event_log_down(&mut self, len: usize) {
+        self.event_log_scroll = (self.event_log_scroll + 1).min(len.saturating_sub(1));
+    }
+
+    /// Open or close the help overlay
+    pub fn toggle_help(&mut self) {
+        if self.current() == View::Help {
+            self.back();
+        } else {
+            self.open(View::Help);
+        }
+    }
+}
+
+impl Default for Navigation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Render the current view (and any overlay) into `area`
+pub fn render(frame: &mut Frame, app: &App, area: Rect) {
+    render_view(frame, app, app.navigation.base(), area);
+
+    if app.navigation.current().is_overlay() {
+        // Clicking anywhere closes the overlay instead of reaching the view below
+        app.hit_map
+            .borrow_mut()
+            .add(frame.area(), HitTarget::Action(Action::Back));
+        render_view(frame, app, app.navigation.current(), area);
+    }
+}
+
+fn render_view(frame: &mut Frame, app: &App, view: View, area: Rect) {
+    match view {
+        View::Main => main::render(frame, app, area),
+        View::Help => help::render(frame, app, area),
+        View::StatsHistory => stats_history::render(frame, app, area),
+        View::Settings => settings::render(frame, app, area),
+        View::PetInfo => pet_info::render(frame, app, area),
+        View::EventLog => event_log::render(frame, app, area),
+        View::Setup => setup::render(frame, app, area),
+    }
+}
+
+/// Whether key presses should go to a text field instead of the keymap
+pub fn wants_text_input(app: &App) -> bool {
+    match app.navigation.current() {
+        View::EventLog => app.navigation.editing_search,
+        View::Setup => app.setup.step == SetupStep::Name,
+        _ => false,
+    }
+}
+
+/// Route a raw key press to the focused text field; `false` when the field
+/// has no use for it and the keymap should have it
+pub fn handle_text_input(app: &mut App, key: KeyEvent) -> bool {
+    match app.navigation.current() {
+        View::EventLog => event_log::handle_text_input(app, key),
+        View::Setup => setup::handle_text_input(app, key),
+        _ => false,
+    }
+}
+
+/// Whether a key press types its character: Shift is the only modifier
+/// that doesn't make it a chord
+pub fn types_char(key: &KeyEvent) -> bool {
+    key.modifiers.difference(KeyModifiers::SHIFT).is_empty()
+}
+
+/// Route an action to navigation or the current view
+pub fn handle_action(app: &mut App, action: Action) {
+    app.notice = None;
+    match action {
+        Action::Quit => app.quit(),
+        // The wizard can't be left until a new game starts
+        _ if app.navigation.current() == View::Setup => setup::handle_action(app, action),
+        Action::Help => app.navigation.toggle_help(),
+        Action::Back => app.navigation.back(),
+        // The help overlay swallows everything else
+        _ if app.navigation.current() == View::Help => {}
+        Action::Pause => app.toggle_pause(),
+        Action::Vacation => app.toggle_vacation(),
+        Action::NextPet => app.select_next_pet(),
+        Action::Adopt => app.adopt(),
+        Action::Match => app.match_pets(),
+        Action::ShowMain => app.navigation.open(View::Main),
+        Action::ShowStats => app.navigation.open(View::StatsHistory),
+        Action::ShowPetInfo => app.navigation.open(View::PetInfo),
+        Action::ShowEventLog => app.navigation.open(View::EventLog),
+        Action::ShowSettings => app.navigation.open(View::Settings),
+        _ => match app.navigation.current() {
+            View::Main => main::handle_action(app, action),
+            View::Settings => settings::handle_action(app, action),
+            View::EventLog => event_log::handle_action(app, action),
+            View::Help | View::StatsHistory | View::PetInfo | View::Setup => {}
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn open_existing_view_unwinds_stack() {
+        let mut nav = Navigation::new();
+        nav.open(View::StatsHistory);
+        nav.open(View::PetInfo);
+        nav.open(View::StatsHistory);
+
+        assert_eq!(nav.current(), View::StatsHistory);
+        nav.back();
+        assert_eq!(nav.current(), View::Main);
+    }
+
+    #[test]
+    fn back_never_pops_main() {
+        let mut nav = Navigation::new();
+        nav.back();
+        assert_eq!(nav.current(), View::Main);
+    }
+
+    #[test]
+    fn help_overlay_keeps_base_view() {
+        let mut nav = Navigation::new();
+        nav.open(View::Settings);
+        nav.toggle_help();
+
+        assert_eq!(nav.current(), View::Help);
+        assert_eq!(nav.base(), View::Settings);
+
+        nav.toggle_help();
+        assert_eq!(nav.current(), View::Settings);
+    }
+
+    #[test]
+    fn chords_skip_the_search_box() {
+        use ratatui::crossterm::event::KeyCode;
+
+        let mut app = App::new();
+        app.navigation.open(View::EventLog);
+        app.navigation.editing_search = true;
+
+        let key = |c, modifiers| KeyEvent::new(KeyCode::Char(c), modifiers);
+        assert!(handle_text_input(&mut app, key('a', KeyModifiers::NONE)));
+        assert!(handle_text_input(&mut app, key('B', KeyModifiers::SHIFT)));
+        assert!(!handle_text_input(
+            &mut app,
+            key('c', KeyModifiers::CONTROL)
+        ));
+        assert!(!handle_text_input(&mut app, key('x', KeyModifiers::ALT)));
+        assert_eq!(app.navigation.event_log_search, "aB");
+    }
+}
@@ -0,0 +1,107 @@
+//! Pet info view: details that don't fit on the main screen
+
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::Style,
+    text::Line,
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+
+use crate::app::App;
+use crate::i18n::{self, tr};
+use crate::pet::{LifeStage, Pet, PetState};
+
+/// Render the pet info view
+pub fn render(frame: &mut Frame, app: &App, area: Rect) {
+    let pet = app.pet();
+
+    let state = match pet.state {
+        PetState::Normal => i18n::lookup("info.awake").to_string(),
+        PetState::Sleeping { since } => tr!("info.sleeping", seconds = since.elapsed().as_secs()),
+        PetState::Sick { since } => tr!("info.sick", seconds = since.elapsed().as_secs()),
+        PetState::Dead => i18n::lookup("info.dead").to_string(),
+    };
+    let next = match (pet.stage.next(), pet.next_stage_at()) {
+        (Some(next), Some(at)) => tr!(
+            "info.next_stage",
+            stage = next.display_name(),
+            seconds = at.saturating_sub(pet.age_seconds)
+        ),
+        (Some(next), None) => tr!(
+            "info.next_hatch",
+            stage = next.display_name(),
+            percent = pet.get_incubation()
+        ),
+        (None, _) => i18n::lookup("info.fully_grown").to_string(),
+    };
+
+    let mut lines = aligned(vec![
+        ("info.name", pet.name.clone()),
+        ("info.species", pet.species().label().to_string()),
+        ("info.coat", pet.genome.coat.label().to_string()),
+        ("info.traits", traits(pet)),
+        ("info.stage", pet.stage.display_name().to_string()),
+        ("info.state", state),
+        ("info.age", pet.age_formatted()),
+        ("info.next", next),
+    ]);
+
+    lines.push(Line::from(""));
+    let percent = |value: u8| format!("{}%", value);
+    if pet.stage == LifeStage::Egg {
+        lines.extend(aligned(vec![
+            ("stat.incubation", percent(pet.get_incubation())),
+            ("stat.warmth", percent(pet.get_warmth())),
+            ("stat.egg_health", percent(pet.get_egg_health())),
+        ]));
+    } else {
+        let stats = &pet.stats;
+        lines.extend(aligned(vec![
+            ("stat.hunger", percent(stats.hunger.value())),
+            ("stat.happiness", percent(stats.happiness.value())),
+            ("stat.energy", percent(stats.energy.value())),
+            ("stat.health", percent(stats.health.value())),
+            ("stat.hygiene", percent(stats.hygiene.value())),
+        ]));
+    }
+
+    let info = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(i18n::lookup("panel.pet_info"))
+                .borders(Borders::ALL)
+                .style(Style::default().fg(app.settings.theme().info_border)),
+        )
+        .wrap(Wrap { trim: true });
+
+    frame.render_widget(info, area);
+}
+
+/// `Label: value` lines with the values lined up
+fn aligned(rows: Vec<(&'static str, String)>) -> Vec<Line<'static>> {
+    let width = rows
+        .iter()
+        .map(|(key, _)| i18n::lookup(key).chars().count())
+        .max()
+        .unwrap_or(0);
+    rows.into_iter()
+        .map(|(key, value)| {
+            let label = format!("{}:", i18n::lookup(key));
+            Line::from(format!("{:<pad$} {}", label, value, pad = width + 1))
+        })
+        .collect()
+}
+
+/// Inherited stat modifiers, like `Hunger +5, Energy -10`
+fn traits(pet: &Pet) -> String {
+    let entries = pet.genome.modifiers.entries();
+    if entries.is_empty() {
+        return i18n::lookup("info.no_traits").to_string();
+    }
+    entries
+        .iter()
+        .map(|(stat, value)| format!("{} {:+}", i18n::lookup(stat), value))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,90 @@
+//! Settings view
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Paragraph, Row, Table, TableState},
+};
+
+use crate::app::App;
+use crate::keymap::Action;
+use crate::mouse::HitTarget;
+use crate::settings::SettingItem;
+
+/// Render the settings list
+pub fn render(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = app.settings.theme();
+    let warnings = &app.settings.themes.warnings;
+
+    // Problems with theme files are listed below the settings
+    let area = if warnings.is_empty() {
+        area
+    } else {
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(3),
+                Constraint::Length(warnings.len() as u16 + 2),
+            ])
+            .split(area);
+        let list = Paragraph::new(warnings.join("\n"))
+            .style(Style::default().fg(theme.warning))
+            .block(
+                Block::default()
+                    .title(" Theme files ")
+                    .borders(Borders::ALL),
+            );
+        frame.render_widget(list, layout[1]);
+        layout[0]
+    };
+
+    let rows = SettingItem::ALL
+        .iter()
+        .map(|item| Row::new([item.label().to_string(), app.settings.value_label(*item)]));
+
+    let table = Table::new(rows, [Constraint::Length(20), Constraint::Min(10)])
+        .block(
+            Block::default()
+                .title(" Settings - [Up/Down] select, [Enter] change ")
+                .borders(Borders::ALL)
+                .style(Style::default().fg(theme.settings_border)),
+        )
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+
+    let mut state = TableState::default().with_selected(Some(app.navigation.settings_selected));
+    frame.render_stateful_widget(table, area, &mut state);
+
+    // One clickable row per item inside the border
+    let mut hits = app.hit_map.borrow_mut();
+    for index in 0..SettingItem::ALL.len() {
+        let row = Rect::new(
+            area.x + 1,
+            area.y + 1 + index as u16,
+            area.width.saturating_sub(2),
+            1,
+        );
+        if row.bottom() < area.bottom() {
+            hits.add(row, HitTarget::Setting(index));
+        }
+    }
+}
+
+/// Move the selection and change settings
+pub fn handle_action(app: &mut App, action: Action) {
+    let selected = &mut app.navigation.settings_selected;
+
+    match action {
+        Action::Up => *selected = selected.saturating_sub(1),
+        Action::Down => *selected = (*selected + 1).min(SettingItem::ALL.len() - 1),
+        Action::Select => {
+            let item = SettingItem::ALL[*selected];
+            app.settings.toggle(item);
+            if let Err(err) = app.settings.remember(item) {
+                tracing::warn!("could not save settings: {}", err);
+            }
+        }
+        _ => {}
+    }
+}
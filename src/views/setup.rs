@@ -0,0 +1,156 @@
+//! New-game wizard: name, egg and difficulty, one step at a time
+
+use ratatui::{
+    Frame,
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+use crate::app::App;
+use crate::difficulty::Difficulty;
+use crate::i18n::{self, tr};
+use crate::keymap::Action;
+use crate::pet::Species;
+use crate::setup::SetupStep;
+use crate::ui;
+
+/// Render the current step of the wizard
+pub fn render(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = app.settings.theme();
+    let setup = &app.setup;
+    let popup = ui::centered_rect(60, 70, area);
+    frame.render_widget(Clear, popup);
+
+    let selected = Style::default()
+        .fg(theme.highlight)
+        .add_modifier(Modifier::BOLD);
+    let mut lines = vec![
+        Line::from(tr!(
+            "setup.step",
+            number = setup.step.number(),
+            total = SetupStep::ALL.len(),
+            title = setup.step.title()
+        )),
+        Line::from(""),
+    ];
+
+    match setup.step {
+        SetupStep::Name => {
+            lines.push(Line::styled(format!("> {}_", setup.name), selected));
+            if let Some(err) = setup.error {
+                lines.push(Line::styled(
+                    err.to_string(),
+                    Style::default().fg(theme.warning),
+                ));
+            }
+        }
+        SetupStep::Species => {
+            lines.extend(choices(
+                &Species::ALL,
+                setup.species,
+                Species::label,
+                selected,
+            ));
+            let ears = setup.species.ears();
+            lines.push(Line::from(""));
+            for art in [
+                format!(" {} ", ears),
+                "( o.o )".to_string(),
+                " > ^ < ".to_string(),
+            ] {
+                lines.push(Line::styled(art, Style::default().fg(theme.pet_art)));
+            }
+        }
+        SetupStep::Difficulty => {
+            lines.extend(choices(
+                &Difficulty::ALL,
+                setup.difficulty,
+                Difficulty::label,
+                selected,
+            ));
+            lines.push(Line::from(""));
+            lines.push(Line::from(setup.difficulty.description()));
+        }
+    }
+
+    lines.push(Line::from(""));
+    let hint = match setup.step {
+        SetupStep::Name => i18n::lookup("setup.name_hint"),
+        SetupStep::Species | SetupStep::Difficulty => i18n::lookup("setup.choice_hint"),
+    };
+    lines.push(Line::styled(hint, Style::default().fg(theme.text_dim)));
+
+    let wizard = Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: false })
+        .block(
+            Block::default()
+                .title(i18n::lookup("setup.title"))
+                .borders(Borders::ALL)
+                .style(Style::default().fg(theme.settings_border)),
+        );
+    frame.render_widget(wizard, popup);
+}
+
+/// One line per option, the selected one marked and highlighted
+fn choices<T: Copy + PartialEq>(
+    all: &[T],
+    current: T,
+    label: fn(T) -> &'static str,
+    selected: Style,
+) -> Vec<Line<'static>> {
+    all.iter()
+        .map(|item| {
+            if *item == current {
+                Line::styled(format!("> {} <", label(*item)), selected)
+            } else {
+                Line::from(label(*item))
+            }
+        })
+        .collect()
+}
+
+/// Type the pet's name while on the name step, returning whether the key
+/// was used
+pub fn handle_text_input(app: &mut App, key: KeyEvent) -> bool {
+    match key.code {
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => app.quit(),
+        KeyCode::Enter => confirm(app),
+        KeyCode::Esc => back(app),
+        KeyCode::Tab => app.setup.randomize_name(),
+        KeyCode::Backspace => app.setup.pop_char(),
+        KeyCode::Char(c) if super::types_char(&key) => app.setup.push_char(c),
+        _ => return false,
+    }
+    true
+}
+
+/// Choose an option, move between steps and start the game
+pub fn handle_action(app: &mut App, action: Action) {
+    match action {
+        Action::Up | Action::Left => app.setup.cycle(false),
+        Action::Down | Action::Right => app.setup.cycle(true),
+        Action::Select => confirm(app),
+        Action::Back => back(app),
+        _ => {}
+    }
+}
+
+/// Go back a step, or leave an adoption wizard from its first step
+fn back(app: &mut App) {
+    if app.setup.step == SetupStep::Name {
+        app.cancel_setup();
+    } else {
+        app.setup.back();
+    }
+}
+
+/// Accept the current step, starting the game after the last one
+fn confirm(app: &mut App) {
+    if let Ok(true) = app.setup.confirm() {
+        app.start_game();
+    }
+}
@@ -0,0 +1,75 @@
+//! Stats history view: a sparkline per stat
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    widgets::{Block, Borders, Paragraph, Sparkline, Wrap},
+};
+
+use crate::app::App;
+use crate::i18n;
+use crate::stats::{StatValue, Stats};
+
+/// Picks one stat out of a snapshot
+type StatPicker = fn(&Stats) -> StatValue;
+
+/// Render the stats history view
+pub fn render(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = app.settings.theme();
+    let block = Block::default()
+        .title(" Stats History ")
+        .borders(Borders::ALL)
+        .style(Style::default().fg(theme.history_border));
+    frame.render_widget(block, area);
+
+    let inner = Layout::default()
+        .constraints([Constraint::Percentage(100)])
+        .margin(1)
+        .split(area)[0];
+
+    if app.companion().stats_history.is_empty() {
+        let empty = Paragraph::new(i18n::lookup("panel.no_history"))
+            .style(Style::default().fg(theme.text_muted))
+            .wrap(Wrap { trim: true });
+        frame.render_widget(empty, inner);
+        return;
+    }
+
+    let series: [(&str, StatPicker, Color); 5] = [
+        (i18n::lookup("stat.hunger"), |s| s.hunger, theme.hunger),
+        (
+            i18n::lookup("stat.happiness"),
+            |s| s.happiness,
+            theme.happiness,
+        ),
+        (i18n::lookup("stat.energy"), |s| s.energy, theme.energy),
+        (i18n::lookup("stat.health"), |s| s.health, theme.health),
+        (i18n::lookup("stat.hygiene"), |s| s.hygiene, theme.hygiene),
+    ];
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Ratio(1, 5); 5])
+        .split(inner);
+
+    for ((label, pick, color), row) in series.into_iter().zip(rows.iter()) {
+        let data = app.companion().stats_history.series(pick);
+        // Show the most recent samples that fit in the row
+        let visible = row.width.saturating_sub(2) as usize;
+        let data = &data[data.len().saturating_sub(visible)..];
+        let current = data.last().copied().unwrap_or(0);
+
+        let sparkline = Sparkline::default()
+            .block(
+                Block::default()
+                    .title(format!("{} ({}%)", label, current))
+                    .borders(Borders::NONE),
+            )
+            .data(data)
+            .max(StatValue::MAX as u64)
+            .style(Style::default().fg(color));
+
+        frame.render_widget(sparkline, *row);
+    }
+}
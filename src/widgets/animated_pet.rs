@@ -1,10 +1,22 @@
-use ratatui::{buffer::Buffer, layout::Rect, style::Style, widgets::Widget};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    widgets::Widget,
+};
+
+use unicode_width::UnicodeWidthStr;
 
 use crate::animation::engine::AnimationEngine;
+use crate::genome::{Coat, Genome};
+use crate::pet::Species;
+use crate::theme::Theme;
 
 #[derive(Debug)]
 pub struct AnimatedPet {
     engine: AnimationEngine,
+    species: Species,
+    coat: Coat,
 }
 
 impl AnimatedPet {
@@ -12,7 +24,18 @@ impl AnimatedPet {
         let mut engine = AnimationEngine::new();
         engine.request(crate::animation::types::AnimationType::IdleNeutral);
 
-        Self { engine }
+        Self {
+            engine,
+            species: Species::default(),
+            coat: Coat::default(),
+        }
+    }
+
+    /// Draw the built-in art with the ears and coat a pet inherited
+    pub fn with_genome(mut self, genome: &Genome) -> Self {
+        self.species = genome.species;
+        self.coat = genome.coat;
+        self
     }
 
     pub fn trigger(&mut self, anim_type: crate::animation::types::AnimationType) {
@@ -42,6 +65,11 @@ impl AnimatedPet {
         self.engine
             .request(crate::animation::types::AnimationType::IdleSleeping);
     }
+
+    /// Widget drawing the pet in the colors of a theme
+    pub fn themed<'a>(&'a self, theme: &'a Theme) -> ThemedPet<'a> {
+        ThemedPet { pet: self, theme }
+    }
 }
 
 impl Default for AnimatedPet {
@@ -50,21 +78,45 @@ impl Default for AnimatedPet {
     }
 }
 
-impl Widget for &AnimatedPet {
+/// An [`AnimatedPet`] paired with the theme it is drawn in
+pub struct ThemedPet<'a> {
+    pet: &'a AnimatedPet,
+    theme: &'a Theme,
+}
+
+impl Widget for ThemedPet<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
+        let engine = &self.pet.engine;
         let fallback = "  (?.?)  ".to_string();
         let binding = [fallback];
-        let art = self.engine.current_art().unwrap_or(&binding);
+        let art = engine.current_art().unwrap_or(&binding);
 
         let art_height = art.len() as u16;
-        let art_width = art.first().map(|s| s.len() as u16).unwrap_or(0);
+        // Display width, not bytes: art may contain multi-byte characters
+        let art_width = art.iter().map(|s| s.width() as u16).max().unwrap_or(0);
 
         let y_offset = area.height.saturating_sub(art_height) / 2;
         let x_offset = area.width.saturating_sub(art_width) / 2;
 
-        let mut style = Style::default();
-        if let Some(color) = self.engine.current_color() {
-            style = style.fg(color);
+        let tint = engine.current_color();
+        // High contrast themes keep their own color so the pet stays legible
+        let coat = if self.theme.high_contrast {
+            self.theme.pet_art
+        } else {
+            self.theme.coat_color(self.pet.coat)
+        };
+        let color = match tint {
+            Some(tint) => self.theme.art_color(tint),
+            None => coat,
+        };
+        let mut style = Style::default().fg(color);
+        if self.theme.high_contrast {
+            // Tinted frames (e.g. sleeping) are dimmed rather than just recolored
+            style = style.add_modifier(if tint.is_some() {
+                Modifier::DIM
+            } else {
+                Modifier::BOLD
+            });
         }
 
         for (i, line) in art.iter().enumerate() {
@@ -72,21 +124,26 @@ impl Widget for &AnimatedPet {
             let x = area.x + x_offset;
 
             if y < area.y + area.height {
+                let line = line.replace(Species::CAT_EARS, self.pet.species.ears());
                 buf.set_string(x, y, line, style);
             }
         }
 
-        for particle in self.engine.particles() {
+        for particle in engine.particles() {
             let (px, py) = particle.position();
-            let abs_x = area.x + x_offset + px;
-            let abs_y = area.y + y_offset + py;
+            let abs_x = (area.x + x_offset) as i32 + px as i32;
+            let abs_y = (area.y + y_offset) as i32 + py as i32;
 
-            if abs_x < area.x + area.width && abs_y < area.y + area.height {
+            let inside_x = abs_x >= area.x as i32 && abs_x < area.right() as i32;
+            let inside_y = abs_y >= area.y as i32 && abs_y < area.bottom() as i32;
+            if inside_x && inside_y {
                 buf.set_string(
-                    abs_x,
-                    abs_y,
+                    abs_x as u16,
+                    abs_y as u16,
                     particle.spec.symbol.to_string(),
-                    Style::default().fg(particle.spec.color),
+                    Style::default()
+                        .fg(self.theme.art_color(particle.spec.color))
+                        .add_modifier(self.theme.emphasis()),
                 );
             }
         }